@@ -1,5 +1,6 @@
-use std::fs::File;
 use std::io::{BufRead, BufReader, Error, ErrorKind, Read};
+use aoc_2020_common::common::load_file;
+use tracing::info_span;
 
 #[macro_use]
 extern crate itertools;
@@ -30,15 +31,15 @@ fn find_answer_part2(numbers: &[i64]) -> Option<i64> {
 }
 
 fn main() {
-    let file = File::open("./input/01.txt").expect("Failed to open file");
-    let numbers = read_numbers(file).expect("Failed to parse file");
+    let input = load_file("./input/01.txt");
+    let numbers = info_span!("parse").in_scope(|| read_numbers(input.as_bytes())).expect("Failed to parse file");
 
-    match find_answer(&numbers) {
+    match info_span!("part_1").in_scope(|| find_answer(&numbers)) {
         Some(n) => println!("{}", n),
         None => println!("Input doesn't contain solution for part 1"),
     }
 
-    match find_answer_part2(&numbers) {
+    match info_span!("part_2").in_scope(|| find_answer_part2(&numbers)) {
         Some(n) => println!("{}", n),
         None => println!("Input doesn't contain solution for part 2"),
     }
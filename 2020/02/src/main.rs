@@ -1,5 +1,6 @@
-use std::fs::File;
 use std::io::{BufRead, BufReader, Error, Read};
+use aoc_2020_common::common::load_file;
+use tracing::info_span;
 
 fn read_lines<R: Read>(io: R) -> Result<Vec<String>, Error> {
     BufReader::new(io).lines().collect()
@@ -45,15 +46,15 @@ impl PasswordTest<'_> {
 
 fn main() {
     // Parse input into PasswordTests
-    let file = File::open("./input/1.txt").expect("Failed to open file");
-    let lines = read_lines(file).expect("Incorrect input");
-    let tests = lines.iter().map(|s| PasswordTest::from_str(s));
+    let input = load_file("./input/1.txt");
+    let lines = read_lines(input.as_bytes()).expect("Incorrect input");
+    let tests: Vec<PasswordTest> = info_span!("parse").in_scope(|| lines.iter().map(|s| PasswordTest::from_str(s)).collect());
 
     // Check how many PasswordTests pass
     // NOTE: cannot write the filter call as `.filter(PasswordTest::part1)` because of this known issue:
     // https://users.rust-lang.org/t/explanation-for-difference-between-filter-func-and-filter-x-func-x/14945/5
-    let answer1 = tests.clone().filter(|test| test.part1()).count();
-    let answer2 = tests.filter(|test| test.part2()).count();
+    let answer1 = info_span!("part_1").in_scope(|| tests.iter().filter(|test| test.part1()).count());
+    let answer2 = info_span!("part_2").in_scope(|| tests.iter().filter(|test| test.part2()).count());
 
     println!("{}", answer1);
     println!("{}", answer2);
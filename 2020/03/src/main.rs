@@ -2,6 +2,8 @@ use std::ops::{AddAssign, Index};
 use std::str::FromStr;
 use std::convert::TryFrom;
 use aoc_2020_common::common::load_file;
+use aoc_common::tile::Tile;
+use tracing::info_span;
 
 
 #[derive(Debug)]
@@ -24,24 +26,14 @@ impl AddAssign<&Slope> for Point {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Tile)]
 enum Square {
+    #[tile('.')]
     Open,
+    #[tile('#')]
     Tree,
 }
 
-impl TryFrom<char> for Square {
-    type Error = String;
-
-    fn try_from(c: char) -> Result<Self, Self::Error> {
-        match c {
-            '#' => Ok(Self::Tree),
-            '.' => Ok(Self::Open),
-            _ => Err(format!("Can't parse Square from `{}`", c)),
-        }
-    }
-}
-
 #[derive(Debug)]
 struct World {
     width: usize,
@@ -82,8 +74,8 @@ impl FromStr for World {
             .into_iter()
             .flat_map(str::chars)
             .map(Square::try_from)
-            .filter_map(Result::ok)
-            .collect();
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
 
         Ok(World {
             width,
@@ -103,10 +95,10 @@ impl Index<&Point> for World {
 
 fn main() {
     let input = load_file("./input/1.txt");
-    let world = World::from_str(&input).expect("Couldn't parse world.");
+    let world = info_span!("parse").in_scope(|| World::from_str(&input)).expect("Couldn't parse world.");
 
     // Part 1
-    let answer1 = world.count_trees_with(&Slope { x: 3, y: 1 });
+    let answer1 = info_span!("part_1").in_scope(|| world.count_trees_with(&Slope { x: 3, y: 1 }));
     println!("{:#?}", answer1);
 
     // Part 2
@@ -118,9 +110,9 @@ fn main() {
         Slope { x: 1, y: 2 },
     ];
 
-    let answer2 = slopes
+    let answer2 = info_span!("part_2").in_scope(|| slopes
         .iter()
-        .map(|slope| world.count_trees_with(&slope))
-        .product::<usize>();
+        .map(|slope| world.count_trees_with(slope) as u64)
+        .product::<u64>());
     println!("{:#?}", answer2);
 }
@@ -2,6 +2,7 @@ extern crate regex;
 use regex::Regex;
 use std::str::FromStr;
 use aoc_2020_common::common::load_file;
+use tracing::info_span;
 
 #[derive(Debug, PartialEq)]
 enum Field {
@@ -134,8 +135,8 @@ impl FromStr for Passports {
 fn main() {
     let input = load_file("./input/1.txt");
 
-    let passports = Passports::from_str(&input).expect("Couldn't parse passports");
+    let passports = info_span!("parse").in_scope(|| Passports::from_str(&input)).expect("Couldn't parse passports");
 
-    println!("{}", passports.valid_count_1());
-    println!("{}", passports.valid_count_2());
+    println!("{}", info_span!("part_1").in_scope(|| passports.valid_count_1()));
+    println!("{}", info_span!("part_2").in_scope(|| passports.valid_count_2()));
 }
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 use aoc_2020_common::common::load_file;
+use aoc_common::interval::{Interval, IntervalSet};
+use tracing::info_span;
 
 #[derive(Debug)]
 struct Seat {
@@ -13,50 +15,31 @@ impl Seat {
     }
 }
 
-// both bounds are inclusive.
-#[derive(Debug)]
-struct Range {
-    lower: usize,
-    upper: usize,
-}
-
-impl Range {
-    fn take_lower_half(&mut self) {
-        let half = (self.upper - self.lower) / 2;
-        self.upper = self.lower + half;
-    }
-
-    fn take_upper_half(&mut self) {
-        let half = (self.upper - self.lower) / 2;
-        self.lower = self.lower + half + 1;
-    }
-}
-
 impl FromStr for Seat {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Determine Row
-        let mut row_range = Range { lower: 0, upper: 127};
-        for c in (&s[..(s.len()-3)]).chars() {
-            match c {
-                'F' => row_range.take_lower_half(),
-                'B' => row_range.take_upper_half(),
+        let mut row_range = Interval::new(0, 127);
+        for c in s[..(s.len()-3)].chars() {
+            row_range = match c {
+                'F' => row_range.halves().0,
+                'B' => row_range.halves().1,
                 _   => panic!("Invalid row char in input"),
             }
         }
-        let row = row_range.lower;
+        let row = row_range.start;
 
         // Determine Column
-        let mut column_range = Range { lower: 0, upper: 7};
-        for c in (&s[(s.len()-3)..]).chars() {
-            match c {
-                'L' => column_range.take_lower_half(),
-                'R' => column_range.take_upper_half(),
+        let mut column_range = Interval::new(0, 7);
+        for c in s[(s.len()-3)..].chars() {
+            column_range = match c {
+                'L' => column_range.halves().0,
+                'R' => column_range.halves().1,
                 _   => panic!("Invalid row char in input"),
             }
         }
-        let column = column_range.lower;
+        let column = column_range.start;
 
         Ok(Seat { row, column })
     }
@@ -66,29 +49,29 @@ fn main() {
     let input = load_file("./input/1.txt");
 
     // Get Seats from input
-    let seats: Vec<Seat> = input
+    let seats: Vec<Seat> = info_span!("parse").in_scope(|| input
         .lines()
-        .map(|s| Seat::from_str(s))
+        .map(Seat::from_str)
         .filter_map(Result::ok)
-        .collect();
+        .collect());
 
     // Map to IDs
-    let mut seat_ids: Vec<usize> = seats
+    let seat_ids: IntervalSet<usize> = seats
         .iter()
-        .map(|s| s.id())
+        .map(|s| Interval::point(s.id()))
         .collect();
-    seat_ids.sort_unstable();
 
     // Answer 1
-    let answer_1 = seat_ids.iter().max();
+    let answer_1 = info_span!("part_1").in_scope(|| seat_ids.max());
     if let Some(ans) = answer_1 {
         println!("{:?}", ans);
     }
 
-    // Answer 2
-    for i in 0..(seat_ids.len() - 2) {
-        if seat_ids[i+1] == seat_ids[i] + 2 {
-            println!("{:?}", seat_ids[i] + 1);
+    // Answer 2: the only empty seat between the first and last taken seat
+    let _part_2 = info_span!("part_2").entered();
+    if let Some(all_seats) = seat_ids.hull() {
+        for gap in seat_ids.gaps(all_seats) {
+            println!("{:?}", gap.start);
         }
     }
 
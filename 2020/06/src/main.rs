@@ -1,21 +1,23 @@
 use itertools::Itertools;
 use std::collections::HashMap;
 use aoc_2020_common::common::load_file;
+use tracing::info_span;
 
 fn main() {
     let input = load_file("./input/1.txt");
-    let groups = input.split("\n\n");
+    let groups = info_span!("parse").in_scope(|| input.split("\n\n"));
 
-    let answer1: usize = groups.clone()
+    let answer1: usize = info_span!("part_1").in_scope(|| groups.clone()
         .map(|s| s.to_string().replace("\n", ""))
         .map(|g| g.chars().into_iter().unique().count())
-        .sum();
+        .sum());
 
+    let _part_2 = info_span!("part_2").entered();
     let answer2: usize = groups
         .map(|group| (group.lines().count(), group))
         .map(|(answer_count, answers)| {
             let mut char_counts: HashMap<char, usize> = HashMap::new();
-            for c in answers.chars() {
+            for c in answers.chars().filter(char::is_ascii_alphabetic) {
                 let count = char_counts.entry(c).or_insert(0);
                 *count += 1;
             }
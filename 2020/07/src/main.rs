@@ -1,6 +1,7 @@
 use aoc_2020_common::common::load_file;
 use regex::Regex;
 use std::collections::HashMap;
+use tracing::info_span;
 
 /// We create two separate HashMaps, one called `can_be_contained_in`, and the other `contains`.
 /// `can_be_contained_in is ` 's key is a bag B its value a list of bags that may contain B.
@@ -57,17 +58,19 @@ fn main() {
     let target = String::from("shiny gold");
 
     // can_be_contained_in is for answer 1, contains is for answer 2
-    let (can_be_contained_in, contains) = parse_input(&input);
+    let (can_be_contained_in, contains) = info_span!("parse").in_scope(|| parse_input(&input));
 
     // Answer 1
-    let mut containers : Vec<String> = Vec::new();
-    add_to_containers(&target, &mut containers, &can_be_contained_in);
-    containers.sort_unstable();
-    containers.dedup();
-    let answer1 = containers.len();
+    let answer1 = info_span!("part_1").in_scope(|| {
+        let mut containers : Vec<String> = Vec::new();
+        add_to_containers(&target, &mut containers, &can_be_contained_in);
+        containers.sort_unstable();
+        containers.dedup();
+        containers.len()
+    });
     println!("{:#?}", answer1);
 
     // Answer 2
-    let answer2 = count_bags(&target, &contains);
+    let answer2 = info_span!("part_2").in_scope(|| count_bags(&target, &contains));
     println!("{:#?}", answer2);
 }
@@ -1,6 +1,7 @@
 use aoc_2020_common::common::load_file;
 use regex::Regex;
 use std::collections::HashSet;
+use tracing::{debug, info_span, trace};
 
 
 #[derive(Debug, Clone)]
@@ -55,6 +56,7 @@ fn run_machine_instructions(instructions: &[Instruction]) -> MachineState {
 
         // Mark instruction as visited
         visited_instructions.insert(ip);
+        trace!(ip, instruction = ?instructions[ip], accumulator = state.accumulator);
 
         // Execute instruction
         match &instructions[ip] {
@@ -74,15 +76,16 @@ fn run_machine_instructions(instructions: &[Instruction]) -> MachineState {
 
 fn main() {
     let input = load_file("./input/1.txt");
-    let instructions = parse_instructions(&input);
+    let instructions = info_span!("parse").in_scope(|| parse_instructions(&input));
 
     // Part 1 answer
-    let st = run_machine_instructions(&instructions);
+    let st = info_span!("part_1").in_scope(|| run_machine_instructions(&instructions));
     println!("{}", st.accumulator);
 
     // Part 2 answer: for every instruction, try to change NOP to JMP (or vice versa),
     // After the change, check if it does finish execution (ie, it reaches the end of the file).
     // If it does, then the answer is in our accumulator.
+    let _part_2 = info_span!("part_2").entered();
     for (index, instruction) in instructions.iter().enumerate() {
         let mut modified_instructions = instructions.to_vec();
         modified_instructions[index] = match instruction {
@@ -92,6 +95,7 @@ fn main() {
         };
 
         let st = run_machine_instructions(&modified_instructions);
+        debug!(index, finished = st.finished, accumulator = st.accumulator, "patched instruction");
 
         if st.finished {
             println!("{}", st.accumulator);
@@ -1,7 +1,8 @@
 use aoc_2020_common::common::load_file;
 use std::cmp::Ordering;
+use tracing::info_span;
 
-fn numbers_contain_summing_pair(numbers: &[usize], target: &usize) -> bool {
+fn numbers_contain_summing_pair(numbers: &[u64], target: &u64) -> bool {
     for (i, n) in numbers.iter().enumerate() {
         for (j, m) in numbers.iter().enumerate() {
             if i != j && n + m == *target {
@@ -14,14 +15,15 @@ fn numbers_contain_summing_pair(numbers: &[usize], target: &usize) -> bool {
 
 fn main() {
     let input = load_file("./input/1.txt");
-    let numbers:Vec<usize> = input
+    let numbers:Vec<u64> = info_span!("parse").in_scope(|| input
         .lines()
         .map(|s| s.parse().unwrap())
-        .collect();
+        .collect());
 
     // Part 1 answer
+    let part_1 = info_span!("part_1").entered();
     let range_size = 25;
-    let mut part_2_target: usize = 0;
+    let mut part_2_target: u64 = 0;
     for (i, n) in numbers.iter().enumerate() {
         if i > range_size {
             let preceding = &numbers[(i-range_size)..i];
@@ -33,12 +35,14 @@ fn main() {
     }
 
     assert_ne!(part_2_target, 0);
+    drop(part_1);
 
     // Part 2 answer
+    let _part_2 = info_span!("part_2").entered();
     for (i, _) in numbers.iter().enumerate() {
         for (j, _) in numbers[i..].iter().enumerate() {
             let range = &numbers[i..(i+j)];
-            match range.iter().sum::<usize>().cmp(&part_2_target) {
+            match range.iter().sum::<u64>().cmp(&part_2_target) {
                 Ordering::Less => (),
                 Ordering::Equal => {
                     let smallest = range.iter().min().unwrap();
@@ -1,218 +1,103 @@
 use aoc_2020_common::common::load_file;
-use std::ops::{Index, IndexMut};
-
-#[derive(Debug, Clone, Eq, PartialEq)]
+use aoc_common::automaton::{Automaton, Neighbourhood};
+use aoc_common::flood::Bounds;
+use aoc_common::grid::Grid;
+use aoc_common::player::{animate_requested, Player};
+use aoc_common::point::Point2;
+use aoc_common::render::{Color, Frame, Glyph, Style};
+use aoc_common::tile::Tile;
+use std::time::Duration;
+use tracing::{debug, info_span};
+
+#[derive(Debug, Clone, Eq, PartialEq, Tile)]
 enum Field {
+    #[tile('.')]
     Floor,
+    #[tile('L')]
     Seat,
+    #[tile('#')]
     Occupied,
 }
 
-impl Field {
-    fn to_char(&self) -> char {
-        match self {
-            Field::Floor => '.',
-            Field::Seat => 'L',
-            Field::Occupied => '#',
+/// Seats get taken when no visible seat is occupied,
+/// and are left when at least `tolerance` visible seats are occupied
+fn seating_rule(tolerance: usize) -> impl Fn(&Field, &[&Field]) -> Field {
+    move |field, visible| {
+        let occupied = visible.iter().filter(|&&f| *f == Field::Occupied).count();
+        match (field, occupied) {
+            (Field::Seat, 0) => Field::Occupied,
+            (Field::Occupied, x) if x >= tolerance => Field::Seat,
+            (field, _) => field.clone(),
         }
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-struct Area {
-    width: usize,
-    height: usize,
-    fields: Vec<Field>
-}
-
-impl Area {
-
-    /// Parses a `string` into an `Area`
-    fn from(s: &str) ->  Area {
-        // let lines = s.lines();
-
-        let fields: Vec<Field> = s.lines().flat_map(|s| s.chars().map(|c| {
-            match c {
-                '.' => Field::Floor,
-                'L' => Field::Seat,
-                '#' => Field::Occupied,
-                _ => panic!("Invalid char in input"),
-            }
-        })
-        ).collect();
-
-        Area {
-            width: s.lines().next().unwrap().len(),
-            height: s.lines().count(),
-            fields
-        }
-    }
-
-    /// Runs the part 1 simulation
-    fn simulation_1(&self) -> usize {
-        let mut area = self.clone();
-        loop {
-            let old_area = area.clone();
-            area = area.next_1();
-            if old_area == area { break } // chaos has stabilized
-        }
-
-        area.total_occupied_count()
-    }
-
-    /// Runs the part 2 simulation
-    fn simulation_2(&self) -> usize {
-        let mut area = self.clone();
-        loop {
-            let old_area = area.clone();
-            area = area.next_2();
-            if old_area == area { break } // chaos has stabilized
-        }
-
-        area.total_occupied_count()
-    }
-
-    /// Returns the next evolution of this area according to the `adjacent_occupied_count`
-    /// Answer for part 1
-    fn next_1(&self) -> Area {
-        let mut a = self.clone();
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let point = (x,y);
-                let occ_count = self.adjacent_occupied_count(&point);
-
-                a[point] = match (&self[point], occ_count) {
-                    (Field::Seat, 0) => Field::Occupied,
-                    (Field::Occupied, x) if x >= 4 => Field::Seat,
-                    _ => continue,
-                }
-            }
-        }
-        a
-    }
-
-    /// Returns the next evolution of this area according to the `ray_occupied_count`
-    /// Answer for part 2
-    fn next_2(&self) -> Area {
-        let mut a = self.clone();
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let point = (x,y);
-                let occ_count = self.ray_occupied_count(&point);
-
-                a[point] = match (&self[point], occ_count) {
-                    (Field::Seat, 0) => Field::Occupied,
-                    (Field::Occupied, x) if x >= 5 => Field::Seat,
-                    _ => continue,
-                }
-            }
-        }
-        a
-    }
-
-    /// Returns if a point is within the bounds of this `Area`.
-    /// Area can be safely indexed if this is true.
-    fn in_bounds(&self, point: &(isize, isize)) -> bool {
-        let (x, y) = point;
-
-        *x >= 0 && *x < self.width as isize && *y >= 0 && *y < self.height as isize
-    }
-
-    /// Returns how many adjacent neighbouring fields are `Field::Occupied`
-    fn adjacent_occupied_count(&self, point: &(usize, usize)) -> usize {
-        let (x, y) = (point.0 as isize, point.1 as isize);
-        [ // Neighboring fields
-            (x-1, y-1), (x-1, y), (x-1, y+1),
-            (x, y-1), (x, y+1),
-            (x+1, y-1), (x+1, y), (x+1, y+1),
-        ].iter()
-            .filter(|&point| {
-                if !self.in_bounds(point) { return false }
-
-                self[(point.0 as usize, point.1 as usize)] == Field::Occupied
-            })
-            .count()
-    }
-
-    /// Returns how many ray traced seats are `Field::Occupied`
-    fn ray_occupied_count(&self, point: &(usize, usize)) -> usize {
-        [ // Directions to shoot rays in
-            (-1, -1), (-1, 0), (-1, 1),
-            (0, -1), (0, 1),
-            (1, -1), (1, 0), (1, 1),
-        ].iter()
-            .filter(|delta| {
-                let (mut x, mut y) = (point.0 as isize, point.1 as isize);
-                loop {
-                    // extend ray by adding delta to acc
-                    x += delta.0; y += delta.1;
-
-                    // check if we've hit the edge of the area
-                    if !self.in_bounds(&(x, y)) { return false };
-
-                    // check current end of ray
-                    match self[(x as usize, y as usize)] {
-                        Field::Floor => continue,
-                        Field::Occupied => return true,
-                        Field::Seat => return false,
-                    };
-                }
-            })
-            .count()
-    }
-
-    /// Returns how many seats are occupied in the total area
-    fn total_occupied_count(&self) -> usize {
-        self.fields
-            .iter()
-            .filter(|&f| *f == Field::Occupied)
-            .count()
-    }
-}
-
-impl Index<(usize, usize)> for Area {
-    type Output = Field;
-
-    fn index(&self, index: (usize, usize)) -> &Self::Output {
-        if index.0 >= self.width || index.1 >= self.height {
-            panic!("Area index out of range");
-        }
-
-        &self.fields[index.0 + index.1 * self.width]
+/// Runs the simulation until chaos has stabilized, and returns how many seats are occupied
+fn simulate(area: &Grid<Field>, neighbourhood: Neighbourhood<Field>, tolerance: usize) -> usize {
+    let mut seats = Automaton::from_grid(area, neighbourhood, seating_rule(tolerance));
+    while seats.step() {
+        debug!(generation = seats.generation(), occupied = seats.count(|f| *f == Field::Occupied));
     }
+    seats.count(|f| *f == Field::Occupied)
 }
 
-impl IndexMut<(usize, usize)> for Area {
-    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
-        if index.0 >= self.width || index.1 >= self.height {
-            panic!("Area index out of range");
+/// Every generation of a simulation, until it stabilizes
+fn generations(
+    area: &Grid<Field>,
+    neighbourhood: Neighbourhood<Field>,
+    tolerance: usize,
+    title: &'static str,
+) -> impl Iterator<Item = Frame> {
+    let bounds = Bounds {
+        min: Point2::ORIGIN,
+        max: Point2::new(area.width() as i64 - 1, area.height() as i64 - 1),
+    };
+    let mut seats = Automaton::from_grid(area, neighbourhood, seating_rule(tolerance));
+    let mut stable = false;
+
+    std::iter::from_fn(move || {
+        if stable {
+            return None;
         }
-
-        &mut self.fields[index.0 + index.1 * self.width]
-    }
-}
-
-// For debugging. Unused in end result
-impl ToString for Area {
-    fn to_string(&self) -> String {
-        let mut s = String::new();
-
-        for (c, f) in self.fields.iter().enumerate() {
-            if c % self.width == 0 {
-                s.push('\n');
-            }
-            s.push(f.to_char());
-        }
-
-        s
-    }
+        let frame = Frame::from_bounds(&bounds, |p| {
+            let field = seats.get([p.x, p.y]).unwrap();
+            let style = match field {
+                Field::Floor => Style::fg(Color::GREY),
+                Field::Seat => Style::fg(Color::GREEN),
+                Field::Occupied => Style::fg(Color::RED).bold(),
+            };
+            Glyph::new(char::from(field.clone()), style)
+        })
+        .border(Style::PLAIN)
+        .title(title, Style::PLAIN.bold())
+        .caption(format!("generation {}", seats.generation()), Style::PLAIN);
+        stable = !seats.step();
+        Some(frame)
+    })
 }
 
-
 fn main() {
     let input = load_file("./input/1.txt");
-    let area = Area::from(&input);
+    let area = info_span!("parse").in_scope(|| Grid::parse_tiles(&input)).expect("Invalid char in input");
+
+    if animate_requested() {
+        let line_of_sight = Neighbourhood::LineOfSight(|f| *f == Field::Floor);
+        let frames = generations(&area, Neighbourhood::Moore, 4, "part 1").chain(generations(
+            &area,
+            line_of_sight,
+            5,
+            "part 2",
+        ));
+        Player::new()
+            .delay(Duration::from_millis(150))
+            .play(frames)
+            .expect("Couldn't animate the seats");
+    }
 
-    println!("{}", area.simulation_1());
-    println!("{}", area.simulation_2());
+    // Part 1 looks at adjacent seats, part 2 at the first seat in every direction
+    println!("{}", info_span!("part_1").in_scope(|| simulate(&area, Neighbourhood::Moore, 4)));
+    println!(
+        "{}",
+        info_span!("part_2").in_scope(|| simulate(&area, Neighbourhood::LineOfSight(|f| *f == Field::Floor), 5))
+    );
 }
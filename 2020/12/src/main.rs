@@ -3,6 +3,7 @@ use num_enum::IntoPrimitive;
 use num_enum::TryFromPrimitive;
 use std::str::FromStr;
 use std::convert::TryFrom;
+use tracing::{info_span, trace};
 
 // NOTE: I really dislike the extensibility used for this day.
 
@@ -124,23 +125,31 @@ impl FromStr for Instruction {
 
 fn main() {
     let input = load_file("./input/1.txt");
-    let instructions: Vec<Instruction> = input
+    let instructions: Vec<Instruction> = info_span!("parse").in_scope(|| input
         .lines()
         .map(|s| Instruction::from_str(s))
         .filter_map(Result::ok)
-        .collect();
+        .collect());
 
     // Part 1 answer
-    let mut position = Position::START;
-    for instruction in &instructions {
-        position.apply_instruction_1(instruction);
-    }
-    println!("{:?}", position.manhattan_distance());
+    let part_1 = info_span!("part_1").in_scope(|| {
+        let mut position = Position::START;
+        for instruction in &instructions {
+            position.apply_instruction_1(instruction);
+            trace!(?instruction, ?position);
+        }
+        position.manhattan_distance()
+    });
+    println!("{:?}", part_1);
 
     // Part 2 answer
-    let mut position_2 = Position::START;
-    for instruction in &instructions {
-        position_2.apply_instruction_2(instruction);
-    }
-    println!("{:?}", position_2.manhattan_distance());
+    let part_2 = info_span!("part_2").in_scope(|| {
+        let mut position_2 = Position::START;
+        for instruction in &instructions {
+            position_2.apply_instruction_2(instruction);
+            trace!(?instruction, position = ?position_2);
+        }
+        position_2.manhattan_distance()
+    });
+    println!("{:?}", part_2);
 }
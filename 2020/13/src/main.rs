@@ -2,7 +2,8 @@ use aoc_2020_common::common::load_file;
 use std::str::FromStr;
 use crate::Bus::Unavailable;
 use std::num::ParseIntError;
-use ring_algorithm::chinese_remainder_theorem;
+use aoc_common::numtheory::crt;
+use tracing::info_span;
 
 
 /// Part 1, Implemented using a custom Iterator
@@ -130,20 +131,18 @@ fn parse_busses(s: &str) -> Vec<Bus2> {
         .collect()
 }
 
-fn part_2(input: &str) -> u64 {
+fn part_2(input: &str) -> i64 {
     let busses = parse_busses(input);
 
-    let (residues, modulii): (Vec<i64>, Vec<i64>) = busses
-        .iter()
-        .map(Bus2::residues_modulii)
-        .unzip();
+    let (timestamp, _) = crt(busses.iter().map(Bus2::residues_modulii))
+        .expect("busses never line up");
 
-    chinese_remainder_theorem(&residues, &modulii).unwrap() as u64
+    timestamp
 }
 
 fn main() {
     let input = load_file("./input/1.txt");
 
-    println!("{}", part_1(&input));
-    println!("{}", part_2(&input));
+    println!("{}", info_span!("part_1").in_scope(|| part_1(&input)));
+    println!("{}", info_span!("part_2").in_scope(|| part_2(&input)));
 }
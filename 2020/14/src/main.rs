@@ -2,6 +2,7 @@ use aoc_2020_common::common::load_file;
 use std::str::FromStr;
 use regex::Regex;
 use std::collections::HashMap;
+use tracing::{info_span, trace};
 
 #[macro_use]
 extern crate lazy_static;
@@ -51,12 +52,6 @@ impl Mask {
     fn apply(&self, target: u64) -> u64 {
         (target | self.ones) & self.zeros
     }
-
-    #[allow(dead_code)]
-    fn print(&self) {
-        println!("{:64b}", self.zeros);
-        println!("{:64b}", self.ones);
-    }
 }
 
 impl FromStr for Mask {
@@ -83,11 +78,13 @@ impl FromStr for Mask {
 fn main() {
     let input = load_file("./input/1.txt");
 
-    let instructions: Vec<Instruction> = input
+    let instructions: Vec<Instruction> = info_span!("parse").in_scope(|| input
         .lines()
         .map(Instruction::from_str)
         .filter_map(Result::ok)
-        .collect();
+        .collect());
+
+    let _part_1 = info_span!("part_1").entered();
 
     // Machine state
     let mut memory: HashMap<u64, u64> = HashMap::new();
@@ -95,9 +92,14 @@ fn main() {
 
     for instruction in &instructions {
         match instruction {
-            Instruction::MASK(m) => mask = m.clone(),
+            Instruction::MASK(m) => {
+                mask = m.clone();
+                trace!("zeros {:064b}", mask.zeros);
+                trace!("ones  {:064b}", mask.ones);
+            },
             Instruction::MEM(addr, value) => {
-                *memory.entry(*addr).or_insert(0) = mask.apply(*value)
+                *memory.entry(*addr).or_insert(0) = mask.apply(*value);
+                trace!(addr, value, masked = mask.apply(*value));
             },
         }
     }
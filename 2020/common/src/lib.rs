@@ -2,11 +2,23 @@ pub mod common {
     use std::fs::File;
     use std::io::Read;
 
+    use aoc_common::input::normalise;
+    use aoc_common::trace;
+
+    /// The file with LF line endings, no trailing whitespace and a single final newline.
+    /// See [`aoc_common::input::normalise`].
     pub fn load_file(path: &str) -> String {
+        normalise(&load_file_raw(path))
+    }
+
+    /// The file exactly as it is on disk. Sets up logging as the `-v` and `--log-json` flags
+    /// ask, see [`aoc_common::trace`].
+    pub fn load_file_raw(path: &str) -> String {
+        trace::init();
         let mut input = String::new();
         let mut f = File::open(path).expect("Unable to open file");
         f.read_to_string(&mut input).expect("Unable to read string");
 
         input
     }
-}
\ No newline at end of file
+}
@@ -1,10 +1,12 @@
 use aoc_2022_common::challenge_input;
+use tracing::info_span;
 
 fn main() {
-    let calories = parse_input(&challenge_input());
+    let input = challenge_input();
+    let calories = info_span!("parse").in_scope(|| parse_input(&input));
 
-    println!("{}", part_1(&calories));
-    println!("{}", part_2(calories));
+    println!("{}", info_span!("part_1").in_scope(|| part_1(&calories)));
+    println!("{}", info_span!("part_2").in_scope(|| part_2(calories)));
 }
 
 fn parse_input(input: &str) -> Vec<u64> {
@@ -1,4 +1,5 @@
 use aoc_2022_common::challenge_input;
+use tracing::info_span;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Move {
@@ -28,11 +29,13 @@ struct Game2(Vec<Turn2>);
 
 fn main() {
     let input = challenge_input();
-    let game = Game::from(input.as_ref());
-    let game2 = Game2::from(input.as_ref());
+    let (game, game2) =
+        info_span!("parse").in_scope(|| (Game::from(input.as_ref()), Game2::from(input.as_ref())));
 
-    println!("{}", game.0.iter().map(Turn::score).sum::<u64>());
-    println!("{}", game2.0.iter().map(Turn2::score).sum::<u64>());
+    let part_1 = info_span!("part_1").in_scope(|| game.0.iter().map(Turn::score).sum::<u64>());
+    println!("{part_1}");
+    let part_2 = info_span!("part_2").in_scope(|| game2.0.iter().map(Turn2::score).sum::<u64>());
+    println!("{part_2}");
 }
 
 impl Turn {
@@ -1,12 +1,13 @@
 use aoc_2022_common::challenge_input;
 use std::collections::HashSet;
+use tracing::info_span;
 
 type CharSet = HashSet<char>;
 
 fn main() {
     let input = challenge_input();
-    println!("{}", part_1(&input));
-    println!("{}", part_2(&input));
+    println!("{}", info_span!("part_1").in_scope(|| part_1(&input)));
+    println!("{}", info_span!("part_2").in_scope(|| part_2(&input)));
 }
 
 fn part_1(input: &str) -> u64 {
@@ -1,49 +1,37 @@
 use aoc_2022_common::challenge_input;
+use aoc_common::interval::Interval;
+use tracing::info_span;
+
+type Range = Interval<u64>;
 
 fn main() {
-    let ranges = parse_input(&challenge_input());
+    let input = challenge_input();
+    let ranges = info_span!("parse").in_scope(|| parse_input(&input));
 
-    println!("{}", day_1(&ranges));
-    println!("{}", day_2(&ranges));
+    println!("{}", info_span!("part_1").in_scope(|| day_1(&ranges)));
+    println!("{}", info_span!("part_2").in_scope(|| day_2(&ranges)));
 }
 
 fn parse_input(input: &str) -> Vec<(Range, Range)> {
     input
         .lines()
         .filter_map(|l| l.split_once(','))
-        .map(|(a, b)| (Range::from(a), Range::from(b)))
+        .map(|(a, b)| (parse_range(a), parse_range(b)))
         .collect()
 }
 
-fn day_1(ranges: &[(Range, Range)]) -> usize {
-    ranges
-        .iter()
-        .filter(|(a, b)| a.contains(b) || b.contains(a))
-        .count()
+fn parse_range(input: &str) -> Range {
+    let (left, right) = input.split_once('-').unwrap();
+    Range::new(left.parse().unwrap(), right.parse().unwrap())
 }
 
-fn day_2(ranges: &[(Range, Range)]) -> usize {
+fn day_1(ranges: &[(Range, Range)]) -> usize {
     ranges
         .iter()
-        .filter(|(a, b)| a.overlaps(b) || b.overlaps(a))
+        .filter(|(a, b)| a.contains_interval(*b) || b.contains_interval(*a))
         .count()
 }
 
-struct Range(u64, u64);
-
-impl From<&str> for Range {
-    fn from(input: &str) -> Self {
-        let (left, right) = input.split_once('-').unwrap();
-        Range(left.parse().unwrap(), right.parse().unwrap())
-    }
-}
-
-impl Range {
-    fn contains(&self, other: &Range) -> bool {
-        self.0 <= other.0 && self.1 >= other.1
-    }
-
-    fn overlaps(&self, other: &Range) -> bool {
-        (self.0 >= other.0 && self.0 <= other.1) || (self.1 >= other.0 && self.1 <= other.1)
-    }
+fn day_2(ranges: &[(Range, Range)]) -> usize {
+    ranges.iter().filter(|(a, b)| a.overlaps(*b)).count()
 }
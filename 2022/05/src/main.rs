@@ -1,10 +1,13 @@
-use aoc_2022_common::challenge_input;
+use aoc_2022_common::challenge_input_raw;
 use regex::Regex;
+use tracing::{info_span, trace};
 
 fn main() {
-    let crate_mover = CrateMover::from(challenge_input().as_str());
-    println!("{}", crate_mover.part_1());
-    println!("{}", crate_mover.part_2());
+    // The crate drawing is fixed width, so the spaces at the end of its lines matter
+    let input = challenge_input_raw();
+    let crate_mover = info_span!("parse").in_scope(|| CrateMover::from(input.as_str()));
+    println!("{}", info_span!("part_1").in_scope(|| crate_mover.part_1()));
+    println!("{}", info_span!("part_2").in_scope(|| crate_mover.part_2()));
 }
 
 type Crates = Vec<Vec<char>>;
@@ -24,6 +27,7 @@ impl CrateMover {
                 let to_move = crates[from].pop().unwrap();
                 crates[to].push(to_move);
             }
+            trace!(count, from, to, ?crates, "moved");
         }
         crates
     }
@@ -35,6 +39,7 @@ impl CrateMover {
             let drain_from = crates[from].len() - count;
             let mut drained_crates: Vec<char> = crates[from].drain(drain_from..).collect();
             crates[to].append(&mut drained_crates);
+            trace!(count, from, to, ?crates, "moved");
         }
         crates
     }
@@ -1,11 +1,14 @@
 use std::collections::HashSet;
 
 use aoc_2022_common::challenge_input;
+use tracing::info_span;
 
 fn main() {
     let signal = challenge_input();
-    println!("{}", first_unique_sequence(&signal, 4).unwrap());
-    println!("{}", first_unique_sequence(&signal, 14).unwrap());
+    let part_1 = info_span!("part_1").in_scope(|| first_unique_sequence(&signal, 4));
+    println!("{}", part_1.unwrap());
+    let part_2 = info_span!("part_2").in_scope(|| first_unique_sequence(&signal, 14));
+    println!("{}", part_2.unwrap());
 }
 
 fn first_unique_sequence(signal: &str, len: usize) -> Option<usize> {
@@ -1,6 +1,7 @@
 use std::{cmp, collections::HashMap};
 
 use aoc_2022_common::challenge_input;
+use tracing::info_span;
 
 /// We want to convert [`Command`]s to this structure
 #[derive(Debug, Eq, PartialEq)]
@@ -72,14 +73,15 @@ enum CdLocation {
 }
 
 fn main() {
-    let commands = parse_input(&challenge_input());
-    let fs = commands_to_fs(commands);
+    let input = challenge_input();
+    let fs = info_span!("parse").in_scope(|| commands_to_fs(parse_input(&input)));
 
-    println!("{}", fs.part_1());
+    println!("{}", info_span!("part_1").in_scope(|| fs.part_1()));
 
     let minimum_folder_size = 30_000_000 - (70_000_000 - fs.total_size());
 
-    println!("{}", fs.part_2(minimum_folder_size).unwrap());
+    let part_2 = info_span!("part_2").in_scope(|| fs.part_2(minimum_folder_size));
+    println!("{}", part_2.unwrap());
 }
 
 /// TODO: make less ugly
@@ -144,7 +146,9 @@ fn commands_to_fs(commands: Vec<Command>) -> Node {
                     }
                 }
 
-                let Node::Directory(map) = current_node else { panic!(); };
+                let Node::Directory(map) = current_node else {
+                    panic!();
+                };
 
                 for node in output {
                     match node {
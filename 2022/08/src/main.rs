@@ -3,6 +3,7 @@ use aoc_2022_common::challenge_input;
 use derive_more::{Add, AddAssign};
 use nalgebra::DMatrix;
 use std::convert::TryInto;
+use tracing::info_span;
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Add, AddAssign)]
 struct Point(isize, isize);
@@ -10,15 +11,22 @@ struct Point(isize, isize);
 const DIRECTIONS: [Point; 4] = [Point(0, 1), Point(0, -1), Point(1, 0), Point(-1, 0)];
 
 fn main() {
-    let height_map = to_height_map(&challenge_input());
-    let visibility_map = height_map
-        .map_with_location(|x, col, _| Point(x as isize, col as isize).is_visible(&height_map));
-    let scenic_map = height_map.map_with_location(|row, col, _| {
-        Point(row as isize, col as isize).scenic_score(&height_map)
-    });
+    let input = challenge_input();
+    let height_map = info_span!("parse").in_scope(|| to_height_map(&input));
 
-    println!("{}", visibility_map.iter().filter(|v| **v).count());
-    println!("{}", scenic_map.iter().max().unwrap());
+    let part_1 = info_span!("part_1").in_scope(|| {
+        let visibility_map = height_map
+            .map_with_location(|x, col, _| Point(x as isize, col as isize).is_visible(&height_map));
+        visibility_map.iter().filter(|v| **v).count()
+    });
+    println!("{part_1}");
+    let part_2 = info_span!("part_2").in_scope(|| {
+        let scenic_map = height_map.map_with_location(|row, col, _| {
+            Point(row as isize, col as isize).scenic_score(&height_map)
+        });
+        *scenic_map.iter().max().unwrap()
+    });
+    println!("{part_2}");
 }
 
 impl Point {
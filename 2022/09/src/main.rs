@@ -1,22 +1,60 @@
 use aoc_2022_common::challenge_input;
+use aoc_common::flood::Bounds;
+use aoc_common::player::{animate_requested, Player};
+use aoc_common::point::Point2 as GridPoint;
+use aoc_common::render::{Color, Frame, Glyph, Style};
 use derive_more::{Add, Sub};
 use std::collections::HashSet;
+use std::time::Duration;
+use tracing::{info_span, trace};
 
 fn main() {
-    let moves = parse_input(&challenge_input());
+    let input = challenge_input();
+    let moves = info_span!("parse").in_scope(|| parse_input(&input));
 
-    println!("{}", unique_tail_places::<2>(&moves));
-    println!("{}", unique_tail_places::<10>(&moves));
+    if animate_requested() {
+        animate(&moves);
+    }
+
+    println!(
+        "{}",
+        info_span!("part_1").in_scope(|| unique_tail_places::<2>(&moves))
+    );
+    println!(
+        "{}",
+        info_span!("part_2").in_scope(|| unique_tail_places::<10>(&moves))
+    );
 }
 
 fn unique_tail_places<const N: usize>(moves: &Vec<Vec2>) -> usize {
     let mut ropes = vec![Rope([Vec2::default(); N])];
     for mov in moves {
-        ropes.push(ropes.last().unwrap().next(mov));
+        let rope = ropes.last().unwrap().next(mov);
+        trace!(?mov, knots = ?rope.0, "moved");
+        ropes.push(rope);
     }
     ropes.iter().map(Rope::tail).collect::<HashSet<_>>().len()
 }
 
+/// Part 2's rope, one frame per step, with the view following the head
+fn animate(moves: &[Vec2]) {
+    let mut rope = Rope([Vec2::default(); 10]);
+    let mut visited = HashSet::from([Vec2::default()]);
+    let steps = std::iter::once(None).chain(moves.iter().map(Some));
+    let frames = steps.map(move |mov| {
+        if let Some(mov) = mov {
+            rope = rope.next(mov);
+            visited.insert(rope.tail());
+        }
+        rope.frame(&visited)
+    });
+
+    Player::new()
+        .delay(Duration::from_millis(20))
+        .play(frames)
+        .expect("couldn't animate the rope");
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 struct Rope<const N: usize>([Vec2; N]);
 
@@ -36,19 +74,58 @@ impl<const N: usize> Rope<N> {
     pub fn tail(&self) -> Vec2 {
         self.0[N - 1]
     }
+
+    /// The rope and the places its tail visited, around the head
+    pub fn frame(&self, visited: &HashSet<Vec2>) -> Frame {
+        let head = self.0[0].to_grid();
+        let view = Bounds {
+            min: head - GridPoint::new(30, 12),
+            max: head + GridPoint::new(30, 12),
+        };
+
+        let knots = self.0.iter().enumerate().rev().map(|(i, knot)| {
+            let c = if i == 0 {
+                'H'
+            } else {
+                char::from_digit(i as u32, 36).unwrap()
+            };
+            (
+                knot.to_grid(),
+                Glyph::new(c, Style::fg(Color::YELLOW).bold()),
+            )
+        });
+
+        let mut frame = Frame::from_bounds(&view, |_| Glyph::new('.', Style::fg(Color::GREY)))
+            .draw(
+                visited.iter().map(|&v| v.to_grid()),
+                Glyph::new('#', Style::fg(Color::BLUE)),
+            )
+            .draw([Vec2::default().to_grid()], Glyph::plain('s'));
+        for (point, glyph) in knots {
+            frame = frame.draw([point], glyph);
+        }
+        frame
+            .border(Style::PLAIN)
+            .caption(format!("{} visited", visited.len()), Style::PLAIN)
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Default, Hash, Add, Sub)]
 struct Vec2(isize, isize);
 
 impl Vec2 {
+    /// On screen, with y pointing down
+    fn to_grid(self) -> GridPoint {
+        GridPoint::new(self.0 as i64, -self.1 as i64)
+    }
+
     const NORTH: Vec2 = Vec2(0, 1);
     const SOUTH: Vec2 = Vec2(0, -1);
     const EAST: Vec2 = Vec2(1, 0);
     const WEST: Vec2 = Vec2(-1, 0);
 
     fn corrective_move(&self) -> Vec2 {
-        let normalize = |n: isize| (n.cmp(&0) as isize);
+        let normalize = |n: isize| n.cmp(&0) as isize;
 
         match self {
             Vec2(2, y) => Self::EAST + Vec2(0, normalize(*y)),
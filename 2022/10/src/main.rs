@@ -1,19 +1,33 @@
 use aoc_2022_common::challenge_input;
+use aoc_common::parsers::{lines_of, parse_all};
 use nom::{
     self,
     branch::alt,
     bytes::complete::tag,
-    character::complete::{i64, line_ending},
+    character::complete::i64,
     combinator::{map, success},
-    multi::separated_list0,
     sequence::preceded,
     IResult,
 };
+use tracing::{info_span, trace};
 
 fn main() {
     let input = challenge_input();
-    let (_, instructions) = Instruction::parse_list0(&input).expect("Invalid input");
+    let instructions = info_span!("parse")
+        .in_scope(|| parse_all(lines_of(Instruction::parse), &input))
+        .unwrap_or_else(|e| panic!("{e}"));
 
+    let x_history = x_history(instructions);
+
+    match info_span!("part_1").in_scope(|| part_1(&x_history)) {
+        Some(sum) => println!("{sum}"),
+        None => println!("Output is too short to produce signal sum"),
+    }
+    print!("{}", info_span!("part_2").in_scope(|| part_2(&x_history)));
+}
+
+/// The value of X during every cycle, starting at cycle 0
+fn x_history(instructions: Vec<Instruction>) -> Vec<i64> {
     let mut x_history: Vec<i64> = vec![1, 1];
     for instr in instructions {
         let last = *x_history.last().unwrap();
@@ -22,39 +36,37 @@ fn main() {
         if let Instruction::Addx(n) = instr {
             x_history.push(last + n);
         }
+        trace!(cycle = x_history.len() - 1, x = x_history.last(), ?instr);
     }
-
-    part_1(&x_history);
-    part_2(&x_history);
+    x_history
 }
 
-pub fn part_1(x_history: &[i64]) {
+/// The sum of the signal strengths during the 20th, 60th, … 220th cycles
+pub fn part_1(x_history: &[i64]) -> Option<i64> {
     let sigs = x_history
         .iter()
         .enumerate()
         .map(|(i, x)| i as i64 * x)
         .collect::<Vec<_>>();
 
-    if sigs.len() >= 220 {
-        let sum = sigs[20] + sigs[60] + sigs[100] + sigs[140] + sigs[180] + sigs[220];
-        println!("{sum}");
-    } else {
-        println!("Output is too short to produce signal sum");
-    }
+    (sigs.len() > 220).then(|| sigs[20] + sigs[60] + sigs[100] + sigs[140] + sigs[180] + sigs[220])
 }
 
-pub fn part_2(x_history: &[i64]) {
+/// What the CRT draws, a line of 40 pixels per row, with the sprite's pixels lit
+pub fn part_2(x_history: &[i64]) -> String {
+    let mut screen = String::new();
     for (i, &x) in x_history.iter().skip(1).enumerate() {
         let i = i as i64 % 40;
-        if i % 40 == 0 {
-            println!();
-        }
         if i == (x - 1) || i == x || i == (x + 1) {
-            print!("█");
+            screen.push('█');
         } else {
-            print!(" ");
+            screen.push(' ');
+        }
+        if i == 39 {
+            screen.push('\n');
         }
     }
+    screen
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -70,7 +82,17 @@ impl Instruction {
             map(preceded(tag("addx "), i64), Self::Addx),
         ))(input)
     }
-    pub fn parse_list0(input: &str) -> IResult<&str, Vec<Self>> {
-        separated_list0(line_ending, Self::parse)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = include_str!("../input/example.txt");
+        let x_history = x_history(parse_all(lines_of(Instruction::parse), input).unwrap());
+        assert_eq!(part_1(&x_history), Some(13140));
+        insta::assert_snapshot!(part_2(&x_history));
     }
 }
@@ -1,6 +1,7 @@
 #[cfg(test)]
 pub mod tests;
 use aoc_2022_common::challenge_input;
+use aoc_common::numtheory::lcm_all;
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -11,13 +12,18 @@ use nom::{
     sequence::{delimited, preceded, separated_pair, tuple},
     IResult,
 };
+use tracing::{debug, info_span, trace};
 
 fn main() {
     let input = challenge_input();
-    let (_, monkeys) = Monkey::parse_many(&input).expect("Invalid monkey(s) in input!");
-
-    println!("{}", Monkey::business(monkeys.clone(), true));
-    println!("{}", Monkey::business(monkeys, false));
+    let (_, monkeys) = info_span!("parse")
+        .in_scope(|| Monkey::parse_many(&input))
+        .expect("Invalid monkey(s) in input!");
+
+    let part_1 = info_span!("part_1").in_scope(|| Monkey::business(monkeys.clone(), true));
+    println!("{part_1}");
+    let part_2 = info_span!("part_2").in_scope(|| Monkey::business(monkeys, false));
+    println!("{part_2}");
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -59,11 +65,13 @@ impl Monkey {
     }
 
     pub fn business(mut monkeys: Vec<Monkey>, part_1: bool) -> u64 {
-        let total_multiplier: u64 = monkeys.iter().map(|m| m.test).product();
+        // Every test still gives the same result modulo the lcm of all divisors
+        let modulus =
+            lcm_all(monkeys.iter().map(|m| m.test)).expect("lcm of the divisors overflows a u64");
 
         let rounds = if part_1 { 20 } else { 10_000 };
 
-        for _ in 0..rounds {
+        for round in 1..=rounds {
             for i in 0..monkeys.len() {
                 while let Some(mut item) = monkeys[i].items.pop() {
                     item = monkeys[i].operation.apply(item);
@@ -72,7 +80,7 @@ impl Monkey {
                     if part_1 {
                         item /= 3;
                     } else {
-                        item %= total_multiplier;
+                        item %= modulus;
                     }
 
                     let target = if item % monkeys[i].test == 0 {
@@ -85,9 +93,15 @@ impl Monkey {
                     monkeys[i].inspected += 1;
                 }
             }
+            trace!(
+                round,
+                inspected = ?monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>(),
+                "round done"
+            );
         }
 
         let mut inspection_scores: Vec<u64> = monkeys.iter().map(|m| m.inspected).collect();
+        debug!(?inspection_scores, "after {rounds} rounds");
         inspection_scores.sort_unstable();
         inspection_scores.reverse();
         inspection_scores[0] * inspection_scores[1]
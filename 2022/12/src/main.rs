@@ -1,9 +1,12 @@
-use std::{collections::HashSet, fmt::Display};
+use std::collections::HashSet;
+use std::fmt;
 
 use aoc_2022_common::challenge_input;
+use aoc_common::tile::Tile;
 
 use derive_more::{Add, AddAssign};
 use nalgebra::DMatrix;
+use tracing::info_span;
 
 // copied form day 8
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Add, AddAssign, Hash)]
@@ -13,22 +16,26 @@ const DIRECTIONS: [Point; 4] = [Point(0, 1), Point(0, -1), Point(1, 0), Point(-1
 
 fn main() {
     let input = challenge_input();
-    let map = parse_input(&input);
+    let map = info_span!("parse").in_scope(|| parse_input(&input));
 
     let start_index = map.iter().position(|&c| c == MapItem::Start).unwrap();
     let start = Point::from_matrix_index(start_index, map.nrows());
 
-    let part_1 = start.steps_to_end(&map).expect("no part 1 solution");
+    let part_1 = info_span!("part_1")
+        .in_scope(|| start.steps_to_end(&map))
+        .expect("no part 1 solution");
     println!("{part_1}");
 
     // Can be made faster by caching visited startpoints or by multithreading
-    let part_2 = map
-        .iter()
-        .enumerate()
-        .filter(|(_, &item)| item == MapItem::Start || item == MapItem::Level(1))
-        .map(|(i, _)| Point::from_matrix_index(i, map.ncols()))
-        .filter_map(|p| p.steps_to_end(&map))
-        .min()
+    let part_2 = info_span!("part_2")
+        .in_scope(|| {
+            map.iter()
+                .enumerate()
+                .filter(|(_, &item)| item == MapItem::Start || item == MapItem::Level(Height('a')))
+                .map(|(i, _)| Point::from_matrix_index(i, map.nrows()))
+                .filter_map(|p| p.steps_to_end(&map))
+                .min()
+        })
         .expect("no part 2 solution");
     println!("{part_2}");
 }
@@ -41,11 +48,8 @@ fn parse_input(input: &str) -> DMatrix<MapItem> {
     let height_data: Vec<MapItem> = lines
         .iter()
         .flat_map(|l| {
-            l.as_bytes().iter().map(|&b| match b {
-                83 => MapItem::Start,
-                69 => MapItem::End,
-                n => MapItem::Level(u64::from(n - 96)),
-            })
+            l.chars()
+                .map(|c| MapItem::try_from(c).expect("invalid map item"))
         })
         .collect();
     DMatrix::from_row_slice(rows, cols, &height_data)
@@ -102,7 +106,7 @@ impl Point {
             .filter(|&&dir| {
                 (*self + dir)
                     .on_map(map)
-                    .map_or(false, |neighbor_item| self_item.can_move_to(neighbor_item))
+                    .is_some_and(|neighbor_item| self_item.can_move_to(neighbor_item))
             })
             .map(|&dir| *self + dir)
             .collect()
@@ -120,11 +124,38 @@ impl Point {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Tile)]
 enum MapItem {
+    #[tile('S')]
     Start,
+    #[tile('E')]
     End,
-    Level(u64),
+    #[tile(delegate)]
+    Level(Height),
+}
+
+/// An elevation, from `a` to `z`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Height(char);
+
+impl TryFrom<char> for Height {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        c.is_ascii_lowercase().then_some(Height(c)).ok_or(c)
+    }
+}
+
+impl From<Height> for char {
+    fn from(height: Height) -> Self {
+        height.0
+    }
+}
+
+impl fmt::Display for Height {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
 impl MapItem {
@@ -132,20 +163,10 @@ impl MapItem {
         match self {
             MapItem::Start => 1,
             MapItem::End => 26,
-            MapItem::Level(l) => *l,
+            MapItem::Level(Height(l)) => u64::from(*l) - u64::from('a') + 1,
         }
     }
     pub fn can_move_to(&self, other: MapItem) -> bool {
         other.as_level() <= self.as_level() + 1
     }
 }
-
-impl Display for MapItem {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            MapItem::Start => write!(f, "S"),
-            MapItem::End => write!(f, "E"),
-            MapItem::Level(n) => write!(f, "{n}"),
-        }
-    }
-}
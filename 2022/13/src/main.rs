@@ -1,15 +1,17 @@
 use std::cmp::Ordering;
 
 use aoc_2022_common::challenge_input;
+use aoc_common::parsers::{blocks_of, parse_all};
 use nom::{
     branch::alt,
     character::complete::u64,
     character::{complete::line_ending, streaming::char},
     combinator::map,
-    multi::{count, many0, separated_list0},
-    sequence::{delimited, preceded, separated_pair},
+    multi::separated_list0,
+    sequence::{delimited, separated_pair},
     IResult,
 };
+use tracing::info_span;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 enum Packet {
@@ -33,12 +35,8 @@ impl Packet {
         separated_pair(Self::parse, line_ending, Self::parse)(input)
     }
 
-    pub fn parse_pair_list0(input: &str) -> IResult<&str, Vec<(Packet, Packet)>> {
-        separated_list0(count(line_ending, 2), Self::parse_pair)(input)
-    }
-
-    pub fn parse_list0(input: &str) -> IResult<&str, Vec<Packet>> {
-        separated_list0(line_ending, preceded(many0(line_ending), Self::parse))(input)
+    pub fn parse_pairs(input: &str) -> Vec<(Packet, Packet)> {
+        parse_all(blocks_of(Self::parse_pair), input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     pub fn new_divider(number: u64) -> Self {
@@ -77,9 +75,7 @@ impl PartialOrd<Self> for Packet {
 
 #[must_use]
 pub fn part_1(input: &str) -> usize {
-    Packet::parse_pair_list0(input)
-        .expect("invalid packets in input")
-        .1
+    Packet::parse_pairs(input)
         .iter()
         .map(|(lhs, rhs)| lhs.cmp(rhs))
         .enumerate()
@@ -91,7 +87,10 @@ pub fn part_1(input: &str) -> usize {
 
 #[must_use]
 pub fn part_2(input: &str) -> usize {
-    let (_, mut packets) = Packet::parse_list0(input).expect("invalid packets in input");
+    let mut packets: Vec<Packet> = Packet::parse_pairs(input)
+        .into_iter()
+        .flat_map(|(lhs, rhs)| [lhs, rhs])
+        .collect();
     let divider_packet_2 = Packet::new_divider(2);
     let divider_packet_6 = Packet::new_divider(6);
     packets.push(divider_packet_2.clone());
@@ -107,6 +106,6 @@ pub fn part_2(input: &str) -> usize {
 fn main() {
     let input = challenge_input();
 
-    println!("{}", part_1(&input));
-    println!("{}", part_2(&input));
+    println!("{}", info_span!("part_1").in_scope(|| part_1(&input)));
+    println!("{}", info_span!("part_2").in_scope(|| part_2(&input)));
 }
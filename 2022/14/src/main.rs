@@ -1,6 +1,13 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use aoc_2022_common::challenge_input;
+use aoc_common::export::{path_requested, ImageExport, Palette};
+use aoc_common::flood::Bounds;
+use aoc_common::player::{animate_requested, Player};
+use aoc_common::point::Point2 as GridPoint;
+use aoc_common::render::{Color, Frame, Glyph, Style};
 
 use derive_more::{Add, Constructor};
 use nom::{
@@ -10,10 +17,11 @@ use nom::{
         streaming::char,
     },
     combinator::map,
-    multi::separated_list0,
+    multi::{separated_list0, separated_list1},
     sequence::separated_pair,
     IResult,
 };
+use tracing::{info_span, trace};
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Constructor)]
 struct Element {
@@ -50,7 +58,7 @@ impl Point {
         map(separated_pair(i32, char(','), i32), |(x, y)| Point { x, y })(input)
     }
     pub fn parse_sequence(input: &str) -> IResult<&str, Vec<Self>> {
-        separated_list0(tag(" -> "), Self::parse)(input)
+        separated_list1(tag(" -> "), Self::parse)(input)
     }
     pub fn parse_sequence_list(input: &str) -> IResult<&str, Vec<Vec<Self>>> {
         separated_list0(line_ending, Self::parse_sequence)(input)
@@ -157,16 +165,62 @@ impl World {
     pub fn sand_count(&self) -> usize {
         self.elements.iter().filter(|el| el.ty == Sand).count()
     }
+
+    /// Everything that can get filled with sand, with some room on the sides
+    pub fn view(&self) -> Bounds<GridPoint> {
+        let points = self.elements.iter().map(|el| el.point).chain([self.start]);
+        Bounds::<GridPoint>::around(points.map(|p| GridPoint::new(p.x.into(), p.y.into())))
+            .expect("empty world")
+            .expanded(1)
+    }
+
+    pub fn frame(&self, view: &Bounds<GridPoint>) -> Frame {
+        Frame::from_bounds(view, |p| {
+            let point = Point::new(p.x as i32, p.y as i32);
+            match self.get(point) {
+                Some(Rock) => Glyph::new('█', Style::fg(Color::GREY)),
+                Some(Sand) => Glyph::new('o', Style::fg(Color::YELLOW)),
+                None if point == self.start => Glyph::new('+', Style::fg(Color::RED)),
+                None => Glyph::plain(' '),
+            }
+        })
+        .border(Style::PLAIN)
+        .caption(format!("{} grains", self.sand_count()), Style::PLAIN)
+    }
+}
+
+/// Part 1's sand, one frame per grain that comes to rest
+fn grains(rock_corner_sequences: &[Vec<Point>]) -> impl Iterator<Item = Frame> {
+    let mut world = World::new(rock_corner_sequences, false);
+    let view = world.view();
+    let first = world.frame(&view);
+    let grains = std::iter::from_fn(move || world.add_sand().map(|_| world.frame(&view)));
+    std::iter::once(first).chain(grains)
+}
+
+fn animate(rock_corner_sequences: &[Vec<Point>]) {
+    Player::new()
+        .delay(Duration::from_millis(20))
+        .play(grains(rock_corner_sequences))
+        .expect("couldn't animate the sand");
+}
+
+fn save_gif(rock_corner_sequences: &[Vec<Point>], path: PathBuf) {
+    let palette = Palette::new()
+        .tile('█', Color::Rgb(110, 90, 70))
+        .tile('o', Color::Rgb(230, 200, 120));
+    ImageExport::new(palette)
+        .scale(3)
+        .delay(2)
+        .save_gif(grains(rock_corner_sequences), path)
+        .unwrap_or_else(|e| panic!("{e}"));
 }
 
 #[must_use]
 fn part_1(rock_corner_sequences: &[Vec<Point>]) -> usize {
     let mut world = World::new(rock_corner_sequences, false);
-    loop {
-        let added_sand = world.add_sand();
-        if added_sand.is_none() {
-            break;
-        }
+    while let Some(grain) = world.add_sand() {
+        trace!(?grain, "came to rest");
     }
     world.sand_count()
 }
@@ -176,6 +230,7 @@ fn part_2(rock_corner_sequences: &[Vec<Point>]) -> usize {
     let mut world = World::new(rock_corner_sequences, true);
     loop {
         let added_sand = world.add_sand();
+        trace!(grain = ?added_sand, "came to rest");
         if added_sand == Some(world.start) {
             break;
         }
@@ -185,9 +240,23 @@ fn part_2(rock_corner_sequences: &[Vec<Point>]) -> usize {
 
 fn main() {
     let input = challenge_input();
-    let (_, rock_corner_sequences) =
-        Point::parse_sequence_list(&input).expect("Invalid rock corners in input");
+    let (_, rock_corner_sequences) = info_span!("parse")
+        .in_scope(|| Point::parse_sequence_list(&input))
+        .expect("Invalid rock corners in input");
+
+    if animate_requested() {
+        animate(&rock_corner_sequences);
+    }
+    if let Some(path) = path_requested("--gif") {
+        save_gif(&rock_corner_sequences, path);
+    }
 
-    println!("{}", part_1(&rock_corner_sequences));
-    println!("{}", part_2(&rock_corner_sequences));
+    println!(
+        "{}",
+        info_span!("part_1").in_scope(|| part_1(&rock_corner_sequences))
+    );
+    println!(
+        "{}",
+        info_span!("part_2").in_scope(|| part_2(&rock_corner_sequences))
+    );
 }
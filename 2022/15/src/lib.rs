@@ -0,0 +1,90 @@
+//! The sensors and how they cover the rows, shared by the binary and the Python module.
+
+use std::collections::HashSet;
+
+use aoc_common::interval::{Interval, IntervalSet};
+use aoc_common::parallel::prelude::*;
+use aoc_common::parsers::{labelled_point, lines_of, parse_all, ParseError};
+use aoc_common::point::Point2;
+
+use derive_more::Constructor;
+
+use nom::sequence::preceded;
+use nom::{bytes::complete::tag, combinator::map, sequence::separated_pair, IResult};
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Constructor)]
+pub struct Sensor {
+    pub position: Point2,
+    pub beacon: Point2,
+}
+
+impl Sensor {
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        map(
+            preceded(
+                tag("Sensor at "),
+                separated_pair(
+                    labelled_point,
+                    tag(": closest beacon is at "),
+                    labelled_point,
+                ),
+            ),
+            |(position, closest_beacon)| Sensor::new(position, closest_beacon),
+        )(input)
+    }
+
+    pub fn radius(&self) -> i64 {
+        self.position.manhattan(self.beacon)
+    }
+
+    pub fn intersection(&self, y: i64) -> Option<Interval<i64>> {
+        let r = self.radius();
+        let (px, py) = (self.position.x, self.position.y);
+
+        (!(y > py + r || y < py - r)).then(|| {
+            let dy = (y - py).abs();
+            let dx = r - dy;
+            Interval::new(px - dx, px + dx)
+        })
+    }
+}
+
+/// A sensor on every line
+pub fn parse(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    parse_all(lines_of(Sensor::parse), input)
+}
+
+pub trait Sensors {
+    fn intersections(&self, y: i64) -> IntervalSet<i64>;
+}
+
+impl Sensors for Vec<Sensor> {
+    fn intersections(&self, y: i64) -> IntervalSet<i64> {
+        self.iter().filter_map(|s| s.intersection(y)).collect()
+    }
+}
+
+#[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+pub fn part_1(sensors: &Vec<Sensor>, y: i64) -> i64 {
+    let beacons_on_y = sensors
+        .iter()
+        .map(|s| s.beacon)
+        .filter(|b| b.y == y)
+        .collect::<HashSet<_>>()
+        .len() as i64;
+
+    let seen = sensors.intersections(y).len();
+    seen.expect("the sensors see fewer than i64::MAX positions") - beacons_on_y
+}
+
+pub fn part_2(sensors: &Vec<Sensor>, limit: i64) -> Option<i64> {
+    let target_range = Interval::new(0, limit);
+
+    (0..limit).into_par_iter().find_map_first(|y| {
+        sensors
+            .intersections(y)
+            .gaps(target_range)
+            .next()
+            .map(|gap| gap.start * 4_000_000 + y)
+    })
+}
@@ -1,4 +1,5 @@
 use aoc_2022_common::challenge_input;
+use tracing::{debug, info_span, trace};
 
 #[derive(Debug, Clone)]
 struct Number {
@@ -40,23 +41,31 @@ fn mix_n_times_with_key(input_numbers: &[Number], times: usize, decryption_key:
         })
         .collect::<Vec<_>>();
     let mut mixing_numbers = input.clone();
-    (0..times).for_each(|_| mix(&input, &mut mixing_numbers));
+    for round in 1..=times {
+        mix(&input, &mut mixing_numbers);
+        debug!(round, "mixed");
+        trace!(numbers = ?mixing_numbers.iter().map(|n| n.value).collect::<Vec<_>>());
+    }
 
     grove_coordinates_sum(&mixing_numbers)
 }
 
 fn main() {
-    let input_numbers: Vec<Number> = challenge_input()
-        .lines()
-        .enumerate()
-        .map(|(index, val)| Number {
-            value: val.parse().unwrap(),
-            index,
-        })
-        .collect();
+    let input = challenge_input();
+    let input_numbers: Vec<Number> = info_span!("parse").in_scope(|| {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, val)| Number {
+                value: val.parse().unwrap(),
+                index,
+            })
+            .collect()
+    });
 
-    let part_1 = mix_n_times_with_key(&input_numbers, 1, 1);
+    let part_1 = info_span!("part_1").in_scope(|| mix_n_times_with_key(&input_numbers, 1, 1));
     println!("{part_1}");
-    let part_2 = mix_n_times_with_key(&input_numbers, 10, 811_589_153);
+    let part_2 =
+        info_span!("part_2").in_scope(|| mix_n_times_with_key(&input_numbers, 10, 811_589_153));
     println!("{part_2}");
 }
@@ -10,6 +10,7 @@ use nom::{
     sequence::{preceded, separated_pair, terminated, tuple},
     IResult,
 };
+use tracing::{info_span, trace};
 
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 struct MonkeyId<'a>(&'a str);
@@ -80,23 +81,28 @@ impl<'a> Monkey<'a> {
 fn main() {
     // Part 1
     let input = challenge_input();
-    let (_, monkeys) = Monkey::parse_map(&input).unwrap();
+    let (_, monkeys) = info_span!("parse")
+        .in_scope(|| Monkey::parse_map(&input))
+        .unwrap();
     let root = MonkeyId("root");
-    let part_1 = &monkeys[&root].value(&monkeys);
+    let part_1 = info_span!("part_1").in_scope(|| monkeys[&root].value(&monkeys));
     println!("{part_1}");
 
     // Part 2
-    let (mut human_expr, mut other_expr) = Expr::from_monkeys(&monkeys);
-    if !human_expr.contains_human() {
-        mem::swap(&mut human_expr, &mut other_expr);
-    }
+    let part_2 = info_span!("part_2").in_scope(|| {
+        let (mut human_expr, mut other_expr) = Expr::from_monkeys(&monkeys);
+        if !human_expr.contains_human() {
+            mem::swap(&mut human_expr, &mut other_expr);
+        }
 
-    // Simplify unti we have Human = Expr
-    while human_expr != Expr::Human {
-        (human_expr, other_expr) = simplify_expr(human_expr, other_expr);
-    }
+        // Simplify unti we have Human = Expr
+        while human_expr != Expr::Human {
+            (human_expr, other_expr) = simplify_expr(human_expr, other_expr);
+            trace!(?human_expr, ?other_expr, "simplified");
+        }
 
-    let part_2 = other_expr.value();
+        other_expr.value()
+    });
     println!("{part_2}");
 }
 
@@ -1,7 +1,14 @@
-use rayon::prelude::*;
 use std::{collections::HashSet, ops::Add};
 
 use aoc_2022_common::challenge_input;
+use aoc_common::cycle::fixed_point;
+use aoc_common::export::{path_requested, ImageExport, Palette};
+use aoc_common::flood::Bounds;
+use aoc_common::parallel::prelude::*;
+use aoc_common::player::{animate_requested, Player};
+use aoc_common::point::Point2 as GridPoint;
+use aoc_common::render::{Color, Frame, Glyph, Style};
+use tracing::{debug, info_span, trace};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 struct Point2 {
@@ -19,10 +26,10 @@ enum Dir {
     NW,
     SE,
     SW,
-    STAY,
+    Stay,
 }
 
-use Dir::{E, N, NE, NW, S, SE, STAY, SW, W};
+use Dir::{Stay, E, N, NE, NW, S, SE, SW, W};
 
 impl Add<Dir> for Point2 {
     type Output = Point2;
@@ -50,7 +57,7 @@ impl Add<Dir> for Point2 {
                 self.y += 1;
                 self.x -= 1;
             }
-            STAY => {}
+            Stay => {}
         }
 
         self
@@ -80,17 +87,11 @@ fn opposite_dir(dir: Dir) -> Dir {
 }
 
 trait ElveExt {
-    fn scan(self, scan: &[Dir], others: &Elves) -> bool;
     fn proposed_dir(self, round: usize, others: &Elves) -> Dir;
     fn next(self, round: usize, others: &Elves) -> Elve;
 }
 
 impl ElveExt for Elve {
-    fn scan(self, scan: &[Dir], others: &Elves) -> bool {
-        scan.iter().all(|&dir| !others.contains(&(self + dir)))
-    }
-
-    #[must_use]
     #[inline(always)]
     fn proposed_dir(self, round: usize, others: &Elves) -> Dir {
         let around_scan = (others.contains(&(self + NW)) as u8)
@@ -103,7 +104,7 @@ impl ElveExt for Elve {
             + ((others.contains(&(self + W)) as u8) << 7);
 
         if around_scan == 0 {
-            return STAY;
+            return Stay;
         }
 
         for i in 0..SCANS.len() {
@@ -113,14 +114,14 @@ impl ElveExt for Elve {
             }
         }
 
-        STAY
+        Stay
     }
 
     #[inline(always)]
     fn next(self, round: usize, others: &Elves) -> Elve {
         let prop_dir = self.proposed_dir(round, others);
 
-        if prop_dir == STAY {
+        if prop_dir == Stay {
             return self;
         }
 
@@ -146,7 +147,8 @@ trait ElvesExt {
     fn next(&self, round: usize) -> Elves;
     fn parse(input: &str) -> Elves;
     fn edges(&self) -> (i16, i16, i16, i16);
-    fn print(&self, round: usize, end_min_y: i16, end_min_x: i16);
+    fn view(&self) -> Bounds<GridPoint>;
+    fn frame(&self, round: usize, view: &Bounds<GridPoint>) -> Frame;
     fn part_1(&self) -> i16;
 }
 
@@ -186,43 +188,27 @@ impl ElvesExt for Elves {
         )
     }
 
-    fn print(&self, round: usize, end_min_y: i16, end_min_x: i16) {
+    /// The smallest view with all elves in it
+    fn view(&self) -> Bounds<GridPoint> {
         let (min_y, max_x, max_y, min_x) = self.edges();
+        let corners = [(min_x, min_y), (max_x, max_y)]
+            .map(|(x, y)| GridPoint::new(i64::from(x), i64::from(y)));
+        Bounds::<GridPoint>::around(corners).expect("no elves")
+    }
 
-        let extra_x_space = 4;
-        let extra_y_space = 3;
-
-        let y_correct_range = 0..((min_y - end_min_y).abs() + extra_y_space);
-        let x_correct_range = 0..((min_x - end_min_x).abs() + extra_x_space);
-        // ensure that the visual remains aligned when the bounds expand
-        y_correct_range.for_each(|_| println!());
-
-        x_correct_range.clone().for_each(|_| print!("  "));
-        print!("\x1b[38;5;29m┏");
-        (min_x..=(max_x)).for_each(|_| print!("━━"));
-
-        println!("━┓\x1b[0m");
-        for y in min_y..=max_y {
-            x_correct_range.clone().for_each(|_| print!("  "));
-            print!("\x1b[38;5;29m┃ ");
-            for x in min_x..=max_x {
-                if self.contains(&Elve { x, y }) {
-                    print!("\x1b[93m⬤ \x1b[0m");
-                } else if x == 0 && y == 0 {
-                    print!("\x1b[38;5;246m∘ \x1b[0m");
-                } else {
-                    print!("\x1b[38;5;240m∘ \x1b[0m");
-                }
-            }
-            println!("\x1b[38;5;29m┃");
-        }
-
-        x_correct_range.for_each(|_| print!("  "));
-        print!("┗━");
-        let end = format!(" R-{:0width$} ", round, width = 3);
-        (0..=((max_x - min_x) * 2 - end.len() as i16)).for_each(|_| print!("━"));
-        print!("\x1b[1;38;5;160m{}\x1b[0m", end);
-        println!("\x1b[38;5;29m━┛\x1b[0m");
+    fn frame(&self, round: usize, view: &Bounds<GridPoint>) -> Frame {
+        let ground = Glyph::new('∘', Style::fg(Color::Ansi(240)));
+        let elf = Glyph::new('⬤', Style::fg(Color::Ansi(11)));
+        let elves = self
+            .iter()
+            .map(|e| GridPoint::new(i64::from(e.x), i64::from(e.y)));
+
+        Frame::from_bounds(view, |_| ground)
+            .highlight([GridPoint::ORIGIN], Style::fg(Color::Ansi(246)))
+            .draw(elves, elf)
+            .cell_width(2)
+            .border(Style::fg(Color::Ansi(29)))
+            .caption(format!("R-{round:03}"), Style::fg(Color::Ansi(160)).bold())
     }
 
     fn part_1(&self) -> i16 {
@@ -231,82 +217,91 @@ impl ElvesExt for Elves {
     }
 }
 
-fn clear_screen() {
-    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-}
-
 fn main() {
     let input = challenge_input();
-    let mut elves = Elves::parse(&input);
+    let animate = animate_requested();
+    let gif = path_requested("--gif");
     let mut part_1 = None;
-    let mut part_2 = None;
-
-    let mut smallest_x = 0;
-    let mut smallest_y = 0;
-
-    // clear_screen();
-    // println!();
-    // println!();
-    // println!();
-    // println!("     *** Merry Christmas! ***");
-
-    for round in 0.. {
+    // Everywhere the elves go, so the animation doesn't have to move around
+    let mut view: Option<Bounds<GridPoint>> = None;
+
+    let elves = info_span!("parse").in_scope(|| Elves::parse(&input));
+    // Part 1 is along the way to part 2
+    let mut phase = Some(info_span!("part_1").entered());
+    let (last_round, _) = fixed_point(elves, |round, elves| {
+        debug!(round, edges = ?elves.edges());
+        trace!("\n{}", elves.frame(round, &elves.view()));
         if round == 11 {
             part_1 = Some(elves.part_1());
+            phase.take();
+            phase = Some(info_span!("part_2").entered());
         }
-        let next_elves = elves.next(round);
-        if next_elves == elves {
-            part_2 = Some(round + 1);
-            break;
+        if animate || gif.is_some() {
+            let here = elves.view();
+            let seen = view.iter().flat_map(|v| [v.min, v.max]);
+            view = Bounds::<GridPoint>::around([here.min, here.max].into_iter().chain(seen));
+        }
+        elves.next(round)
+    });
+    drop(phase);
+    let part_2 = last_round + 1;
+
+    if let Some(view) = view {
+        let view = view.expanded(1);
+        let rounds = || {
+            (0..=last_round).scan(Elves::parse(&input), |elves, round| {
+                let frame = elves
+                    .frame(round, &view)
+                    .title("Merry Christmas!", Style::fg(Color::Ansi(160)).bold());
+                *elves = elves.next(round);
+                Some(frame)
+            })
+        };
+        if animate {
+            Player::new()
+                .play(rounds())
+                .expect("couldn't animate the elves");
+        }
+        if let Some(path) = gif {
+            ImageExport::new(Palette::new().tile('∘', Color::Rgb(16, 24, 32)))
+                .scale(3)
+                .delay(4)
+                .save_gif(rounds(), path)
+                .unwrap_or_else(|e| panic!("{e}"));
         }
-        // next_elves.print(round, -10, -10);
-
-        // update the smallest_x and smallest_y we've encountered
-        elves = elves.next(round);
-        // let (min_y, _, _, min_x) = elves.edges();
-        // smallest_x = smallest_x.min(min_x);
-        // smallest_y = smallest_y.min(min_y);
     }
 
-    // const DIMENSIONS: usize = 500;
-    // let grid: [bool; DIMENSIONS * DIMENSIONS] = [false; DIMENSIONS * DIMENSIONS];
-
-    // reset
-    // elves = Elves::parse(&input);
-    // for round in 0.. {
-    //     // clear the screen
-    //     clear_screen();
-    //     elves.print(round, smallest_y, smallest_x);
-    //     let next_elves = elves.next(round);
-    //     if next_elves == elves {
-    //         break;
-    //     }
-    //     elves = elves.next(round);
-
-    //     thread::sleep(time::Duration::from_millis(50));
-    // }
-
-    // println!();
-    // println!();
-    // println!();
-    // println!();
-
-    // println!(
-    //     "          Part 1: \x1b[1;38;5;160m{}\x1b[0m",
-    //     part_1.unwrap()
-    // );
-    // println!(
-    //     "          Part 2: \x1b[1;38;5;160m{}\x1b[0m",
-    //     part_2.unwrap()
-    // );
-
     println!("{}", part_1.unwrap());
-    println!("{}", part_2.unwrap());
+    println!("{part_2}");
+}
 
-    // println!("{}\n{}, part_1.unwrap(), part_2.unwrap());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // println!();
-    // println!();
-    // println!();
-    // println!();
+    /// The elves after `rounds` rounds
+    fn after(input: &str, rounds: usize) -> Elves {
+        (0..rounds).fold(Elves::parse(input), |elves, round| elves.next(round))
+    }
+
+    #[test]
+    fn small_example() {
+        let input = include_str!("../input/example_small.txt");
+        let view = Bounds {
+            min: GridPoint::ORIGIN,
+            max: GridPoint::new(4, 5),
+        };
+        let frames: String = (0..=3)
+            .map(|round| after(input, round).frame(round, &view).to_string())
+            .collect();
+        insta::assert_snapshot!(frames);
+    }
+
+    #[test]
+    fn example_after_ten_rounds() {
+        let input = include_str!("../input/example.txt");
+        let elves = after(input, 10);
+        assert_eq!(elves.part_1(), 110);
+        insta::assert_snapshot!(elves.frame(10, &elves.view()).to_string());
+    }
 }
@@ -1,4 +1,5 @@
 use aoc_2023_common::challenge_input;
+use tracing::info_span;
 
 const DIGIT_WORDS: [&str; 9] = [
     "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
@@ -76,15 +77,19 @@ fn calibration_value(line: &str, finder: fn(&str, &Direction) -> Option<u32>) ->
 
 fn main() {
     let input = challenge_input();
-    let lines = input.lines().collect::<Vec<_>>();
-    let part_1: u32 = lines
-        .iter()
-        .filter_map(|line| calibration_value(line, first_digit_1))
-        .sum();
-    let part_2: u32 = lines
-        .iter()
-        .filter_map(|line| calibration_value(line, first_digit_2))
-        .sum();
+    let lines = info_span!("parse").in_scope(|| input.lines().collect::<Vec<_>>());
+    let part_1: u32 = info_span!("part_1").in_scope(|| {
+        lines
+            .iter()
+            .filter_map(|line| calibration_value(line, first_digit_1))
+            .sum()
+    });
+    let part_2: u32 = info_span!("part_2").in_scope(|| {
+        lines
+            .iter()
+            .filter_map(|line| calibration_value(line, first_digit_2))
+            .sum()
+    });
     println!("{part_1}");
     println!("{part_2}");
 }
@@ -1,16 +1,15 @@
 use aoc_2023_common::challenge_input;
+use aoc_common::parsers::{lines_of, parse_all};
 use nom::{
     bytes::complete::tag,
-    character::{
-        complete::{alphanumeric1, space1, u32},
-        streaming::newline,
-    },
+    character::complete::{alphanumeric1, space1, u32},
     combinator::map,
     multi::separated_list0,
     sequence::preceded,
     sequence::separated_pair,
     IResult,
 };
+use tracing::info_span;
 
 #[derive(Debug)]
 struct Game {
@@ -19,8 +18,8 @@ struct Game {
 }
 
 impl Game {
-    pub fn parse_many(input: &str) -> IResult<&str, Vec<Self>> {
-        separated_list0(newline, Self::parse)(input)
+    pub fn parse_many(input: &str) -> Vec<Self> {
+        parse_all(lines_of(Self::parse), input).unwrap_or_else(|e| panic!("{e}"))
     }
     pub fn parse(input: &str) -> IResult<&str, Self> {
         map(
@@ -53,26 +52,30 @@ impl Game {
 
 fn main() {
     let input = challenge_input();
-    let games = Game::parse_many(&input).expect("invalid input").1;
-    let part_1: u32 = games
-        .iter()
-        .filter(|game| {
-            game.sets
-                .iter()
-                .all(|(r, g, b)| *r <= 12 && *g <= 13 && *b <= 14)
-        })
-        .map(|game| game.id)
-        .sum();
+    let games = info_span!("parse").in_scope(|| Game::parse_many(&input));
+    let part_1: u32 = info_span!("part_1").in_scope(|| {
+        games
+            .iter()
+            .filter(|game| {
+                game.sets
+                    .iter()
+                    .all(|(r, g, b)| *r <= 12 && *g <= 13 && *b <= 14)
+            })
+            .map(|game| game.id)
+            .sum()
+    });
 
-    let part_2: u32 = games
-        .iter()
-        .map(|game| {
-            let (r, g, b) = game.sets.iter().fold((0, 0, 0), |(ar, ag, ab), (r, g, b)| {
-                (ar.max(*r), ag.max(*g), ab.max(*b))
-            });
-            r * g * b
-        })
-        .sum();
+    let part_2: u32 = info_span!("part_2").in_scope(|| {
+        games
+            .iter()
+            .map(|game| {
+                let (r, g, b) = game.sets.iter().fold((0, 0, 0), |(ar, ag, ab), (r, g, b)| {
+                    (ar.max(*r), ag.max(*g), ab.max(*b))
+                });
+                r * g * b
+            })
+            .sum()
+    });
     println!("{part_1}");
     println!("{part_2}");
 }
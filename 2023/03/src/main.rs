@@ -1,6 +1,7 @@
 use std::{collections::HashMap, str::FromStr};
 
 use aoc_2023_common::challenge_input;
+use tracing::info_span;
 
 #[derive(Debug)]
 struct Schematic {
@@ -101,28 +102,35 @@ fn find_part_numbers(schematic: &Schematic) -> Vec<PartNumber> {
 
 fn main() {
     let input = challenge_input();
-    let schematic = Schematic::from_str(&input).unwrap();
-    let part_numbers = find_part_numbers(&schematic);
-    let part_1: usize = part_numbers
-        .iter()
-        .filter(|part_number| !matches!(part_number.typ(&schematic), PartType::Invalid))
-        .map(|part| part.number)
-        .sum();
+    let (schematic, part_numbers) = info_span!("parse").in_scope(|| {
+        let schematic = Schematic::from_str(&input).unwrap();
+        let part_numbers = find_part_numbers(&schematic);
+        (schematic, part_numbers)
+    });
+    let part_1: usize = info_span!("part_1").in_scope(|| {
+        part_numbers
+            .iter()
+            .filter(|part_number| !matches!(part_number.typ(&schematic), PartType::Invalid))
+            .map(|part| part.number)
+            .sum()
+    });
 
     println!("{part_1}");
 
-    let mut gears: HashMap<(i32, i32), Vec<PartNumber>> = HashMap::new();
-    for part in part_numbers {
-        if let PartType::Gear(field) = part.typ(&schematic) {
-            gears.entry(field).or_default().push(part);
-        };
-    }
+    let part_2: usize = info_span!("part_2").in_scope(|| {
+        let mut gears: HashMap<(i32, i32), Vec<PartNumber>> = HashMap::new();
+        for part in part_numbers {
+            if let PartType::Gear(field) = part.typ(&schematic) {
+                gears.entry(field).or_default().push(part);
+            };
+        }
 
-    let part_2: usize = gears
-        .iter()
-        .filter(|(_, gears)| gears.len() == 2)
-        .map(|(_, gears)| gears.iter().map(|g| g.number).product::<usize>())
-        .sum();
+        gears
+            .iter()
+            .filter(|(_, gears)| gears.len() == 2)
+            .map(|(_, gears)| gears.iter().map(|g| g.number).product::<usize>())
+            .sum()
+    });
 
     println!("{part_2}");
 }
@@ -1,5 +1,5 @@
 use aoc_2023_common::challenge_input;
-use rayon::prelude::*;
+use aoc_common::parallel::prelude::*;
 
 use nom::{
     bytes::complete::tag,
@@ -13,6 +13,7 @@ use nom::{
     sequence::{delimited, preceded},
     IResult,
 };
+use tracing::info_span;
 
 #[derive(Debug)]
 struct Card {
@@ -72,12 +73,18 @@ impl Card {
 
 fn main() {
     let input = challenge_input();
-    let cards = Card::parse_many(&input).expect("Invalid input").1;
-    let points = cards.par_iter().map(|card| card.points()).sum::<u32>();
-    let points_2 = cards
-        .par_iter()
-        .map(|card| card.points_2(&cards))
-        .sum::<u32>();
+    let cards = info_span!("parse")
+        .in_scope(|| Card::parse_many(&input))
+        .expect("Invalid input")
+        .1;
+    let points =
+        info_span!("part_1").in_scope(|| cards.par_iter().map(|card| card.points()).sum::<u32>());
+    let points_2 = info_span!("part_2").in_scope(|| {
+        cards
+            .par_iter()
+            .map(|card| card.points_2(&cards))
+            .sum::<u32>()
+    });
     println!("{points}");
     println!("{points_2}");
 }
@@ -0,0 +1,78 @@
+//! The almanac and its maps, shared by the binary and the Python module.
+
+use aoc_common::interval::{Interval, IntervalSet, PiecewiseMap};
+use nom::{
+    bytes::complete::{tag, take_till},
+    character::complete::{line_ending, newline, space1, u64},
+    combinator::map,
+    multi::{separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, tuple},
+    IResult,
+};
+
+/// One of the almanac's maps, like `seed-to-soil`
+#[derive(Debug, Clone)]
+pub struct Map {
+    pub name: String,
+    pub ranges: PiecewiseMap<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    /// In the order they're applied in
+    pub maps: Vec<Map>,
+}
+
+impl Almanac {
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        map(
+            tuple((
+                (delimited(tag("seeds: "), separated_list0(space1, u64), newline)),
+                preceded(
+                    newline,
+                    separated_list1(pair(line_ending, line_ending), Map::parse),
+                ),
+            )),
+            |(seeds, maps)| Self { seeds, maps },
+        )(input)
+    }
+
+    pub fn destination(&self, seed: u64) -> u64 {
+        self.maps
+            .iter()
+            .fold(seed, |seed, map| map.ranges.map(seed))
+    }
+
+    pub fn destinations(&self, seeds: &IntervalSet<u64>) -> IntervalSet<u64> {
+        self.maps
+            .iter()
+            .fold(seeds.clone(), |seeds, map| map.ranges.map_set(&seeds))
+    }
+}
+
+impl Map {
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        map(
+            separated_pair(
+                take_till(|c| c == ' '),
+                tag(" map:\n"),
+                separated_list1(newline, parse_range),
+            ),
+            |(name, ranges)| Self {
+                name: name.to_string(),
+                ranges: PiecewiseMap::new(ranges.into_iter().flatten()),
+            },
+        )(input)
+    }
+}
+
+/// Parses a `destination source length` line into a piece of a map, if it isn't empty
+fn parse_range(input: &str) -> IResult<&str, Option<(Interval<u64>, u64)>> {
+    map(
+        tuple((u64, preceded(space1, u64), preceded(space1, u64))),
+        |(destination_start, source_start, length)| {
+            Interval::with_len(source_start, length).map(|source| (source, destination_start))
+        },
+    )(input)
+}
@@ -9,6 +9,7 @@ use nom::{
     sequence::separated_pair,
     IResult,
 };
+use tracing::info_span;
 
 #[derive(Debug)]
 struct Race {
@@ -75,11 +76,15 @@ impl Race {
 
 fn main() {
     let input = challenge_input();
-    let races = Race::parse_many(&input).expect("Invalid input").1;
-    let part_1: u64 = races.iter().map(Race::winning_options).product();
+    let (races, race) = info_span!("parse").in_scope(|| {
+        let races = Race::parse_many(&input).expect("Invalid input").1;
+        let race = Race::parse_one(&input).expect("Invalid input").1;
+        (races, race)
+    });
+    let part_1: u64 =
+        info_span!("part_1").in_scope(|| races.iter().map(Race::winning_options).product());
     println!("{}", part_1);
 
-    let race = Race::parse_one(&input).expect("Invalid input").1;
-    let part_2 = race.winning_options();
+    let part_2 = info_span!("part_2").in_scope(|| race.winning_options());
     println!("{}", part_2);
 }
@@ -1,5 +1,6 @@
 use aoc_2023_common::challenge_input;
 use itertools::Itertools;
+use tracing::info_span;
 
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 pub enum Rank {
@@ -45,7 +46,7 @@ pub enum HandType {
 }
 use HandType::*;
 
-#[derive(PartialOrd, Ord, PartialEq, Eq, Debug)]
+#[derive(PartialOrd, Ord, PartialEq, Eq, Debug, Clone, Copy)]
 pub enum Game {
     WithoutJokers,
     WithJokers,
@@ -145,17 +146,24 @@ fn total_winnings(hands: &[Hand]) -> u64 {
 
 fn main() {
     let input = challenge_input();
-    let mut hands_1 = input
-        .lines()
-        .map(|line| Hand::parse(line, Game::WithoutJokers))
-        .collect::<Vec<_>>();
-    let mut hands_2 = input
-        .lines()
-        .map(|line| Hand::parse(line, Game::WithJokers))
-        .collect::<Vec<_>>();
-    hands_1.sort_unstable();
-    hands_2.sort_unstable();
-
-    println!("{}", total_winnings(&hands_1));
-    println!("{}", total_winnings(&hands_2));
+    let (mut hands_1, mut hands_2) = info_span!("parse").in_scope(|| {
+        let hands = |game| {
+            input
+                .lines()
+                .map(|line| Hand::parse(line, game))
+                .collect::<Vec<_>>()
+        };
+        (hands(Game::WithoutJokers), hands(Game::WithJokers))
+    });
+
+    let part_1 = info_span!("part_1").in_scope(|| {
+        hands_1.sort_unstable();
+        total_winnings(&hands_1)
+    });
+    println!("{part_1}");
+    let part_2 = info_span!("part_2").in_scope(|| {
+        hands_2.sort_unstable();
+        total_winnings(&hands_2)
+    });
+    println!("{part_2}");
 }
@@ -1,8 +1,9 @@
-use num::integer::lcm;
-use rayon::prelude::*;
 use std::collections::HashMap;
 
 use aoc_2023_common::challenge_input;
+use aoc_common::numtheory::{crt, CrtError};
+use aoc_common::parallel::prelude::*;
+use tracing::{debug, info_span};
 
 type Node = [char; 3];
 
@@ -72,6 +73,109 @@ impl Map {
 
         panic!("QED");
     }
+
+    fn next(&self, node: Node, step: usize) -> Node {
+        let (left, right) = self.network.get(&node).expect("invalid node in network");
+        match self.instructions[step % self.instructions.len()] {
+            Instruction::Left => *left,
+            Instruction::Right => *right,
+        }
+    }
+
+    /// Walks from `start` until the ghost is back in a state it has been in before
+    fn ghost(&self, start: Node) -> Ghost {
+        let mut seen: HashMap<(Node, usize), usize> = HashMap::new();
+        let mut arrivals = Vec::new();
+        let mut node = start;
+
+        for step in 0.. {
+            let state = (node, step % self.instructions.len());
+            if let Some(&cycle_start) = seen.get(&state) {
+                return Ghost {
+                    cycle_start,
+                    cycle_len: step - cycle_start,
+                    arrivals,
+                };
+            }
+            seen.insert(state, step);
+
+            if matches!(node, [_, _, 'Z']) {
+                arrivals.push(step);
+            }
+            node = self.next(node, step);
+        }
+
+        panic!("QED");
+    }
+}
+
+/// The steps at which a ghost stands on a node ending in `Z`.
+#[derive(Debug)]
+struct Ghost {
+    /// The first step of the part of the walk that repeats forever
+    cycle_start: usize,
+    cycle_len: usize,
+    /// Arrivals up to the first repetition, the ones before `cycle_start` only happen once
+    arrivals: Vec<usize>,
+}
+
+impl Ghost {
+    fn arrives_at(&self, step: usize) -> bool {
+        if step < self.cycle_start {
+            return self.arrivals.contains(&step);
+        }
+        self.arrivals
+            .iter()
+            .any(|&a| a >= self.cycle_start && (step - a).is_multiple_of(self.cycle_len))
+    }
+
+    fn repeating_arrivals(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.arrivals
+            .iter()
+            .filter(|&&a| a >= self.cycle_start)
+            .map(|&a| (a as i64, self.cycle_len as i64))
+    }
+}
+
+/// The first step at which all ghosts arrive at a `Z` node together. That can be past what a
+/// 32-bit `usize` holds.
+fn first_common_arrival(ghosts: &[Ghost]) -> Option<u64> {
+    // Arrivals before a ghost starts repeating only happen once, so just check them
+    let once = ghosts
+        .iter()
+        .flat_map(|g| g.arrivals.iter().filter(|&&a| a < g.cycle_start))
+        .filter(|&&step| ghosts.iter().all(|g| g.arrives_at(step)))
+        .min()
+        .map(|&step| step as u64);
+
+    // Every combination of repeating arrivals is a system of congruences
+    let combinations = ghosts.iter().fold(vec![vec![]], |combinations, ghost| {
+        combinations
+            .iter()
+            .flat_map(|combination| {
+                ghost.repeating_arrivals().map(move |arrival| {
+                    let mut next: Vec<(i64, i64)> = combination.clone();
+                    next.push(arrival);
+                    next
+                })
+            })
+            .collect()
+    });
+
+    // Only valid once every ghost has started repeating
+    let earliest = ghosts.iter().map(|g| g.cycle_start).max()? as i64;
+    let repeating = combinations
+        .into_iter()
+        .filter_map(|congruences| match crt(congruences) {
+            Ok((x, _)) if x >= earliest => Some(x),
+            Ok((x, m)) => Some(x + (earliest - x + m - 1) / m * m),
+            Err(CrtError::NoSolution) => None,
+            Err(CrtError::Overflow) => panic!("ghosts take too long to line up"),
+        })
+        .min()
+        .map(|step| step as u64);
+
+    once.into_iter().chain(repeating).min()
 }
 
 impl Instruction {
@@ -86,20 +190,28 @@ impl Instruction {
 
 fn main() {
     let input = challenge_input();
-    let map = Map::parse(&input);
-    println!("{}", map.step_count(['A', 'A', 'A']));
-
-    let start_nodes: Vec<&Node> = map
-        .network
-        .keys()
-        .filter(|key| matches!(key, [_, _, 'A']))
-        .collect();
-
-    let step_count_2: Vec<usize> = start_nodes
-        .par_iter()
-        .map(|&&node| map.step_count(node))
-        .collect();
+    let map = info_span!("parse").in_scope(|| Map::parse(&input));
+    let part_1 = info_span!("part_1").in_scope(|| map.step_count(['A', 'A', 'A']));
+    println!("{part_1}");
+
+    let part_2 = info_span!("part_2")
+        .in_scope(|| {
+            let start_nodes: Vec<&Node> = map
+                .network
+                .keys()
+                .filter(|key| matches!(key, [_, _, 'A']))
+                .collect();
+
+            let ghosts: Vec<Ghost> = start_nodes
+                .par_iter()
+                .map(|&&node| map.ghost(node))
+                .collect();
+            for ghost in &ghosts {
+                debug!(?ghost);
+            }
 
-    let part_2 = step_count_2.iter().cloned().reduce(lcm).unwrap();
+            first_common_arrival(&ghosts)
+        })
+        .expect("ghosts never line up");
     println!("{}", part_2);
 }
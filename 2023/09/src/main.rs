@@ -1,4 +1,5 @@
 use aoc_2023_common::challenge_input;
+use tracing::info_span;
 
 fn deltas(sequence: &[i64]) -> Vec<i64> {
     sequence
@@ -37,20 +38,25 @@ fn extrapolate(sequence: &[i64]) -> i64 {
 
 fn main() {
     let input = challenge_input();
-    let mut sequences: Vec<Vec<i64>> = input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .filter_map(|num| num.parse().ok())
-                .collect()
-        })
-        .collect();
-
-    let part_1: i64 = sequences.iter().map(|seq| extrapolate(seq)).sum();
+    let mut sequences: Vec<Vec<i64>> = info_span!("parse").in_scope(|| {
+        input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .filter_map(|num| num.parse().ok())
+                    .collect()
+            })
+            .collect()
+    });
+
+    let part_1: i64 =
+        info_span!("part_1").in_scope(|| sequences.iter().map(|seq| extrapolate(seq)).sum());
     println!("{}", part_1);
 
-    sequences.iter_mut().for_each(|seq| seq.reverse());
-    let part_2: i64 = sequences.iter().map(|seq| extrapolate(seq)).sum();
+    let part_2: i64 = info_span!("part_2").in_scope(|| {
+        sequences.iter_mut().for_each(|seq| seq.reverse());
+        sequences.iter().map(|seq| extrapolate(seq)).sum()
+    });
 
     println!("{}", part_2);
 }
@@ -1,22 +1,38 @@
-use colored::Colorize;
 use std::{collections::HashSet, fmt::Display, ops::Add};
 
 use aoc_2023_common::challenge_input;
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+use aoc_common::flood::label_regions;
+use aoc_common::geometry::Polygon;
+use aoc_common::grid::Grid;
+use aoc_common::point::Point2;
+use aoc_common::render::{Color, Frame, Glyph, Style};
+use aoc_common::tile::Tile;
+use tracing::{debug, info_span, Level};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Tile)]
 enum Tile {
+    #[tile('S')]
     Start,
+    /// The examples mark some ground as inside or outside
+    #[tile('.', 'I', 'O')]
     Ground,
+    #[tile(delegate)]
     Pipe(PipeType),
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Tile)]
 enum PipeType {
+    #[tile('|')]
     NorthSouth,
+    #[tile('-')]
     EastWest,
+    #[tile('L')]
     NorthEast,
+    #[tile('J')]
     NorthWest,
+    #[tile('7')]
     SouthWest,
+    #[tile('F')]
     SouthEast,
 }
 
@@ -50,24 +66,6 @@ impl Direction {
             West => East,
         }
     }
-
-    fn right(&self) -> Self {
-        match &self {
-            North => East,
-            East => South,
-            South => West,
-            West => North,
-        }
-    }
-
-    fn left(&self) -> Self {
-        match &self {
-            North => West,
-            East => North,
-            South => East,
-            West => South,
-        }
-    }
 }
 
 use Direction::*;
@@ -75,140 +73,135 @@ use PipeType::*;
 use Tile::*;
 
 struct Map {
-    tiles: Vec<Vec<Tile>>,
-}
-
-#[derive(Hash, Copy, Clone, Debug, PartialEq, Eq)]
-struct Point {
-    x: i16,
-    y: i16,
+    tiles: Grid<Tile>,
 }
 
-impl Add<Direction> for Point {
-    type Output = Point;
+impl Add<Direction> for Point2 {
+    type Output = Point2;
 
     fn add(self, rhs: Direction) -> Self::Output {
         match rhs {
-            Direction::North => Point {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Direction::East => Point {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Direction::South => Point {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Direction::West => Point {
-                x: self.x - 1,
-                y: self.y,
-            },
+            North => Point2::new(self.x, self.y - 1),
+            East => Point2::new(self.x + 1, self.y),
+            South => Point2::new(self.x, self.y + 1),
+            West => Point2::new(self.x - 1, self.y),
         }
     }
 }
 
-impl Point {
-    fn neighbors(&self) -> [Self; 4] {
-        [*self + North, *self + East, *self + South, *self + West]
-    }
-}
-
 impl Map {
     fn parse(input: &str) -> Self {
-        let tiles = input
-            .lines()
-            .map(|line| {
-                line.bytes()
-                    .map(|b| match b {
-                        b'-' => Pipe(EastWest),
-                        b'|' => Pipe(NorthSouth),
-                        b'F' => Pipe(SouthEast),
-                        b'7' => Pipe(SouthWest),
-                        b'L' => Pipe(NorthEast),
-                        b'J' => Pipe(NorthWest),
-                        b'S' => Start,
-                        _ => Ground,
-                    })
-                    .collect()
-            })
-            .collect();
+        let tiles = Grid::parse_tiles(input).unwrap_or_else(|e| panic!("{e}"));
 
         Self { tiles }
     }
 
-    fn get(&self, &Point { x, y }: &Point) -> Tile {
-        if x < 0 || y < 0 {
-            return Ground;
-        }
+    fn get(&self, point: &Point2) -> Tile {
+        self.tiles.get(*point).copied().unwrap_or(Ground)
+    }
 
-        match self
-            .tiles
-            .get(y as usize)
-            .and_then(|row| row.get(x as usize))
-        {
-            Some(t) => *t,
-            None => Ground,
-        }
+    fn start_point(&self) -> Point2 {
+        self.tiles
+            .position(|tile| tile == &Start)
+            .expect("no start point")
+    }
+
+    /// The first direction from the start that leads into a pipe connecting back to it
+    fn start_direction(&self, start: Point2) -> Direction {
+        [North, East, South, West]
+            .into_iter()
+            .find(|&dir| match self.get(&(start + dir)) {
+                Pipe(pipe_type) => {
+                    let (dir1, dir2) = pipe_type.directions();
+                    dir.inverse() == dir1 || dir.inverse() == dir2
+                }
+                _ => false,
+            })
+            .expect("start isn't connected to a pipe")
     }
 
-    fn start_point(&self) -> Point {
-        for (y, row) in self.tiles.iter().enumerate() {
-            for (x, tile) in row.iter().enumerate() {
-                if tile == &Start {
-                    return Point {
-                        x: x as i16,
-                        y: y as i16,
+    /// Every point of the loop in order, starting at the start point
+    fn path(&self) -> Vec<Point2> {
+        let start = self.start_point();
+        let mut current_point = start;
+        let mut current_dir = self.start_direction(start);
+        let mut path = vec![start];
+
+        loop {
+            current_point = current_point + current_dir;
+            match self.get(&current_point) {
+                Pipe(pipe_type) => {
+                    let (dir1, dir2) = pipe_type.directions();
+                    current_dir = if current_dir.inverse() == dir1 {
+                        dir2
+                    } else {
+                        dir1
                     };
                 }
+                Start => return path,
+                Ground => panic!("hit the ground while traversing path"),
             }
+            path.push(current_point);
         }
-        panic!("no start point");
     }
 
-    fn print_with_path_and_floods(
-        &self,
-        path: &HashSet<Point>,
-        left_flood: &HashSet<Point>,
-        right_flood: &HashSet<Point>,
-    ) {
-        for (y, row) in self.tiles.iter().enumerate() {
-            for (x, tile) in row.iter().enumerate() {
-                let char = match tile {
-                    Pipe(EastWest) => '━',
-                    Pipe(NorthSouth) => '┃',
-                    Pipe(SouthEast) => '┏',
-                    Pipe(SouthWest) => '┓',
-                    Pipe(NorthEast) => '┗',
-                    Pipe(NorthWest) => '┛',
-                    Start => 'S',
-                    Ground => '.',
-                };
-
-                let point = Point {
-                    x: x as i16,
-                    y: y as i16,
-                };
-
-                if path.contains(&point) {
-                    print!("{}", char.to_string().green());
-                } else if left_flood.contains(&point) {
-                    print!("{}", char.to_string().red());
-                } else if right_flood.contains(&point) {
-                    print!("{}", char.to_string().blue());
-                } else {
-                    print!("{}", char);
-                }
+    /// The map with its loop, and every region of ground marked by the side of the loop it's on
+    fn loop_frame(&self) -> Frame {
+        let path = self.path();
+        let path_set: HashSet<Point2> = path.iter().copied().collect();
+        let polygon = Polygon::new(path);
+
+        let regions = label_regions(&self.tiles, |p| (!path_set.contains(&p)).then_some(()));
+        let mut inside = HashSet::new();
+        let mut outside = HashSet::new();
+        for region in regions.regions() {
+            if polygon.contains(region.points[0]) {
+                inside.extend(&region.points);
+            } else {
+                outside.extend(&region.points);
             }
-            println!();
         }
+        self.frame_with_path_and_floods(&path_set, &inside, &outside)
+    }
+
+    /// The map with the loop in green, and the ground inside and outside of it as red `I`s and
+    /// blue `O`s
+    fn frame_with_path_and_floods(
+        &self,
+        path: &HashSet<Point2>,
+        inside: &HashSet<Point2>,
+        outside: &HashSet<Point2>,
+    ) -> Frame {
+        Frame::from_grid(&self.tiles, |tile| {
+            Glyph::plain(match tile {
+                Pipe(EastWest) => '━',
+                Pipe(NorthSouth) => '┃',
+                Pipe(SouthEast) => '┏',
+                Pipe(SouthWest) => '┓',
+                Pipe(NorthEast) => '┗',
+                Pipe(NorthWest) => '┛',
+                Start => 'S',
+                Ground => '.',
+            })
+        })
+        .highlight(path.iter().copied(), Style::fg(Color::GREEN))
+        .draw(
+            inside.iter().copied(),
+            Glyph::new('I', Style::fg(Color::RED)),
+        )
+        .draw(
+            outside.iter().copied(),
+            Glyph::new('O', Style::fg(Color::BLUE)),
+        )
+        .legend(Glyph::new('━', Style::fg(Color::GREEN)), "loop")
+        .legend(Glyph::new('I', Style::fg(Color::RED)), "inside")
+        .legend(Glyph::new('O', Style::fg(Color::BLUE)), "outside")
     }
 }
 
 impl Display for Map {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in &self.tiles {
+        for row in self.tiles.rows() {
             for tile in row {
                 let char = match tile {
                     Pipe(EastWest) => '━',
@@ -228,135 +221,64 @@ impl Display for Map {
     }
 }
 
-fn flood_fill(path: &HashSet<Point>, start: Point) -> Option<HashSet<Point>> {
-    let mut flood = HashSet::new();
-    let mut latest = HashSet::new();
+fn main() {
+    let input = challenge_input();
+    let map = info_span!("parse").in_scope(|| Map::parse(&input));
 
-    if path.contains(&start) {
-        // We're attempting to flood on the path, return an empty flood
-        return Some(HashSet::new());
-    }
+    // PART 1: The farthest point is halfway along the loop
+    // ----------------------------------------------------
 
-    flood.insert(start);
-    latest.insert(start);
+    let part_1_span = info_span!("part_1").entered();
+    let path = map.path();
+    let part_1 = path.len() / 2;
+    drop(part_1_span);
 
-    for _ in 0..50 {
-        if latest.is_empty() {
-            // we have flooded the entire area
-            return Some(flood);
-        }
+    // PART 2: Pick's theorem gives the number of tiles strictly within the loop
+    // -------------------------------------------------------------------------
 
-        let new: HashSet<Point> = latest
-            .iter()
-            .flat_map(|point| point.neighbors())
-            .filter(|point| !flood.contains(point) && !path.contains(point))
-            .collect();
+    let part_2 = info_span!("part_2").in_scope(|| Polygon::new(path).interior_points());
 
-        flood.extend(&new);
-        latest = new;
+    if tracing::enabled!(Level::DEBUG) {
+        debug!("\n{}", map.loop_frame());
     }
 
-    // If after 50 cycles we are still expanding, it's the wrong side
-    None
+    println!("{}", part_1);
+    println!("{}", part_2);
 }
 
-// Hardcoded because I didn't bother to check what the start direction is algorithmically.
-const START_DIR: Direction = North;
-
-fn main() {
-    let input = challenge_input();
-    let map = Map::parse(&input);
-    let start = map.start_point();
-
-    // PART 1: Determine the path length and create the path set
-    // ---------------------------------------------------------
-
-    let mut current_point = start;
-    let mut current_dir = START_DIR;
-    let mut path_set: HashSet<Point> = HashSet::new();
-
-    path_set.insert(start);
-    loop {
-        current_point = current_point + current_dir;
-        path_set.insert(current_point);
-        match map.get(&current_point) {
-            Pipe(pipe_type) => {
-                let (dir1, dir2) = pipe_type.directions();
-                current_dir = if current_dir.inverse() == dir1 {
-                    dir2
-                } else {
-                    dir1
-                };
-            }
-            Start => break,
-            Ground => panic!("hit the ground while traversing path"),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loops() {
+        for (name, example) in [
+            ("example", include_str!("../input/example.txt")),
+            ("example_2", include_str!("../input/example_2.txt")),
+            ("example_3", include_str!("../input/example_3.txt")),
+            ("example_4", include_str!("../input/example_4.txt")),
+            (
+                "example_custom",
+                include_str!("../input/example_custom.txt"),
+            ),
+            ("example_flood", include_str!("../input/example_flood.txt")),
+        ] {
+            insta::assert_snapshot!(name, Map::parse(example).loop_frame().to_string());
         }
     }
 
-    let part_1 = (path_set.len() + 1) / 2;
-
-    // PART 2: Flood both sides to determine surface area within the loop
-    // ------------------------------------------------------------------
-
-    let mut current_point = start;
-    let mut current_dir = START_DIR;
-    let mut left_floods: HashSet<Point> = HashSet::new();
-    let mut right_floods: HashSet<Point> = HashSet::new();
-
-    enum OverflowSide {
-        Left,
-        Right,
-    }
-    let mut overflow_side: Option<OverflowSide> = None;
-
-    loop {
-        let left = current_point + current_dir.left();
-        let right = current_point + current_dir.right();
-        let left_next = current_point + current_dir + current_dir.left();
-        let right_next = current_point + current_dir + current_dir.right();
-
-        match flood_fill(&path_set, left) {
-            Some(flood) => left_floods.extend(flood),
-            None => overflow_side = Some(OverflowSide::Left),
-        }
-
-        match flood_fill(&path_set, right) {
-            Some(flood) => right_floods.extend(flood),
-            None => overflow_side = Some(OverflowSide::Right),
-        }
-
-        match flood_fill(&path_set, left_next) {
-            Some(flood) => left_floods.extend(flood),
-            None => overflow_side = Some(OverflowSide::Left),
-        }
-
-        match flood_fill(&path_set, right_next) {
-            Some(flood) => right_floods.extend(flood),
-            None => overflow_side = Some(OverflowSide::Right),
+    #[test]
+    fn examples() {
+        let path = Map::parse(include_str!("../input/example.txt")).path();
+        assert_eq!(path.len() / 2, 4);
+
+        for (example, inside) in [
+            (include_str!("../input/example_2.txt"), 4),
+            (include_str!("../input/example_3.txt"), 8),
+            (include_str!("../input/example_4.txt"), 10),
+        ] {
+            let path = Map::parse(example).path();
+            assert_eq!(Polygon::new(path).interior_points(), inside);
         }
-
-        current_point = current_point + current_dir;
-
-        match map.get(&current_point) {
-            Pipe(pipe_type) => {
-                let (dir1, dir2) = pipe_type.directions();
-                current_dir = if current_dir.inverse() == dir1 {
-                    dir2
-                } else {
-                    dir1
-                };
-            }
-            Start => break,
-            Ground => panic!("hit the ground while traversing path"),
-        }
-    }
-
-    map.print_with_path_and_floods(&path_set, &left_floods, &right_floods);
-
-    println!("{}", part_1);
-    match overflow_side {
-        Some(OverflowSide::Left) => println!("{}", right_floods.len()),
-        Some(OverflowSide::Right) => println!("{}", left_floods.len()),
-        None => panic!("Neither side overflowed, can't determine inside of loop"),
     }
 }
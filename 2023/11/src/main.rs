@@ -1,4 +1,5 @@
 use aoc_2023_common::challenge_input;
+use tracing::info_span;
 
 type UniverseMatrix = Vec<Vec<bool>>;
 type Coordinate = (usize, usize);
@@ -56,17 +57,18 @@ impl<const E: usize> From<Universe> for ExpandedUniverse<E> {
 }
 
 impl<const E: usize> ExpandedUniverse<E> {
-    fn distances(&self) -> usize {
+    fn distances(&self) -> u64 {
         self.0
             .iter()
             .flat_map(|a| self.0.iter().map(|b| distance(a, b)).collect::<Vec<_>>())
-            .sum::<usize>()
+            .sum::<u64>()
             / 2
     }
 }
 
-fn distance(a: &Coordinate, b: &Coordinate) -> usize {
-    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+/// A `u64`, because their sum overflows a 32-bit `usize`
+fn distance(a: &Coordinate, b: &Coordinate) -> u64 {
+    (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as u64
 }
 
 fn parse(input: &str) -> UniverseMatrix {
@@ -78,11 +80,17 @@ fn parse(input: &str) -> UniverseMatrix {
 
 fn main() {
     let input = challenge_input();
-    let universe: Universe = parse(&input).into();
+    let universe: Universe = info_span!("parse").in_scope(|| parse(&input).into());
 
-    let expanded: ExpandedUniverse<2> = universe.clone().into();
-    println!("{}", expanded.distances());
+    let part_1 = info_span!("part_1").in_scope(|| {
+        let expanded: ExpandedUniverse<2> = universe.clone().into();
+        expanded.distances()
+    });
+    println!("{part_1}");
 
-    let expanded: ExpandedUniverse<1_000_000> = universe.into();
-    println!("{}", expanded.distances());
+    let part_2 = info_span!("part_2").in_scope(|| {
+        let expanded: ExpandedUniverse<1_000_000> = universe.into();
+        expanded.distances()
+    });
+    println!("{part_2}");
 }
@@ -1,27 +1,33 @@
 use aoc_2024_common::challenge_input;
+use tracing::info_span;
 
 fn main() {
     let input = challenge_input();
-    let (mut left, mut right): (Vec<isize>, Vec<isize>) = input
-        .lines()
-        .map(|l| {
-            let (l, r) = l.split_once("   ").unwrap();
-            (l.parse::<isize>().unwrap(), r.parse::<isize>().unwrap())
-        })
-        .unzip();
-    left.sort();
-    right.sort();
+    let (left, right) = info_span!("parse").in_scope(|| {
+        let (mut left, mut right): (Vec<isize>, Vec<isize>) = input
+            .lines()
+            .map(|l| {
+                let (l, r) = l.split_once("   ").unwrap();
+                (l.parse::<isize>().unwrap(), r.parse::<isize>().unwrap())
+            })
+            .unzip();
+        left.sort();
+        right.sort();
+        (left, right)
+    });
 
-    let part_1: usize = left
-        .iter()
-        .zip(right.iter())
-        .map(|(&x, &y)| x.abs_diff(y))
-        .sum();
+    let part_1: usize = info_span!("part_1").in_scope(|| {
+        left.iter()
+            .zip(right.iter())
+            .map(|(&x, &y)| x.abs_diff(y))
+            .sum()
+    });
 
-    let part_2: isize = left
-        .iter()
-        .map(|i| i * right.iter().filter(|j| &i == j).count() as isize)
-        .sum();
+    let part_2: isize = info_span!("part_2").in_scope(|| {
+        left.iter()
+            .map(|i| i * right.iter().filter(|j| &i == j).count() as isize)
+            .sum()
+    });
 
     println!("{part_1}");
     println!("{part_2}");
@@ -1,5 +1,6 @@
 use aoc_2024_common::challenge_input;
 use std::cmp::Ordering::*;
+use tracing::info_span;
 
 fn is_safe(report: &&Vec<isize>) -> bool {
     match report[0].cmp(&report[1]) {
@@ -16,27 +17,31 @@ fn is_safe(report: &&Vec<isize>) -> bool {
 fn main() {
     let input = challenge_input();
 
-    let parsed: Vec<Vec<isize>> = input
-        .lines()
-        .map(|l| {
-            l.split_whitespace()
-                .map(|n| n.parse::<isize>().unwrap())
-                .collect()
-        })
-        .collect();
+    let parsed: Vec<Vec<isize>> = info_span!("parse").in_scope(|| {
+        input
+            .lines()
+            .map(|l| {
+                l.split_whitespace()
+                    .map(|n| n.parse::<isize>().unwrap())
+                    .collect()
+            })
+            .collect()
+    });
 
-    let part_1 = parsed.iter().filter(is_safe).count();
+    let part_1 = info_span!("part_1").in_scope(|| parsed.iter().filter(is_safe).count());
 
-    let part_2 = parsed
-        .iter()
-        .filter(|&report| {
-            (0..report.len()).any(|to_skip| {
-                let mut report = report.clone();
-                report.remove(to_skip);
-                is_safe(&&report)
+    let part_2 = info_span!("part_2").in_scope(|| {
+        parsed
+            .iter()
+            .filter(|&report| {
+                (0..report.len()).any(|to_skip| {
+                    let mut report = report.clone();
+                    report.remove(to_skip);
+                    is_safe(&&report)
+                })
             })
-        })
-        .count();
+            .count()
+    });
 
     println!("{part_1}");
     println!("{part_2}");
@@ -1,29 +1,44 @@
 use aoc_2024_common::challenge_input;
-use memoize::memoize;
+use aoc_common::memo::Memo;
+use tracing::{debug, info_span};
 
 fn main() {
     let input = challenge_input();
-    let parsed: Vec<u64> = input.split(' ').map(|n| n.parse().unwrap()).collect();
+    let parsed: Vec<u64> = info_span!("parse").in_scope(|| {
+        input
+            .split_whitespace()
+            .map(|n| n.parse().unwrap())
+            .collect()
+    });
 
-    let part_1 = parsed
-        .iter()
-        .map(|s| count_after_blinks(*s, 25))
-        .sum::<u64>();
+    let mut memo = Memo::new();
+    let part_1 = info_span!("part_1").in_scope(|| {
+        parsed
+            .iter()
+            .map(|s| count_after_blinks(&mut memo, *s, 25))
+            .sum::<u64>()
+    });
+    debug!(memoised = memo.len());
     println!("{part_1}");
-    let part_2 = parsed
-        .iter()
-        .map(|s| count_after_blinks(*s, 75))
-        .sum::<u64>();
+    let part_2 = info_span!("part_2").in_scope(|| {
+        parsed
+            .iter()
+            .map(|s| count_after_blinks(&mut memo, *s, 75))
+            .sum::<u64>()
+    });
+    debug!(memoised = memo.len());
     println!("{part_2}");
 }
 
-#[memoize(Capacity: 75_000)]
-fn count_after_blinks(stone: u64, blinks: u64) -> u64 {
+fn count_after_blinks(memo: &mut Memo<(u64, u64), u64>, stone: u64, blinks: u64) -> u64 {
     if blinks == 0 {
         return 1;
     }
-    let (left, right) = blink(stone);
-    count_after_blinks(left, blinks - 1) + right.map_or(0, |s| count_after_blinks(s, blinks-1))
+    memo.get_or((stone, blinks), |memo| {
+        let (left, right) = blink(stone);
+        count_after_blinks(memo, left, blinks - 1)
+            + right.map_or(0, |s| count_after_blinks(memo, s, blinks - 1))
+    })
 }
 
 fn blink(stone: u64) -> (u64, Option<u64>) {
@@ -31,8 +46,11 @@ fn blink(stone: u64) -> (u64, Option<u64>) {
         return (1, None);
     }
     let width = stone.ilog10() + 1;
-    if width % 2 == 0 {
-        return (stone / 10_u64.pow(width / 2), Some(stone % 10_u64.pow(width / 2)));
+    if width.is_multiple_of(2) {
+        return (
+            stone / 10_u64.pow(width / 2),
+            Some(stone % 10_u64.pow(width / 2)),
+        );
     }
-    return (stone * 2024, None);
+    (stone * 2024, None)
 }
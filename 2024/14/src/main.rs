@@ -1,11 +1,13 @@
 use aoc_2024_common::challenge_input;
+use aoc_common::cycle::brent;
+use aoc_common::export::{path_requested, ImageExport, Palette};
+use aoc_common::flood::Bounds;
+use aoc_common::point::Point2;
+use aoc_common::render::{Color, Frame, Glyph, Style};
 use nalgebra::Vector2;
 use std::cmp::Ordering::*;
-
-const W: i64 = 101;
-const H: i64 = 103;
-// const W: i64 = 11;
-// const H: i64 = 7;
+use std::collections::HashMap;
+use tracing::{debug, info_span, trace, warn};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 struct Robot {
@@ -14,19 +16,64 @@ struct Robot {
 }
 
 impl Robot {
-    pub fn position_at(&self, second: i64) -> Vector2<i64> {
+    pub fn position_at(&self, second: i64, room: Room) -> Vector2<i64> {
         let linear = self.position + (self.velocity * second);
-        Vector2::new(linear.x.rem_euclid(W), linear.y.rem_euclid(H))
+        Vector2::new(
+            linear.x.rem_euclid(room.width),
+            linear.y.rem_euclid(room.height),
+        )
     }
 }
 
-fn part_1(robots: &[Robot], seconds: i64) -> i64 {
+/// The room the robots walk around in, wrapping around its walls
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct Room {
+    width: i64,
+    height: i64,
+}
+
+impl Room {
+    /// The room of the real inputs
+    const BATHROOM: Room = Room {
+        width: 101,
+        height: 103,
+    };
+    /// The room of the example in the puzzle
+    const EXAMPLE: Room = Room {
+        width: 11,
+        height: 7,
+    };
+
+    /// The example's room if every robot starts in it, the real inputs' otherwise. The puzzle
+    /// doesn't give the size, so a real input with every robot in the top left corner would
+    /// be taken for an example.
+    fn for_robots(robots: &[Robot]) -> Self {
+        let room = if robots.iter().all(|r| Room::EXAMPLE.contains(r.position)) {
+            Room::EXAMPLE
+        } else {
+            Room::BATHROOM
+        };
+        assert!(
+            robots.iter().all(|r| room.contains(r.position)),
+            "a robot starts outside of the {}x{} room",
+            room.width,
+            room.height
+        );
+        room
+    }
+
+    fn contains(&self, position: Vector2<i64>) -> bool {
+        (0..self.width).contains(&position.x) && (0..self.height).contains(&position.y)
+    }
+}
+
+fn part_1(robots: &[Robot], room: Room, seconds: i64) -> i64 {
     let (mut q1, mut q2, mut q3, mut q4) = (0, 0, 0, 0);
 
     for robot in robots {
-        let pos = robot.position_at(seconds);
+        let pos = robot.position_at(seconds, room);
 
-        match (pos.x.cmp(&(W / 2)), pos.y.cmp(&(H / 2))) {
+        match (pos.x.cmp(&(room.width / 2)), pos.y.cmp(&(room.height / 2))) {
             (Equal, _) | (_, Equal) => (),
             (Less, Less) => q1 += 1,
             (Less, Greater) => q2 += 1,
@@ -34,31 +81,55 @@ fn part_1(robots: &[Robot], seconds: i64) -> i64 {
             (Greater, Greater) => q4 += 1,
         }
     }
+    debug!(q1, q2, q3, q4, "robots per quadrant");
     q1 * q2 * q3 * q4
 }
 
-/// Look for a straight line of at least 10 robots
-fn part_2(robots: &[Robot]) -> i64 {
-    (0..i64::MAX)
-        .map(|second| {
-            robots
-                .iter()
-                .map(|r| r.position_at(second))
-                .collect::<Vec<_>>()
-        })
-        .position(|robots_at_second| {
-            robots_at_second.iter().any(|robot| {
-                (0..10).all(|line_x| {
-                    robots_at_second.contains(&Vector2::new(robot.x + line_x, robot.y))
-                })
-            })
+/// Look for a straight line of at least 10 robots.
+/// Robots wrap around, so only the seconds before their positions repeat need checking.
+fn part_2(robots: &[Robot], room: Room) -> Option<i64> {
+    let positions: Vec<_> = robots.iter().map(|r| r.position).collect();
+    let (mu, lambda) = brent(&positions, |positions| {
+        positions
+            .iter()
+            .zip(robots)
+            .map(|(&p, r)| Robot { position: p, ..*r }.position_at(1, room))
+            .collect()
+    });
+    debug!(mu, lambda, "positions repeat");
+
+    (0..(mu + lambda) as i64).find(|&second| {
+        trace!(second);
+        let robots_at_second: Vec<_> = robots.iter().map(|r| r.position_at(second, room)).collect();
+        robots_at_second.iter().any(|robot| {
+            (0..10)
+                .all(|line_x| robots_at_second.contains(&Vector2::new(robot.x + line_x, robot.y)))
         })
-        .unwrap() as i64
+    })
 }
 
 fn main() {
     let input = challenge_input();
-    let robots = input
+    let (robots, room) = info_span!("parse").in_scope(|| {
+        let robots = parse(&input);
+        let room = Room::for_robots(&robots);
+        debug!(?room);
+        (robots, room)
+    });
+
+    println!(
+        "{}",
+        info_span!("part_1").in_scope(|| part_1(&robots, room, 100))
+    );
+    match info_span!("part_2").in_scope(|| part_2(&robots, room)) {
+        Some(second) => draw_world(&robots, room, second),
+        // Like in the example, which is too small for a tree
+        None => warn!("the robots never form a tree"),
+    }
+}
+
+fn parse(input: &str) -> Vec<Robot> {
+    input
         .lines()
         .map(|l| {
             let (p, v) = l.split_once(' ').unwrap();
@@ -69,26 +140,69 @@ fn main() {
                 velocity: Vector2::new(vx.parse().unwrap(), vy.parse().unwrap()),
             }
         })
-        .collect::<Vec<_>>();
-
-    println!("{}", part_1(&robots, 100));
-    draw_world(&robots, part_2(&robots));
+        .collect()
 }
 
-fn draw_world(robots: &[Robot], second: i64) {
-    for y in 0..H {
-        for x in 0..W {
-            let pos = Vector2::new(x, y);
-            let count = robots
-                .iter()
-                .filter(|r| r.position_at(second) == pos)
-                .count();
-            match count {
-                0 => print!("."),
-                n => print!("{}", n),
-            }
-        }
-        println!();
+fn draw_world(robots: &[Robot], room: Room, second: i64) {
+    let frame = world_frame(robots, room, second);
+    frame.print();
+
+    if let Some(path) = path_requested("--png") {
+        ImageExport::new(Palette::new().tile('.', Color::BLACK))
+            .scale(4)
+            .save_png(&frame, path)
+            .unwrap_or_else(|e| panic!("{e}"));
     }
     println!("{second}");
 }
+
+/// The robots after `second` seconds, with how many there are on every tile
+fn world_frame(robots: &[Robot], room: Room, second: i64) -> Frame {
+    let mut counts: HashMap<Point2, u32> = HashMap::new();
+    for robot in robots {
+        let pos = robot.position_at(second, room);
+        *counts.entry(Point2::new(pos.x, pos.y)).or_default() += 1;
+    }
+
+    let world = Bounds {
+        min: Point2::ORIGIN,
+        max: Point2::new(room.width - 1, room.height - 1),
+    };
+    Frame::from_bounds(&world, |p| match counts.get(&p) {
+        None => Glyph::new('.', Style::fg(Color::GREY)),
+        Some(&n) => Glyph::new(
+            char::from_digit(n.min(9), 10).unwrap(),
+            Style::fg(Color::GREEN).bold(),
+        ),
+    })
+    .border(Style::PLAIN)
+    .caption(format!("second {second}"), Style::PLAIN.bold())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let robots = parse(include_str!("../input/example.txt"));
+        let room = Room::for_robots(&robots);
+        assert_eq!(room, Room::EXAMPLE);
+        assert_eq!(part_1(&robots, room, 100), 12);
+        assert_eq!(part_2(&robots, room), None);
+    }
+
+    #[test]
+    fn tree() {
+        let robots = parse(include_str!("../input/1.txt"));
+        let room = Room::for_robots(&robots);
+        assert_eq!(room, Room::BATHROOM);
+        insta::assert_snapshot!(world_frame(&robots, room, 7572).to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the 101x103 room")]
+    fn robot_outside() {
+        Room::for_robots(&parse("p=0,0 v=1,1\np=101,5 v=1,1"));
+    }
+}
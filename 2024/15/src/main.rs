@@ -1,8 +1,15 @@
 use std::fmt;
 use std::fmt::Display;
+use std::time::Duration;
 
 use aoc_2024_common::challenge_input;
+use aoc_common::flood::Bounds;
+use aoc_common::player::{animate_requested, Player};
+use aoc_common::point::Point2;
+use aoc_common::render::{Color, Frame, Glyph, Style};
+use aoc_common::tile::Tile;
 use nalgebra::DMatrix;
+use tracing::{debug, info_span, trace, warn};
 
 #[derive(Eq, PartialEq, Debug, Clone)]
 struct Map {
@@ -46,6 +53,38 @@ impl Map {
         }
     }
 
+    fn follow(&mut self, instr: char) {
+        match instr {
+            '>' => self.push((0, 1)),
+            '^' => self.push((-1, 0)),
+            '<' => self.push((0, -1)),
+            'v' => self.push((1, 0)),
+            '\n' => (),
+            _ => warn!(?instr, "not a move"),
+        }
+    }
+
+    fn frame(&self, moves: usize) -> Frame {
+        let (rows, cols) = self.tiles.shape();
+        let warehouse = Bounds {
+            min: Point2::ORIGIN,
+            max: Point2::new(cols as i64 - 1, rows as i64 - 1),
+        };
+        let player = Point2::new(self.player.1 as i64, self.player.0 as i64);
+
+        Frame::from_bounds(&warehouse, |p| {
+            let tile = self.tiles[(p.y as usize, p.x as usize)];
+            let style = match tile {
+                Wall => Style::fg(Color::GREY),
+                Empty => Style::fg(Color::Ansi(236)),
+                Box => Style::fg(Color::YELLOW),
+            };
+            Glyph::new(char::from(tile), style)
+        })
+        .draw([player], Glyph::new('@', Style::fg(Color::RED).bold()))
+        .caption(format!("{moves} moves"), Style::PLAIN)
+    }
+
     fn gps_coordinate_sum(&self) -> usize {
         self.tiles
             .map_with_location(|row, col, tile| match tile {
@@ -72,15 +111,11 @@ impl From<&str> for Map {
 
         for (y, line) in input.lines().enumerate() {
             for (x, char) in line.chars().enumerate() {
-                match char {
-                    '#' => tiles.push(Wall),
-                    '.' => tiles.push(Empty),
-                    'O' => tiles.push(Box),
-                    '@' => {
-                        player = (y, x);
-                        tiles.push(Empty)
-                    }
-                    c => panic!("Invalid item {c} on map"),
+                if char == '@' {
+                    player = (y, x);
+                    tiles.push(Empty);
+                } else {
+                    tiles.push(Tile::try_from(char).unwrap_or_else(|e| panic!("{e}")));
                 }
             }
             // could be more efficient
@@ -93,38 +128,53 @@ impl From<&str> for Map {
     }
 }
 
-#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Tile)]
 enum Tile {
+    #[tile('#')]
     Wall,
+    #[tile('.')]
     Empty,
+    #[tile('O')]
     Box,
 }
-impl Display for Tile {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Wall => write!(f, "#"),
-            Empty => write!(f, "."),
-            Box => write!(f, "O"),
-        }
-    }
-}
 
 use Tile::*;
 
+/// One frame per move of the robot
+fn animate(mut map: Map, instructions: &str) {
+    let first = map.frame(0);
+    let moves = instructions
+        .chars()
+        .filter(|&c| c != '\n')
+        .enumerate()
+        .map(move |(i, instr)| {
+            map.follow(instr);
+            map.frame(i + 1)
+        });
+
+    Player::new()
+        .delay(Duration::from_millis(10))
+        .play(std::iter::once(first).chain(moves))
+        .expect("couldn't animate the warehouse");
+}
+
 fn main() {
     let input = challenge_input();
     let (map_str, instructions) = input.split_once("\n\n").unwrap();
-    let mut map: Map = map_str.into();
+    let mut map: Map = info_span!("parse").in_scope(|| map_str.into());
 
-    for instr in instructions.chars() {
-        match instr {
-            '>' => map.push((0, 1)),
-            '^' => map.push((-1, 0)),
-            '<' => map.push((0, -1)),
-            'v' => map.push((1, 0)),
-            '\n' => (),
-            _ => println!("end of simulation"),
-        }
+    if animate_requested() {
+        animate(map.clone(), instructions);
     }
-    println!("{}", map.gps_coordinate_sum())
+
+    let part_1 = info_span!("part_1").in_scope(|| {
+        debug!("\n{map}");
+        for instr in instructions.chars() {
+            map.follow(instr);
+            trace!(?instr, "\n{map}");
+        }
+        debug!("\n{map}");
+        map.gps_coordinate_sum()
+    });
+    println!("{part_1}")
 }
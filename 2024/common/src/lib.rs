@@ -1,12 +1,23 @@
-use std::env;
-use std::fs;
+use aoc_common::input::{normalise, InputSource};
+use aoc_common::trace;
 
+/// The input file passed as the first argument, with LF line endings, no trailing whitespace
+/// and a single final newline. See [`aoc_common::input::normalise`].
 #[must_use]
 pub fn challenge_input() -> String {
-    let args: Vec<String> = env::args().collect();
-    let file_path = &args
-        .get(1)
-        .expect("⚠️Please pass a path to an input file! ⚠️");
+    normalise(&challenge_input_raw())
+}
 
-    fs::read_to_string(file_path).expect("⚠️Unable to read input file! ⚠️")
+/// The input file passed as the first argument, exactly as it is on disk, or standard input
+/// for `-`. Without one, it's this day's input from the
+/// [`InputStore`](aoc_common::site::InputStore), fetched if needed.
+///
+/// Sets up logging as the `-v` and `--log-json` flags ask, see [`aoc_common::trace`].
+#[must_use]
+pub fn challenge_input_raw() -> String {
+    trace::init();
+    let source = InputSource::from_args().expect("⚠️Please pass a path to an input file! ⚠️");
+    source
+        .read()
+        .unwrap_or_else(|e| panic!("⚠️Unable to read {source}: {e} ⚠️"))
 }
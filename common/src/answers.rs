@@ -0,0 +1,259 @@
+//! What's known about submitted answers, kept in a small tab-separated file.
+//!
+//! Every line is `year  day  part  verdict  answer`. Besides the answers it has seen, the
+//! database knows that anything but the right answer is wrong, and that numbers past an
+//! answer that was too high or too low are too.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// What the site said about an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    #[must_use]
+    pub fn is_right(self) -> bool {
+        self == Verdict::Right
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(Verdict::Right),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            _ => Err(format!("{s:?} isn't a verdict")),
+        }
+    }
+}
+
+/// A part of a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+}
+
+impl Puzzle {
+    #[must_use]
+    pub fn new(year: u16, day: u8, part: u8) -> Self {
+        Puzzle { year, day, part }
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {} part {}", self.year, self.day, self.part)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub puzzle: Puzzle,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Every answer submitted so far, see the [module docs](self)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnswerDb {
+    entries: Vec<Entry>,
+}
+
+impl AnswerDb {
+    /// `answers.tsv` next to the stored inputs
+    #[must_use]
+    pub fn default_path() -> PathBuf {
+        crate::site::InputStore::default_root().join("answers.tsv")
+    }
+
+    /// The database in `path`, empty if there's no such file
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => text
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_string())
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Remembers a verdict, unless it's known already
+    pub fn record(&mut self, puzzle: Puzzle, answer: &str, verdict: Verdict) {
+        let answer = answer.trim();
+        let known = self
+            .entries
+            .iter()
+            .any(|e| e.puzzle == puzzle && e.answer == answer);
+        if !known {
+            self.entries.push(Entry {
+                puzzle,
+                verdict,
+                answer: answer.to_owned(),
+            });
+        }
+    }
+
+    /// The right answer, once it's known
+    #[must_use]
+    pub fn right_answer(&self, puzzle: Puzzle) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.puzzle == puzzle && e.verdict.is_right())
+            .map(|e| e.answer.as_str())
+    }
+
+    /// What the site would say about `answer`, as far as it follows from earlier verdicts
+    #[must_use]
+    pub fn judge(&self, puzzle: Puzzle, answer: &str) -> Option<Verdict> {
+        let answer = answer.trim();
+        let entries = || self.entries.iter().filter(move |e| e.puzzle == puzzle);
+
+        if let Some(entry) = entries().find(|e| e.answer == answer) {
+            return Some(entry.verdict);
+        }
+        if self.right_answer(puzzle).is_some() {
+            return Some(Verdict::Wrong);
+        }
+
+        let number: i128 = answer.parse().ok()?;
+        let bound = |verdict| {
+            entries()
+                .filter(move |e| e.verdict == verdict)
+                .filter_map(|e| e.answer.parse::<i128>().ok())
+        };
+        if bound(Verdict::TooHigh).any(|high| number >= high) {
+            Some(Verdict::TooHigh)
+        } else if bound(Verdict::TooLow).any(|low| number <= low) {
+            Some(Verdict::TooLow)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for AnswerDb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for Entry {
+            puzzle,
+            verdict,
+            answer,
+        } in &self.entries
+        {
+            let Puzzle { year, day, part } = puzzle;
+            writeln!(f, "{year}\t{day}\t{part}\t{verdict}\t{answer}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AnswerDb {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let error = |e: &dyn fmt::Display| format!("answers line {}: {e}", i + 1);
+                let fields: Vec<&str> = line.splitn(5, '\t').collect();
+                let [year, day, part, verdict, answer] = fields[..] else {
+                    return Err(error(&"expected 5 tab-separated fields"));
+                };
+                Ok(Entry {
+                    puzzle: Puzzle {
+                        year: year.parse().map_err(|e| error(&e))?,
+                        day: day.parse().map_err(|e| error(&e))?,
+                        part: part.parse().map_err(|e| error(&e))?,
+                    },
+                    verdict: verdict.parse().map_err(|e| error(&e))?,
+                    answer: answer.to_owned(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(AnswerDb { entries })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART_1: Puzzle = Puzzle {
+        year: 2022,
+        day: 14,
+        part: 1,
+    };
+
+    #[test]
+    fn judges_from_earlier_verdicts() {
+        let mut db = AnswerDb::default();
+        db.record(PART_1, "800", Verdict::TooHigh);
+        db.record(PART_1, "600", Verdict::TooLow);
+        db.record(PART_1, "700", Verdict::Wrong);
+
+        assert_eq!(db.judge(PART_1, "700"), Some(Verdict::Wrong));
+        assert_eq!(db.judge(PART_1, "900"), Some(Verdict::TooHigh));
+        assert_eq!(db.judge(PART_1, " 550\n"), Some(Verdict::TooLow));
+        assert_eq!(db.judge(PART_1, "692"), None);
+        assert_eq!(db.judge(PART_1, "abc"), None);
+        assert_eq!(db.judge(Puzzle::new(2022, 14, 2), "700"), None);
+
+        db.record(PART_1, "692", Verdict::Right);
+        assert_eq!(db.judge(PART_1, "692"), Some(Verdict::Right));
+        assert_eq!(db.judge(PART_1, "693"), Some(Verdict::Wrong));
+        assert_eq!(db.right_answer(PART_1), Some("692"));
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut db = AnswerDb::default();
+        db.record(PART_1, "692", Verdict::Right);
+        db.record(Puzzle::new(2023, 1, 2), "with\ttab", Verdict::TooLow);
+        db.record(PART_1, "692", Verdict::Wrong);
+        assert_eq!(db.entries().len(), 2);
+
+        let text = db.to_string();
+        assert_eq!(text.lines().next(), Some("2022\t14\t1\tright\t692"));
+        assert_eq!(text.parse::<AnswerDb>(), Ok(db));
+        assert!("2022\t14\t1\tmaybe\t3".parse::<AnswerDb>().is_err());
+    }
+}
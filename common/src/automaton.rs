@@ -0,0 +1,490 @@
+//! A cellular automaton engine for any number of dimensions.
+//!
+//! Cells live either in a dense box (every cell stored, like a puzzle grid) or in a sparse,
+//! unbounded map that only stores cells that differ from a background state.
+//! Every generation, each cell's next state is computed from its current state and the states
+//! of its neighbours, using either a Life-like `B3/S23` rule or any closure.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::grid::Grid;
+
+/// A position in a `D` dimensional lattice
+pub type Cell<const D: usize> = [i64; D];
+
+/// Which cells count as the neighbours of a cell.
+#[derive(Clone, Copy)]
+pub enum Neighbourhood<S> {
+    /// Every cell that differs by at most 1 in every coordinate (8 in 2D, 26 in 3D, ...)
+    Moore,
+    /// Every cell that differs by 1 in exactly one coordinate (4 in 2D, 6 in 3D, ...)
+    VonNeumann,
+    /// Along each Moore direction, the first cell for which the function returns `false`.
+    /// Directions that only see transparent cells until the edge have no neighbour.
+    LineOfSight(fn(&S) -> bool),
+}
+
+impl<S> Neighbourhood<S> {
+    fn directions<const D: usize>(&self) -> Vec<Cell<D>> {
+        let mut directions = vec![[0; D]];
+        for axis in 0..D {
+            directions = directions
+                .into_iter()
+                .flat_map(|d| {
+                    [-1, 0, 1].map(|delta| {
+                        let mut d = d;
+                        d[axis] = delta;
+                        d
+                    })
+                })
+                .collect();
+        }
+
+        directions.retain(|d| {
+            let moved = d.iter().filter(|&&delta| delta != 0).count();
+            match self {
+                Neighbourhood::VonNeumann => moved == 1,
+                _ => moved > 0,
+            }
+        });
+        directions
+    }
+}
+
+/// A Life-like rule for two-state automata, like `B3/S23` for Conway's Game of Life:
+/// dead cells with 3 alive neighbours are born, alive cells with 2 or 3 alive neighbours survive.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LifeRule {
+    pub birth: Vec<usize>,
+    pub survival: Vec<usize>,
+}
+
+impl LifeRule {
+    #[must_use]
+    pub fn conway() -> Self {
+        Self {
+            birth: vec![3],
+            survival: vec![2, 3],
+        }
+    }
+
+    #[must_use]
+    pub fn next(&self, alive: bool, alive_neighbours: usize) -> bool {
+        if alive {
+            self.survival.contains(&alive_neighbours)
+        } else {
+            self.birth.contains(&alive_neighbours)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRuleError(String);
+
+impl fmt::Display for ParseRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid Life-like rule: {}", self.0)
+    }
+}
+
+impl core::error::Error for ParseRuleError {}
+
+impl FromStr for LifeRule {
+    type Err = ParseRuleError;
+
+    /// Parses `B<digits>/S<digits>`, for example `B36/S23`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (birth, survival) = s
+            .split_once('/')
+            .ok_or_else(|| ParseRuleError(format!("{s:?} has no '/'")))?;
+
+        let counts = |part: &str, prefix: char| {
+            let digits = part
+                .strip_prefix(prefix)
+                .or_else(|| part.strip_prefix(prefix.to_ascii_lowercase()))
+                .ok_or_else(|| ParseRuleError(format!("{part:?} should start with {prefix}")))?;
+            digits
+                .chars()
+                .map(|c| {
+                    c.to_digit(10)
+                        .map(|d| d as usize)
+                        .ok_or_else(|| ParseRuleError(format!("{c:?} isn't a neighbour count")))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            birth: counts(birth, 'B')?,
+            survival: counts(survival, 'S')?,
+        })
+    }
+}
+
+enum Cells<S, const D: usize> {
+    Dense {
+        size: [usize; D],
+        cells: Vec<S>,
+    },
+    Sparse {
+        background: S,
+        cells: HashMap<Cell<D>, S>,
+    },
+}
+
+type Rule<S> = Box<dyn Fn(&S, &[&S]) -> S>;
+
+/// A cellular automaton with states `S` in `D` dimensions.
+pub struct Automaton<S, const D: usize> {
+    cells: Cells<S, D>,
+    neighbourhood: Neighbourhood<S>,
+    directions: Vec<Cell<D>>,
+    rule: Rule<S>,
+    generation: usize,
+}
+
+impl<S: Clone + PartialEq + 'static, const D: usize> Automaton<S, D> {
+    /// A bounded automaton where `cells` holds every cell, with the first coordinate changing fastest.
+    /// `rule` gets the state of a cell and the states of its neighbours.
+    pub fn dense(
+        size: [usize; D],
+        cells: Vec<S>,
+        neighbourhood: Neighbourhood<S>,
+        rule: impl Fn(&S, &[&S]) -> S + 'static,
+    ) -> Self {
+        assert_eq!(
+            cells.len(),
+            size.iter().product::<usize>(),
+            "cells don't fill the automaton"
+        );
+        Self {
+            cells: Cells::Dense { size, cells },
+            directions: neighbourhood.directions(),
+            neighbourhood,
+            rule: Box::new(rule),
+            generation: 0,
+        }
+    }
+
+    /// An unbounded automaton where every cell not in `cells` is in the `background` state.
+    /// `rule` must keep a background cell surrounded by background cells in the background state.
+    pub fn sparse(
+        background: S,
+        cells: impl IntoIterator<Item = (Cell<D>, S)>,
+        neighbourhood: Neighbourhood<S>,
+        rule: impl Fn(&S, &[&S]) -> S + 'static,
+    ) -> Self {
+        let cells = cells
+            .into_iter()
+            .filter(|(_, s)| *s != background)
+            .collect();
+        Self {
+            cells: Cells::Sparse { background, cells },
+            directions: neighbourhood.directions(),
+            neighbourhood,
+            rule: Box::new(rule),
+            generation: 0,
+        }
+    }
+
+    /// The amount of generations computed so far
+    #[must_use]
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// The state of a cell. `None` outside of a dense automaton.
+    #[must_use]
+    pub fn get(&self, cell: Cell<D>) -> Option<&S> {
+        match &self.cells {
+            Cells::Dense { size, cells } => dense_index(size, cell).map(|i| &cells[i]),
+            Cells::Sparse { background, cells } => Some(cells.get(&cell).unwrap_or(background)),
+        }
+    }
+
+    /// Every stored cell: all cells when dense, the non-background cells when sparse
+    pub fn cells(&self) -> Box<dyn Iterator<Item = (Cell<D>, &S)> + '_> {
+        match &self.cells {
+            Cells::Dense { size, cells } => Box::new(
+                cells
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| (dense_cell(size, i), s)),
+            ),
+            Cells::Sparse { cells, .. } => Box::new(cells.iter().map(|(&c, s)| (c, s))),
+        }
+    }
+
+    /// How many stored cells match `predicate`
+    pub fn count(&self, predicate: impl Fn(&S) -> bool) -> usize {
+        self.cells().filter(|(_, s)| predicate(s)).count()
+    }
+
+    /// Computes the next generation. Returns whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        let mut changed = false;
+        // Where lines of sight end, the same for every cell of this generation
+        let bounds = self.bounds();
+
+        let next = match &self.cells {
+            Cells::Dense { size, cells } => {
+                let next_cells = (0..cells.len())
+                    .map(|i| {
+                        let next = self.next_state(dense_cell(size, i), &cells[i], &bounds);
+                        changed |= next != cells[i];
+                        next
+                    })
+                    .collect();
+                Cells::Dense {
+                    size: *size,
+                    cells: next_cells,
+                }
+            }
+            Cells::Sparse { background, cells } => {
+                // Only stored cells and their neighbours can change
+                let candidates: HashSet<Cell<D>> = cells
+                    .keys()
+                    .flat_map(|&cell| {
+                        self.directions
+                            .iter()
+                            .map(move |d| offset(cell, *d))
+                            .chain([cell])
+                    })
+                    .collect();
+
+                let next_cells = candidates
+                    .into_iter()
+                    .filter_map(|cell| {
+                        let current = cells.get(&cell).unwrap_or(background);
+                        let next = self.next_state(cell, current, &bounds);
+                        changed |= next != *current;
+                        (next != *background).then_some((cell, next))
+                    })
+                    .collect();
+                Cells::Sparse {
+                    background: background.clone(),
+                    cells: next_cells,
+                }
+            }
+        };
+
+        self.cells = next;
+        self.generation += 1;
+        changed
+    }
+
+    /// Computes `generations` more generations
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Steps until a generation doesn't change anything.
+    /// Returns the amount of generations that did change something.
+    pub fn run_until_stable(&mut self) -> usize {
+        let start = self.generation;
+        while self.step() {}
+        self.generation - start - 1
+    }
+
+    fn next_state(&self, cell: Cell<D>, current: &S, bounds: &[(i64, i64); D]) -> S {
+        let neighbours: Vec<&S> = match self.neighbourhood {
+            Neighbourhood::Moore | Neighbourhood::VonNeumann => self
+                .directions
+                .iter()
+                .filter_map(|&d| self.get(offset(cell, d)))
+                .collect(),
+            Neighbourhood::LineOfSight(transparent) => self
+                .directions
+                .iter()
+                .filter_map(|&d| {
+                    let mut seen = offset(cell, d);
+                    loop {
+                        if !within(bounds, seen) {
+                            return None;
+                        }
+                        let state = self.get(seen)?;
+                        if !transparent(state) {
+                            return Some(state);
+                        }
+                        seen = offset(seen, d);
+                    }
+                })
+                .collect(),
+        };
+
+        (self.rule)(current, &neighbours)
+    }
+
+    /// The inclusive bounds of the stored cells, per axis
+    fn bounds(&self) -> [(i64, i64); D] {
+        match &self.cells {
+            Cells::Dense { size, .. } => size.map(|s| (0, s as i64 - 1)),
+            Cells::Sparse { cells, .. } => {
+                let mut bounds = [(i64::MAX, i64::MIN); D];
+                for cell in cells.keys() {
+                    for (axis, &x) in cell.iter().enumerate() {
+                        bounds[axis] = (bounds[axis].0.min(x), bounds[axis].1.max(x));
+                    }
+                }
+                bounds
+            }
+        }
+    }
+}
+
+impl<const D: usize> Automaton<bool, D> {
+    /// A sparse two-state automaton following a Life-like rule, with `alive` as the living cells
+    pub fn life(
+        rule: LifeRule,
+        neighbourhood: Neighbourhood<bool>,
+        alive: impl IntoIterator<Item = Cell<D>>,
+    ) -> Self {
+        Self::sparse(
+            false,
+            alive.into_iter().map(|cell| (cell, true)),
+            neighbourhood,
+            move |&alive, neighbours| rule.next(alive, neighbours.iter().filter(|n| ***n).count()),
+        )
+    }
+}
+
+impl<S: Clone + PartialEq + 'static> Automaton<S, 2> {
+    /// A dense automaton with the same cells as `grid`
+    pub fn from_grid(
+        grid: &Grid<S>,
+        neighbourhood: Neighbourhood<S>,
+        rule: impl Fn(&S, &[&S]) -> S + 'static,
+    ) -> Self {
+        let cells = grid.iter().map(|(_, s)| s.clone()).collect();
+        Self::dense([grid.width(), grid.height()], cells, neighbourhood, rule)
+    }
+}
+
+/// Places a 2D point in a higher dimension, with the other coordinates at 0
+#[must_use]
+pub fn embed<const D: usize>(x: i64, y: i64) -> Cell<D> {
+    let mut cell = [0; D];
+    cell[0] = x;
+    cell[1] = y;
+    cell
+}
+
+fn offset<const D: usize>(mut cell: Cell<D>, direction: Cell<D>) -> Cell<D> {
+    for (x, d) in cell.iter_mut().zip(direction) {
+        *x += d;
+    }
+    cell
+}
+
+fn within<const D: usize>(bounds: &[(i64, i64); D], cell: Cell<D>) -> bool {
+    bounds
+        .iter()
+        .zip(cell)
+        .all(|(&(min, max), x)| min <= x && x <= max)
+}
+
+fn dense_index<const D: usize>(size: &[usize; D], cell: Cell<D>) -> Option<usize> {
+    let mut index = 0;
+    let mut stride = 1;
+    for (&s, x) in size.iter().zip(cell) {
+        if x < 0 || x as usize >= s {
+            return None;
+        }
+        index += x as usize * stride;
+        stride *= s;
+    }
+    Some(index)
+}
+
+fn dense_cell<const D: usize>(size: &[usize; D], mut index: usize) -> Cell<D> {
+    let mut cell = [0; D];
+    for (x, &s) in cell.iter_mut().zip(size) {
+        *x = (index % s) as i64;
+        index /= s;
+    }
+    cell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rules() {
+        assert_eq!("B3/S23".parse(), Ok(LifeRule::conway()));
+        assert_eq!(
+            "b36/s23".parse::<LifeRule>().map(|r| r.birth),
+            Ok(vec![3, 6])
+        );
+        assert!("B3S23".parse::<LifeRule>().is_err());
+        assert!("B3/Sx".parse::<LifeRule>().is_err());
+    }
+
+    #[test]
+    fn neighbourhood_sizes() {
+        assert_eq!(Neighbourhood::<bool>::Moore.directions::<2>().len(), 8);
+        assert_eq!(Neighbourhood::<bool>::Moore.directions::<4>().len(), 80);
+        assert_eq!(Neighbourhood::<bool>::VonNeumann.directions::<3>().len(), 6);
+    }
+
+    #[test]
+    fn blinker_oscillates() {
+        let horizontal = [[-1, 0], [0, 0], [1, 0]];
+        let mut life = Automaton::life(LifeRule::conway(), Neighbourhood::Moore, horizontal);
+
+        life.step();
+        let mut alive: Vec<_> = life.cells().map(|(c, _)| c).collect();
+        alive.sort_unstable();
+        assert_eq!(alive, vec![[0, -1], [0, 0], [0, 1]]);
+
+        life.step();
+        assert_eq!(life.count(|&s| s), 3);
+        assert_eq!(life.get([1, 0]), Some(&true));
+        assert_eq!(life.generation(), 2);
+    }
+
+    #[test]
+    fn dense_stabilises() {
+        // Every cell takes the maximum of itself and its neighbours
+        let mut max = Automaton::dense(
+            [5],
+            vec![0, 0, 7, 0, 0],
+            Neighbourhood::Moore,
+            |&s, neighbours: &[&u8]| neighbours.iter().fold(s, |m, &&n| m.max(n)),
+        );
+        assert_eq!(max.run_until_stable(), 2);
+        assert_eq!(max.count(|&s| s == 7), 5);
+    }
+
+    #[test]
+    fn line_of_sight() {
+        // 1s see each other through the 0s, but not past a 2
+        let grid = Grid::parse("1.1.2.1", |c| match c {
+            '.' => 0u8,
+            c => c.to_digit(10).unwrap() as u8,
+        });
+        let mut sight = Automaton::from_grid(
+            &grid,
+            Neighbourhood::LineOfSight(|&s| s == 0),
+            |&s, neighbours| {
+                if s == 0 {
+                    0
+                } else {
+                    neighbours.len() as u8 + 10
+                }
+            },
+        );
+        sight.step();
+        let seen: Vec<u8> = sight.cells().map(|(_, &s)| s).collect();
+        assert_eq!(seen, vec![11, 0, 12, 0, 12, 0, 11]);
+    }
+}
@@ -0,0 +1,194 @@
+//! Cycle detection for deterministic simulations.
+//!
+//! A simulation is a start state and a step function. Eventually every finite
+//! simulation repeats: after `mu` steps it enters a cycle of length `lambda`.
+//! [`brent`] and [`floyd`] find these in constant memory, [`detect`] remembers
+//! every state (and needs `Hash`) so it can hand them back afterwards.
+
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use hashbrown::HashMap;
+
+/// Brent's algorithm. Returns `(mu, lambda)`.
+pub fn brent<S: Clone + Eq>(start: &S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    // Find lambda by letting the hare run ahead in powers of two
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(start);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    // Then find mu by walking two states that are lambda apart until they meet
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    (mu, lambda)
+}
+
+/// Floyd's tortoise and hare. Returns `(mu, lambda)`.
+pub fn floyd<S: Clone + Eq>(start: &S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    let mut tortoise = step(start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    let mut mu = 0;
+    let mut tortoise = start.clone();
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    (mu, lambda)
+}
+
+/// The state after `n` steps, using [`brent`] to skip over all full cycles.
+pub fn state_at<S: Clone + Eq>(start: &S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let (mu, lambda) = brent(start, &mut step);
+    let steps = if n < mu { n } else { mu + (n - mu) % lambda };
+    (0..steps).fold(start.clone(), |state, _| step(&state))
+}
+
+/// A simulation that was run until it repeated, see [`detect`].
+#[derive(Debug, Clone)]
+pub struct Cycle<S> {
+    /// The amount of steps before the cycle starts
+    pub mu: usize,
+    /// The length of the cycle
+    pub lambda: usize,
+    /// The first `mu + lambda` states, starting with the start state
+    pub states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// The state after `n` steps
+    #[must_use]
+    pub fn state_at(&self, n: usize) -> &S {
+        if n < self.mu {
+            &self.states[n]
+        } else {
+            &self.states[self.mu + (n - self.mu) % self.lambda]
+        }
+    }
+
+    /// The states that keep on repeating
+    #[must_use]
+    pub fn cycle(&self) -> &[S] {
+        &self.states[self.mu..]
+    }
+
+    /// The final state, if the simulation stops changing
+    #[must_use]
+    pub fn fixed_point(&self) -> Option<&S> {
+        (self.lambda == 1).then(|| &self.states[self.mu])
+    }
+}
+
+/// Runs the simulation until a state repeats, remembering every state on the way.
+pub fn detect<S: Clone + Eq + Hash>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle<S> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+
+    loop {
+        if let Some(&mu) = seen.get(&state) {
+            return Cycle {
+                mu,
+                lambda: states.len() - mu,
+                states,
+            };
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Steps until the state stops changing.
+/// `step` also gets the index of the step it's computing, starting at 0.
+/// Returns the index of the first step that didn't change anything, and the final state.
+pub fn fixed_point<S: PartialEq>(start: S, mut step: impl FnMut(usize, &S) -> S) -> (usize, S) {
+    let mut state = start;
+    for i in 0.. {
+        let next = step(i, &state);
+        if next == state {
+            return (i, state);
+        }
+        state = next;
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 7 -> 3 -> ...
+    fn step(&x: &u32) -> u32 {
+        if x == 7 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn algorithms_agree() {
+        assert_eq!(brent(&0, step), (3, 5));
+        assert_eq!(floyd(&0, step), (3, 5));
+
+        let cycle = detect(0, step);
+        assert_eq!((cycle.mu, cycle.lambda), (3, 5));
+        assert_eq!(cycle.cycle(), &[3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn extrapolates() {
+        let cycle = detect(0, step);
+        for n in 0..50 {
+            let simulated = (0..n).fold(0, |x, _| step(&x));
+            assert_eq!(*cycle.state_at(n), simulated);
+            assert_eq!(state_at(&0, step, n), simulated);
+        }
+        assert_eq!(state_at(&0, step, 1_000_000_000_000), 5);
+    }
+
+    #[test]
+    fn fixed_points() {
+        let cycle = detect(10u32, |&x| x / 2);
+        assert_eq!(cycle.fixed_point(), Some(&0));
+        assert_eq!(detect(0, step).fixed_point(), None);
+
+        assert_eq!(fixed_point(10u32, |_, &x| x / 2), (4, 0));
+    }
+}
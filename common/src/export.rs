@@ -0,0 +1,357 @@
+//! Saves [`Frame`]s as PNG images and frame sequences as animated GIFs.
+//!
+//! Every cell becomes a square block of pixels. Only the glyphs are drawn, not the border,
+//! caption or legend. Colours come from a [`Palette`]: a colour per tile character, falling
+//! back to the glyph's terminal style, so frames that look right in the terminal look the
+//! same as images without any extra setup.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::render::{Color, Frame, Glyph};
+
+/// The path after `flag` in the program's arguments, like `--gif elves.gif`
+#[must_use]
+pub fn path_requested(flag: &str) -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    args.find(|arg| arg == flag)?;
+    args.next().map(PathBuf::from)
+}
+
+/// Which colour every glyph gets in an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    tiles: HashMap<char, Color>,
+    background: Color,
+    foreground: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            tiles: HashMap::new(),
+            background: Color::BLACK,
+            foreground: Color::WHITE,
+        }
+    }
+}
+
+impl Palette {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The colour of a tile, or anything else shown as `tile`'s character.
+    /// Works with every `#[derive(Tile)]` type.
+    #[must_use]
+    pub fn tile(mut self, tile: impl Into<char>, colour: Color) -> Self {
+        self.tiles.insert(tile.into(), colour);
+        self
+    }
+
+    /// The colour of spaces
+    #[must_use]
+    pub fn background(self, background: Color) -> Self {
+        Palette { background, ..self }
+    }
+
+    /// The colour of glyphs without a tile colour or a style colour
+    #[must_use]
+    pub fn foreground(self, foreground: Color) -> Self {
+        Palette { foreground, ..self }
+    }
+
+    #[must_use]
+    pub fn colour(&self, glyph: &Glyph) -> Color {
+        if let Some(&colour) = self.tiles.get(&glyph.ch) {
+            return colour;
+        }
+        match (glyph.ch, glyph.style.bg, glyph.style.fg) {
+            (_, Some(bg), _) => bg,
+            (' ', None, _) => self.background,
+            (_, None, Some(fg)) => fg,
+            (_, None, None) => self.foreground,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(io::Error),
+    Png(png::EncodingError),
+    Gif(gif::EncodingError),
+    /// GIFs are at most 65535 pixels wide and high
+    TooLarge {
+        width: usize,
+        height: usize,
+    },
+    NoFrames,
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Io(e) => write!(f, "couldn't write the image: {e}"),
+            ExportError::Png(e) => write!(f, "couldn't encode the PNG: {e}"),
+            ExportError::Gif(e) => write!(f, "couldn't encode the GIF: {e}"),
+            ExportError::TooLarge { width, height } => {
+                write!(f, "{width}x{height} pixels is too large for a GIF")
+            }
+            ExportError::NoFrames => write!(f, "there are no frames to export"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+impl From<png::EncodingError> for ExportError {
+    fn from(e: png::EncodingError) -> Self {
+        ExportError::Png(e)
+    }
+}
+
+impl From<gif::EncodingError> for ExportError {
+    fn from(e: gif::EncodingError) -> Self {
+        ExportError::Gif(e)
+    }
+}
+
+/// Turns frames into images.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageExport {
+    palette: Palette,
+    scale: usize,
+    delay_centis: u16,
+}
+
+impl ImageExport {
+    /// Draws every cell as a 4x4 block, and shows GIF frames for 50ms
+    #[must_use]
+    pub fn new(palette: Palette) -> Self {
+        ImageExport {
+            palette,
+            scale: 4,
+            delay_centis: 5,
+        }
+    }
+
+    /// The size of a cell in pixels
+    #[must_use]
+    pub fn scale(self, pixels: usize) -> Self {
+        ImageExport {
+            scale: pixels.max(1),
+            ..self
+        }
+    }
+
+    /// How long every GIF frame is shown, in hundredths of a second
+    #[must_use]
+    pub fn delay(self, centiseconds: u16) -> Self {
+        ImageExport {
+            delay_centis: centiseconds,
+            ..self
+        }
+    }
+
+    /// The size of `frame` in pixels
+    #[must_use]
+    pub fn size(&self, frame: &Frame) -> (usize, usize) {
+        let glyphs = frame.glyphs();
+        (glyphs.width() * self.scale, glyphs.height() * self.scale)
+    }
+
+    /// The frame as RGB pixels, row by row
+    #[must_use]
+    pub fn pixels(&self, frame: &Frame) -> Vec<[u8; 3]> {
+        let (width, height) = self.size(frame);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in frame.glyphs().rows() {
+            let colours: Vec<[u8; 3]> = row
+                .iter()
+                .map(|glyph| {
+                    let (r, g, b) = self.palette.colour(glyph).rgb();
+                    [r, g, b]
+                })
+                .collect();
+            for _ in 0..self.scale {
+                for &colour in &colours {
+                    pixels.extend(std::iter::repeat_n(colour, self.scale));
+                }
+            }
+        }
+        pixels
+    }
+
+    /// Writes the frame as a PNG
+    pub fn png(&self, frame: &Frame, out: impl Write) -> Result<(), ExportError> {
+        let (width, height) = self.size(frame);
+        let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(self.pixels(frame).as_flattened())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Writes the frames as a looping GIF, as large as the first frame
+    pub fn gif(
+        &self,
+        frames: impl IntoIterator<Item = Frame>,
+        out: impl Write,
+    ) -> Result<(), ExportError> {
+        let mut frames = frames.into_iter().peekable();
+        let (width, height) = self.size(frames.peek().ok_or(ExportError::NoFrames)?);
+        let too_large = || ExportError::TooLarge { width, height };
+        let gif_width = u16::try_from(width).map_err(|_| too_large())?;
+        let gif_height = u16::try_from(height).map_err(|_| too_large())?;
+
+        let mut encoder = gif::Encoder::new(out, gif_width, gif_height, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        for frame in frames {
+            let pixels = fit(self.pixels(&frame), self.size(&frame), (width, height));
+            let mut gif_frame =
+                indexed_frame(gif_width, gif_height, &pixels).unwrap_or_else(|| {
+                    gif::Frame::from_rgb_speed(gif_width, gif_height, pixels.as_flattened(), 10)
+                });
+            gif_frame.delay = self.delay_centis;
+            encoder.write_frame(&gif_frame)?;
+        }
+
+        encoder.into_inner()?.flush()?;
+        Ok(())
+    }
+
+    /// Saves the frame as a PNG file
+    pub fn save_png(&self, frame: &Frame, path: impl AsRef<Path>) -> Result<(), ExportError> {
+        self.png(frame, BufWriter::new(File::create(path)?))
+    }
+
+    /// Saves the frames as a GIF file
+    pub fn save_gif(
+        &self,
+        frames: impl IntoIterator<Item = Frame>,
+        path: impl AsRef<Path>,
+    ) -> Result<(), ExportError> {
+        self.gif(frames, BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Crops or pads (in black) the pixels of an image to `size`
+fn fit(pixels: Vec<[u8; 3]>, from: (usize, usize), size: (usize, usize)) -> Vec<[u8; 3]> {
+    if from == size {
+        return pixels;
+    }
+    (0..size.1)
+        .flat_map(|y| (0..size.0).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            if x < from.0 && y < from.1 {
+                pixels[y * from.0 + x]
+            } else {
+                [0; 3]
+            }
+        })
+        .collect()
+}
+
+/// A GIF frame with a palette of exactly the colours used, if there are at most 256 of them
+fn indexed_frame(width: u16, height: u16, pixels: &[[u8; 3]]) -> Option<gif::Frame<'static>> {
+    let mut palette: Vec<[u8; 3]> = Vec::new();
+    let mut indices: HashMap<[u8; 3], u8> = HashMap::new();
+    let mut indexed = Vec::with_capacity(pixels.len());
+
+    for pixel in pixels {
+        let index = match indices.get(pixel) {
+            Some(&index) => index,
+            None => {
+                let index = u8::try_from(palette.len()).ok()?;
+                palette.push(*pixel);
+                indices.insert(*pixel, index);
+                index
+            }
+        };
+        indexed.push(index);
+    }
+
+    Some(gif::Frame::from_palette_pixels(
+        width,
+        height,
+        indexed,
+        palette.as_flattened(),
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::render::Style;
+
+    fn frame(input: &str) -> Frame {
+        Frame::from_grid(&Grid::parse(input, |c| c), |&c| match c {
+            '#' => Glyph::new('#', Style::fg(Color::RED)),
+            c => Glyph::plain(c),
+        })
+    }
+
+    #[test]
+    fn palette_colours() {
+        let palette = Palette::new().tile('o', Color::YELLOW);
+        assert_eq!(palette.colour(&Glyph::plain('o')), Color::YELLOW);
+        assert_eq!(palette.colour(&Glyph::plain(' ')), Color::BLACK);
+        assert_eq!(palette.colour(&Glyph::plain('x')), Color::WHITE);
+        assert_eq!(
+            palette.colour(&Glyph::new('#', Style::fg(Color::RED))),
+            Color::RED
+        );
+        assert_eq!(
+            palette.colour(&Glyph::new(' ', Style::PLAIN.on(Color::BLUE))),
+            Color::BLUE
+        );
+    }
+
+    #[test]
+    fn scaled_pixels() {
+        let export = ImageExport::new(Palette::new()).scale(2);
+        let pixels = export.pixels(&frame("# \n"));
+        assert_eq!(export.size(&frame("# \n")), (4, 2));
+
+        let red = [205, 0, 0];
+        let black = [0, 0, 0];
+        assert_eq!(pixels, [red, red, black, black, red, red, black, black]);
+    }
+
+    #[test]
+    fn encodes_images() {
+        let export = ImageExport::new(Palette::new());
+
+        let mut png = Vec::new();
+        export.png(&frame("#.\n.#\n"), &mut png).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+
+        let mut gif = Vec::new();
+        let frames = [frame("#.\n.#\n"), frame(".#\n#.\n"), frame("###\n")];
+        export.gif(frames, &mut gif).unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+        // The frames are 8x8 pixels, like the first one
+        assert_eq!(&gif[6..10], [8, 0, 8, 0]);
+
+        assert!(matches!(
+            export.gif([], &mut Vec::new()),
+            Err(ExportError::NoFrames)
+        ));
+    }
+}
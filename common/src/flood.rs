@@ -0,0 +1,369 @@
+//! Flood fill and connected-component labelling on bounded spaces.
+//!
+//! A [`Space`] is anything with a finite set of points and a neighbour relation:
+//! a dense [`Grid`], or [`Bounds`] around a sparse set of 2D or 3D points.
+//! Regions never leave the space, so every fill terminates and regions that
+//! touch the edge of the space can be told apart from enclosed ones.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::grid::Grid;
+use crate::point::{Point2, Point3};
+
+/// A finite space that can be flood filled.
+pub trait Space {
+    type Point: Copy + Eq + Hash;
+
+    fn contains(&self, p: Self::Point) -> bool;
+
+    /// Whether `p` lies on the outer edge of the space
+    fn on_boundary(&self, p: Self::Point) -> bool;
+
+    /// The neighbours of `p` that lie within the space
+    fn neighbours(&self, p: Self::Point) -> impl Iterator<Item = Self::Point>;
+
+    /// Every point in the space
+    fn points(&self) -> impl Iterator<Item = Self::Point>;
+}
+
+/// An inclusive axis-aligned box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<P> {
+    pub min: P,
+    pub max: P,
+}
+
+impl Bounds<Point2> {
+    /// The smallest box containing every point. `None` if there are no points.
+    pub fn around(points: impl IntoIterator<Item = Point2>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds, p| {
+            Some(match bounds {
+                None => Self { min: p, max: p },
+                Some(Self { min, max }) => Self {
+                    min: Point2::new(min.x.min(p.x), min.y.min(p.y)),
+                    max: Point2::new(max.x.max(p.x), max.y.max(p.y)),
+                },
+            })
+        })
+    }
+
+    #[must_use]
+    pub fn expanded(self, by: i64) -> Self {
+        Self {
+            min: self.min - Point2::new(by, by),
+            max: self.max + Point2::new(by, by),
+        }
+    }
+}
+
+impl Bounds<Point3> {
+    /// The smallest box containing every point. `None` if there are no points.
+    pub fn around(points: impl IntoIterator<Item = Point3>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds, p| {
+            Some(match bounds {
+                None => Self { min: p, max: p },
+                Some(Self { min, max }) => Self {
+                    min: Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                    max: Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+                },
+            })
+        })
+    }
+
+    #[must_use]
+    pub fn expanded(self, by: i64) -> Self {
+        Self {
+            min: self.min - Point3::new(by, by, by),
+            max: self.max + Point3::new(by, by, by),
+        }
+    }
+}
+
+impl Space for Bounds<Point2> {
+    type Point = Point2;
+
+    fn contains(&self, p: Point2) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    fn on_boundary(&self, p: Point2) -> bool {
+        p.x == self.min.x || p.x == self.max.x || p.y == self.min.y || p.y == self.max.y
+    }
+
+    fn neighbours(&self, p: Point2) -> impl Iterator<Item = Point2> {
+        p.orthogonal_neighbours()
+            .into_iter()
+            .filter(|&n| self.contains(n))
+    }
+
+    fn points(&self) -> impl Iterator<Item = Point2> {
+        let Self { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point2::new(x, y)))
+    }
+}
+
+impl Space for Bounds<Point3> {
+    type Point = Point3;
+
+    fn contains(&self, p: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    fn on_boundary(&self, p: Point3) -> bool {
+        p.x == self.min.x
+            || p.x == self.max.x
+            || p.y == self.min.y
+            || p.y == self.max.y
+            || p.z == self.min.z
+            || p.z == self.max.z
+    }
+
+    fn neighbours(&self, p: Point3) -> impl Iterator<Item = Point3> {
+        p.orthogonal_neighbours()
+            .into_iter()
+            .filter(|&n| self.contains(n))
+    }
+
+    fn points(&self) -> impl Iterator<Item = Point3> {
+        let Self { min, max } = *self;
+        (min.z..=max.z).flat_map(move |z| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point3::new(x, y, z)))
+        })
+    }
+}
+
+impl<T> Space for Grid<T> {
+    type Point = Point2;
+
+    fn contains(&self, p: Point2) -> bool {
+        Grid::contains(self, p)
+    }
+
+    fn on_boundary(&self, p: Point2) -> bool {
+        p.x == 0
+            || p.y == 0
+            || p.x as usize == self.width() - 1
+            || p.y as usize == self.height() - 1
+    }
+
+    fn neighbours(&self, p: Point2) -> impl Iterator<Item = Point2> {
+        p.orthogonal_neighbours()
+            .into_iter()
+            .filter(|&n| Grid::contains(self, n))
+    }
+
+    fn points(&self) -> impl Iterator<Item = Point2> {
+        Grid::points(self)
+    }
+}
+
+/// A connected set of points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region<P> {
+    pub points: Vec<P>,
+    /// Whether any point of the region lies on the boundary of the space
+    pub touches_boundary: bool,
+}
+
+impl<P> Region<P> {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Whether the region is fully surrounded by points that aren't part of it
+    #[must_use]
+    pub fn is_enclosed(&self) -> bool {
+        !self.touches_boundary
+    }
+}
+
+/// Every point reachable from `start` through `passable` points.
+/// The region is empty if `start` itself is outside the space or not passable.
+pub fn flood_fill<S: Space>(
+    space: &S,
+    start: S::Point,
+    passable: impl Fn(S::Point) -> bool,
+) -> Region<S::Point> {
+    let mut region = Region {
+        points: Vec::new(),
+        touches_boundary: false,
+    };
+
+    if !space.contains(start) || !passable(start) {
+        return region;
+    }
+
+    let mut seen: HashSet<S::Point> = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(p) = queue.pop_front() {
+        region.touches_boundary |= space.on_boundary(p);
+        region.points.push(p);
+
+        for n in space.neighbours(p) {
+            if passable(n) && seen.insert(n) {
+                queue.push_back(n);
+            }
+        }
+    }
+
+    region
+}
+
+/// The result of [`label_regions`]: every labelled point mapped to the index of its region.
+#[derive(Debug, Clone)]
+pub struct Regions<P> {
+    labels: HashMap<P, usize>,
+    regions: Vec<Region<P>>,
+}
+
+impl<P: Copy + Eq + Hash> Regions<P> {
+    /// The label of the region containing `p`, if `p` is part of any region
+    #[must_use]
+    pub fn label(&self, p: P) -> Option<usize> {
+        self.labels.get(&p).copied()
+    }
+
+    #[must_use]
+    pub fn region_of(&self, p: P) -> Option<&Region<P>> {
+        self.label(p).map(|label| &self.regions[label])
+    }
+
+    /// All regions, indexed by their label
+    #[must_use]
+    pub fn regions(&self) -> &[Region<P>] {
+        &self.regions
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+/// Splits the space into connected regions.
+/// Neighbouring points belong to the same region when `key` returns the same
+/// `Some` value for both; points for which `key` returns `None` aren't labelled.
+pub fn label_regions<S, K>(space: &S, key: impl Fn(S::Point) -> Option<K>) -> Regions<S::Point>
+where
+    S: Space,
+    K: Eq,
+{
+    let mut labels = HashMap::new();
+    let mut regions = Vec::new();
+    let mut queue = VecDeque::new();
+
+    for start in space.points() {
+        if labels.contains_key(&start) {
+            continue;
+        }
+        let Some(start_key) = key(start) else {
+            continue;
+        };
+
+        let label = regions.len();
+        let mut region = Region {
+            points: Vec::new(),
+            touches_boundary: false,
+        };
+
+        labels.insert(start, label);
+        queue.push_back(start);
+
+        while let Some(p) = queue.pop_front() {
+            region.touches_boundary |= space.on_boundary(p);
+            region.points.push(p);
+
+            for n in space.neighbours(p) {
+                if !labels.contains_key(&n) && key(n).as_ref() == Some(&start_key) {
+                    labels.insert(n, label);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        regions.push(region);
+    }
+
+    Regions { labels, regions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enclosed_region_in_grid() {
+        let grid = Grid::parse("#####\n#..##\n#####\n...#.\n", |c| c);
+        let regions = label_regions(&grid, |p| (grid[p] == '.').then_some(()));
+
+        assert_eq!(regions.len(), 3);
+        let inner = regions.region_of(Point2::new(1, 1)).unwrap();
+        assert_eq!(inner.len(), 2);
+        assert!(inner.is_enclosed());
+        assert!(
+            regions
+                .region_of(Point2::new(0, 3))
+                .unwrap()
+                .touches_boundary
+        );
+        assert_eq!(regions.label(Point2::new(0, 0)), None);
+    }
+
+    #[test]
+    fn regions_by_key() {
+        let grid = Grid::parse("AAB\nABB\nCCB\n", |c| c);
+        let regions = label_regions(&grid, |p| Some(grid[p]));
+
+        assert_eq!(regions.len(), 3);
+        assert_eq!(
+            regions.label(Point2::new(2, 0)),
+            regions.label(Point2::new(2, 2))
+        );
+    }
+
+    #[test]
+    fn flood_fill_has_no_step_limit() {
+        // A long corridor that a capped fill would give up on
+        let bounds = Bounds::<Point2>::around([Point2::new(0, 0), Point2::new(999, 2)]).unwrap();
+        let walls: HashSet<Point2> = (0..999).map(|x| Point2::new(x, 1)).collect();
+
+        let region = flood_fill(&bounds, Point2::new(0, 0), |p| !walls.contains(&p));
+        assert_eq!(region.len(), 1000 + 1 + 1000);
+    }
+
+    #[test]
+    fn hollow_cube() {
+        let cube = Bounds::<Point3>::around([Point3::ORIGIN, Point3::new(2, 2, 2)]).unwrap();
+        let shell: HashSet<Point3> = cube
+            .points()
+            .filter(|&p| p != Point3::new(1, 1, 1))
+            .collect();
+        let space = cube.expanded(1);
+
+        let regions = label_regions(&space, |p| (!shell.contains(&p)).then_some(()));
+        assert_eq!(regions.len(), 2);
+        assert!(regions
+            .region_of(Point3::new(1, 1, 1))
+            .unwrap()
+            .is_enclosed());
+        assert!(!regions.region_of(space.min).unwrap().is_enclosed());
+    }
+}
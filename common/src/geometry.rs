@@ -0,0 +1,184 @@
+//! Polygons on the integer grid: shoelace area, Pick's theorem and point-in-polygon.
+
+use alloc::vec::Vec;
+
+use crate::numtheory::gcd;
+use crate::point::Point2;
+
+/// A closed polygon. The last vertex connects back to the first one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    pub vertices: Vec<Point2>,
+}
+
+/// Where a point lies relative to a [`Polygon`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+impl Polygon {
+    #[must_use]
+    pub fn new(vertices: Vec<Point2>) -> Self {
+        Self { vertices }
+    }
+
+    /// Every edge as a `(from, to)` pair
+    pub fn edges(&self) -> impl Iterator<Item = (Point2, Point2)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the signed area, positive when the vertices go clockwise (with `y` pointing down).
+    /// Uses the shoelace formula, so it's always an exact integer.
+    #[must_use]
+    pub fn twice_signed_area(&self) -> i64 {
+        self.edges().map(|(a, b)| a.x * b.y - b.x * a.y).sum()
+    }
+
+    /// The area enclosed by the polygon, rounded down to a whole number
+    #[must_use]
+    pub fn area(&self) -> i64 {
+        self.twice_signed_area().abs() / 2
+    }
+
+    /// The amount of grid points on the edges of the polygon
+    #[must_use]
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| gcd((b.x - a.x).abs(), (b.y - a.y).abs()))
+            .sum()
+    }
+
+    /// The amount of grid points strictly inside the polygon, using Pick's theorem:
+    /// `area = interior + boundary / 2 - 1`.
+    #[must_use]
+    pub fn interior_points(&self) -> i64 {
+        (self.twice_signed_area().abs() - self.boundary_points()) / 2 + 1
+    }
+
+    /// Interior and boundary points together, like a flood fill including the walls would count
+    #[must_use]
+    pub fn enclosed_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Casts a ray from `p` to the right and counts the edges it crosses
+    #[must_use]
+    pub fn locate(&self, p: Point2) -> Location {
+        let mut inside = false;
+
+        for (a, b) in self.edges() {
+            if on_segment(p, a, b) {
+                return Location::Boundary;
+            }
+
+            if (a.y > p.y) != (b.y > p.y) {
+                // Whether p lies left of the crossing, without dividing
+                let lhs = i128::from(p.x - a.x) * i128::from(b.y - a.y);
+                let rhs = i128::from(b.x - a.x) * i128::from(p.y - a.y);
+                if (b.y > a.y && lhs < rhs) || (b.y < a.y && lhs > rhs) {
+                    inside = !inside;
+                }
+            }
+        }
+
+        if inside {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+
+    /// Whether `p` lies strictly inside the polygon
+    #[must_use]
+    pub fn contains(&self, p: Point2) -> bool {
+        self.locate(p) == Location::Inside
+    }
+}
+
+fn on_segment(p: Point2, a: Point2, b: Point2) -> bool {
+    let cross = i128::from(b.x - a.x) * i128::from(p.y - a.y)
+        - i128::from(b.y - a.y) * i128::from(p.x - a.x);
+    cross == 0
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+impl FromIterator<Point2> for Polygon {
+    fn from_iter<I: IntoIterator<Item = Point2>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: i64) -> Polygon {
+        Polygon::new(vec![
+            Point2::new(0, 0),
+            Point2::new(size, 0),
+            Point2::new(size, size),
+            Point2::new(0, size),
+        ])
+    }
+
+    #[test]
+    fn square_area() {
+        let square = square(4);
+        assert_eq!(square.twice_signed_area(), 32);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+        assert_eq!(square.enclosed_points(), 25);
+
+        let reversed: Polygon = square.vertices.iter().rev().copied().collect();
+        assert_eq!(reversed.twice_signed_area(), -32);
+        assert_eq!(reversed.interior_points(), 9);
+    }
+
+    #[test]
+    fn triangle_with_slanted_edges() {
+        let triangle = Polygon::new(vec![
+            Point2::new(0, 0),
+            Point2::new(4, 0),
+            Point2::new(0, 4),
+        ]);
+        assert_eq!(triangle.area(), 8);
+        assert_eq!(triangle.boundary_points(), 12);
+        assert_eq!(triangle.interior_points(), 3);
+    }
+
+    #[test]
+    fn locate() {
+        // A U shape, so rays from the arms cross the polygon three times
+        let u = Polygon::new(vec![
+            Point2::new(0, 0),
+            Point2::new(2, 0),
+            Point2::new(2, 4),
+            Point2::new(4, 4),
+            Point2::new(4, 0),
+            Point2::new(6, 0),
+            Point2::new(6, 6),
+            Point2::new(0, 6),
+        ]);
+
+        assert_eq!(u.locate(Point2::new(1, 2)), Location::Inside);
+        assert_eq!(u.locate(Point2::new(3, 5)), Location::Inside);
+        assert_eq!(u.locate(Point2::new(3, 1)), Location::Outside);
+        assert_eq!(u.locate(Point2::new(7, 3)), Location::Outside);
+        assert_eq!(u.locate(Point2::new(3, 4)), Location::Boundary);
+        assert_eq!(u.locate(Point2::new(6, 3)), Location::Boundary);
+
+        let inside = (0..=6)
+            .flat_map(|y| (0..=6).map(move |x| Point2::new(x, y)))
+            .filter(|&p| u.contains(p))
+            .count();
+        assert_eq!(inside, 13);
+        assert_eq!(u.interior_points(), 13);
+    }
+}
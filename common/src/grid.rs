@@ -0,0 +1,182 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::fmt;
+use core::ops::{Index, IndexMut};
+
+use crate::point::Point2;
+
+/// A dense, rectangular 2D grid stored in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Panics if `cells` doesn't contain exactly `width * height` items
+    #[must_use]
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid dimensions don't match");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses every character of every line with `parse_char`, ignoring trailing newlines.
+    /// Panics if the lines don't all have the same length.
+    pub fn parse(input: &str, mut parse_char: impl FnMut(char) -> T) -> Self {
+        match Self::try_parse(input, |c| Ok::<T, Infallible>(parse_char(c))) {
+            Ok(grid) => grid,
+            Err(error) => match error.error {},
+        }
+    }
+
+    /// Like [`Grid::parse`], but stops at the first character `parse_char` fails on
+    pub fn try_parse<E>(
+        input: &str,
+        mut parse_char: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, GridError<E>> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in input.trim_end_matches(['\r', '\n']).lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let cell = parse_char(c).map_err(|error| GridError {
+                    position: Point2::new(x as i64, y as i64),
+                    error,
+                })?;
+                cells.push(cell);
+            }
+            let line_width = line.chars().count();
+            assert_eq!(
+                *width.get_or_insert(line_width),
+                line_width,
+                "line {} has a different width",
+                height + 1
+            );
+            height += 1;
+        }
+
+        Ok(Self::from_vec(width.unwrap_or(0), height, cells))
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Point2) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, p: Point2) -> Option<&T> {
+        self.contains(p)
+            .then(|| &self.cells[p.y as usize * self.width + p.x as usize])
+    }
+
+    pub fn get_mut(&mut self, p: Point2) -> Option<&mut T> {
+        self.contains(p)
+            .then(|| &mut self.cells[p.y as usize * self.width + p.x as usize])
+    }
+
+    /// All points of the grid in row-major order
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    /// All cells together with their position, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The position of the first cell matching `predicate`, in row-major order
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point2> {
+        self.iter().find(|(_, t)| predicate(t)).map(|(p, _)| p)
+    }
+
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_vec(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: TryFrom<char>> Grid<T> {
+    /// Parses a grid of [tiles](crate::tile), like `Grid::<Tile>::parse_tiles(input)?`
+    pub fn parse_tiles(input: &str) -> Result<Self, GridError<T::Error>> {
+        Self::try_parse(input, T::try_from)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    #[must_use]
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::from_vec(width, height, vec![value; width * height])
+    }
+}
+
+/// Shows the grid with one line per row
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A character in the input that couldn't be parsed, see [`Grid::try_parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridError<E> {
+    pub position: Point2,
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for GridError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.position.y + 1,
+            self.position.x + 1,
+            self.error
+        )
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for GridError<E> {}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, p: Point2) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside of the grid"))
+    }
+}
@@ -0,0 +1,254 @@
+//! Cleaning up puzzle inputs before they're parsed.
+//!
+//! Inputs saved on Windows have CRLF line endings, and editors like to add or strip final
+//! newlines and leave spaces at the end of lines. Solutions that split on `"\n\n"` or `' '`
+//! break on all of that, so the year crates' `challenge_input` normalises the text first.
+//!
+//! Expected answers live next to the inputs they belong to, see [`Answers`].
+//!
+//! Where the input comes from is up to the command line, see [`InputSource`].
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::site::{puzzle_from_binary_name, InputStore, SiteError};
+use crate::trace::Verbosity;
+
+/// `raw` with LF line endings, without a byte order mark, trailing whitespace on any line or
+/// blank lines at the end, and with exactly one final newline (unless it's empty).
+#[must_use]
+pub fn normalise(raw: &str) -> String {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let mut input = String::with_capacity(raw.len() + 1);
+    // `lines` splits on LF and CRLF
+    for line in raw.lines() {
+        input.push_str(line.trim_end());
+        input.push('\n');
+    }
+
+    input.truncate(input.trim_end().len());
+    if !input.is_empty() {
+        input.push('\n');
+    }
+    input
+}
+
+/// The expected answers for an input file, kept in a sidecar file next to it:
+/// `input/example.txt` has `input/example.answers`, with part 1's answer on the first line
+/// and part 2's on the second. Unknown answers are empty lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Where the answers for `input` are kept
+    #[must_use]
+    pub fn path_for(input: &Path) -> PathBuf {
+        input.with_extension("answers")
+    }
+
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let mut lines = text.lines().map(str::trim);
+        let mut next = || lines.next().filter(|l| !l.is_empty()).map(String::from);
+        Answers {
+            part_1: next(),
+            part_2: next(),
+        }
+    }
+
+    /// The answers for `input`, or `None` if it doesn't have a sidecar file
+    pub fn load(input: &Path) -> io::Result<Option<Self>> {
+        match fs::read_to_string(Self::path_for(input)) {
+            Ok(text) => Ok(Some(Self::parse(&text))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, input: &Path) -> io::Result<()> {
+        fs::write(Self::path_for(input), self.to_string())
+    }
+
+    /// The answer for part 1 or 2
+    #[must_use]
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.part_1.as_deref().unwrap_or_default())?;
+        if let Some(part_2) = &self.part_2 {
+            writeln!(f, "{part_2}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The flags of some days that take the argument after them, like `--gif elves.gif`
+const FLAGS_WITH_VALUES: [&str; 2] = ["--gif", "--png"];
+
+/// The first argument that isn't a flag or a flag's value
+fn positional<S: AsRef<str>>(args: impl IntoIterator<Item = S>) -> Option<S> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let flag = arg.as_ref();
+        if FLAGS_WITH_VALUES.contains(&flag) {
+            args.next();
+        } else if !flag.starts_with("--") && !Verbosity::is_flag(flag) {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+/// Where a solution reads its input from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    /// The day's input from the [`InputStore`]
+    Stored {
+        year: u16,
+        day: u8,
+    },
+}
+
+impl InputSource {
+    /// The source for the arguments `args` of a binary named `binary`: the first one that isn't
+    /// a flag is a file, or standard input for `-`. Without one, it's the stored input of the
+    /// day the binary is named after. Flags like `--animate` and the [logging flags](Verbosity)
+    /// are skipped, and so is the path after `--gif` or `--png`.
+    #[must_use]
+    pub fn new<S: AsRef<str>>(
+        args: impl IntoIterator<Item = S>,
+        binary: Option<&str>,
+    ) -> Option<Self> {
+        match positional(args).as_ref().map(AsRef::as_ref) {
+            Some("-") => Some(InputSource::Stdin),
+            Some(path) => Some(InputSource::File(PathBuf::from(path))),
+            None => {
+                let (year, day) = puzzle_from_binary_name(binary?)?;
+                Some(InputSource::Stored { year, day })
+            }
+        }
+    }
+
+    /// The source the command line asks for, see [`InputSource::new`]. WebAssembly modules
+    /// don't know their name, so without an argument they read standard input.
+    #[must_use]
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().skip(1).collect();
+        if cfg!(target_family = "wasm") {
+            return Some(Self::new(&args, None).unwrap_or(InputSource::Stdin));
+        }
+        let binary = env::current_exe()
+            .ok()
+            .and_then(|exe| Some(exe.file_stem()?.to_str()?.to_owned()));
+        Self::new(&args, binary.as_deref())
+    }
+
+    /// The input, exactly as it is
+    pub fn read(&self) -> Result<String, SiteError> {
+        match self {
+            InputSource::File(path) => Ok(fs::read_to_string(path)?),
+            InputSource::Stdin => Ok(io::read_to_string(io::stdin())?),
+            InputSource::Stored { year, day } => InputStore::from_env().get(*year, *day),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => f.write_str("standard input"),
+            InputSource::Stored { year, day } => write!(f, "the stored input of {year} day {day}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_endings_and_whitespace() {
+        assert_eq!(normalise("a b\r\n\r\nc  \r\n"), "a b\n\nc\n");
+        assert_eq!(normalise("1 2 3"), "1 2 3\n");
+        assert_eq!(normalise("1\n2\n\n\n  \n"), "1\n2\n");
+        assert_eq!(
+            normalise("  indented\n\t\n\tblock"),
+            "  indented\n\n\tblock\n"
+        );
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(normalise(""), "");
+        assert_eq!(normalise(" \r\n\n"), "");
+        assert_eq!(normalise("\u{feff}x\n"), "x\n");
+        assert_eq!(normalise("already\nclean\n"), "already\nclean\n");
+    }
+
+    #[test]
+    fn answer_sidecars() {
+        let answers = Answers {
+            part_1: None,
+            part_2: Some(String::from("93")),
+        };
+        assert_eq!(answers.to_string(), "\n93\n");
+        assert_eq!(Answers::parse(&answers.to_string()), answers);
+        assert_eq!(Answers::parse("24\r\n").part(1), Some("24"));
+        assert_eq!(Answers::parse("24\n").part(2), None);
+        assert_eq!(
+            Answers::path_for(Path::new("2022/14/input/example.txt")),
+            Path::new("2022/14/input/example.answers")
+        );
+    }
+
+    #[test]
+    fn input_sources() {
+        let binary = Some("aoc-2022-14");
+        let file = |path: &str| Some(InputSource::File(PathBuf::from(path)));
+        let stored = Some(InputSource::Stored {
+            year: 2022,
+            day: 14,
+        });
+        assert_eq!(
+            InputSource::new(["input/1.txt"], binary),
+            file("input/1.txt")
+        );
+        assert_eq!(InputSource::new(["-"], None), Some(InputSource::Stdin));
+        assert_eq!(InputSource::new(["--animate"], binary), stored);
+        assert_eq!(
+            InputSource::new(["--animate", "input/example.txt"], binary),
+            file("input/example.txt")
+        );
+        assert_eq!(
+            InputSource::new(["--gif", "/tmp/x.gif", "input/example.txt"], binary),
+            file("input/example.txt")
+        );
+        assert_eq!(
+            InputSource::new(["-vv", "--log-json", "--png", "tree.png"], binary),
+            stored
+        );
+        assert_eq!(
+            InputSource::new(["input/1.txt", "-v"], binary),
+            file("input/1.txt")
+        );
+        assert_eq!(InputSource::new([""; 0], Some("deps-runner")), None);
+        assert_eq!(InputSource::new([""; 0], None), None);
+    }
+}
@@ -0,0 +1,483 @@
+//! Inclusive integer intervals, sets of them and piecewise offset maps over them.
+//!
+//! [`IntervalSet`] keeps its intervals sorted, disjoint and merged (adjacent intervals
+//! are joined too), so every set operation is a linear merge after an `O(n log n)` sort.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::ops::{Add, Div, Sub};
+
+/// The integer types intervals can be made of.
+pub trait Bound:
+    Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> + Div<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),+) => {
+        $(impl Bound for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+        })+
+    };
+}
+
+impl_bound!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// The inclusive range `start..=end`. Never empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    /// Panics if `start > end`
+    #[must_use]
+    pub fn new(start: T, end: T) -> Self {
+        assert!(
+            start <= end,
+            "interval start {start:?} is after its end {end:?}"
+        );
+        Self { start, end }
+    }
+
+    /// The interval containing only `x`
+    #[must_use]
+    pub fn point(x: T) -> Self {
+        Self { start: x, end: x }
+    }
+
+    /// The `len` numbers starting at `start`. `None` if `len` is zero.
+    #[must_use]
+    pub fn with_len(start: T, len: T) -> Option<Self> {
+        (len > T::ZERO).then(|| Self::new(start, start + (len - T::ONE)))
+    }
+
+    /// The number of integers in the interval, `None` if that's more than `T` holds, like
+    /// for `0..=u64::MAX`
+    #[must_use]
+    pub fn len(self) -> Option<T> {
+        self.end.checked_sub(self.start)?.checked_add(T::ONE)
+    }
+
+    #[must_use]
+    pub fn contains(self, x: T) -> bool {
+        self.start <= x && x <= self.end
+    }
+
+    /// Whether `other` lies completely within this interval
+    #[must_use]
+    pub fn contains_interval(self, other: Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    #[must_use]
+    pub fn overlaps(self, other: Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start <= end).then_some(Self { start, end })
+    }
+
+    /// Splits the interval in a lower and upper half.
+    /// The lower half is the larger one if the length is odd.
+    /// Panics when called on a single point.
+    #[must_use]
+    pub fn halves(self) -> (Self, Self) {
+        assert!(self.start < self.end, "can't split a single point");
+        let mid = self.start + (self.end - self.start) / (T::ONE + T::ONE);
+        (
+            Self::new(self.start, mid),
+            Self::new(mid + T::ONE, self.end),
+        )
+    }
+
+    /// Whether the two intervals overlap or directly follow each other
+    fn touches(self, other: Self) -> bool {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        first.end == T::MAX || second.start <= first.end + T::ONE
+    }
+}
+
+/// A set of integers stored as sorted, disjoint, non-adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Builds a set from intervals that are already sorted and merged
+    fn from_merged(intervals: Vec<Interval<T>>) -> Self {
+        debug_assert!(intervals
+            .windows(2)
+            .all(|w| w[0].end < w[1].start && !w[0].touches(w[1])));
+        Self { intervals }
+    }
+
+    /// The sorted, disjoint intervals in this set
+    #[must_use]
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The total amount of integers in the set, `None` if that's more than `T` holds
+    #[must_use]
+    pub fn len(&self) -> Option<T> {
+        self.iter()
+            .try_fold(T::ZERO, |acc, i| acc.checked_add(i.len()?))
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    #[must_use]
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().map(|i| i.end)
+    }
+
+    /// The smallest interval containing the whole set
+    #[must_use]
+    pub fn hull(&self) -> Option<Interval<T>> {
+        Some(Interval::new(self.min()?, self.max()?))
+    }
+
+    #[must_use]
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.end < x);
+        self.intervals.get(i).is_some_and(|i| i.contains(x))
+    }
+
+    /// Adds an interval, merging it with the ones it touches
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let first = self
+            .intervals
+            .partition_point(|i| i.end < interval.start && !i.touches(interval));
+        let last = first
+            + self.intervals[first..]
+                .iter()
+                .take_while(|i| i.touches(interval))
+                .count();
+
+        let merged = self.intervals[first..last].iter().fold(interval, |acc, i| {
+            Interval::new(acc.start.min(i.start), acc.end.max(i.end))
+        });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut all = Vec::with_capacity(self.intervals.len() + other.intervals.len());
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.start <= y.start => a.next(),
+                (Some(_), Some(_)) => b.next(),
+                (Some(_), None) => a.next(),
+                (None, _) => b.next(),
+            };
+            let Some(next) = next else { break };
+            push_merged(&mut all, next);
+        }
+
+        Self::from_merged(all)
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            if let Some(overlap) = a.intersection(b) {
+                result.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self::from_merged(result)
+    }
+
+    /// Everything in `self` that isn't in `other`
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = Vec::new();
+        let mut j = 0;
+
+        for interval in self.iter() {
+            let mut rest = Some(interval);
+
+            while let (Some(current), Some(&cut)) = (rest, other.intervals.get(j)) {
+                if cut.end < current.start {
+                    j += 1;
+                    continue;
+                }
+                if cut.start > current.end {
+                    break;
+                }
+                if cut.start > current.start {
+                    result.push(Interval::new(current.start, cut.start - T::ONE));
+                }
+                if cut.end >= current.end {
+                    rest = None;
+                } else {
+                    rest = Some(Interval::new(cut.end + T::ONE, current.end));
+                    j += 1;
+                }
+            }
+
+            result.extend(rest);
+        }
+
+        Self::from_merged(result)
+    }
+
+    /// The intervals within `within` that aren't part of this set, in order
+    pub fn gaps(&self, within: Interval<T>) -> impl Iterator<Item = Interval<T>> {
+        IntervalSet::from_merged(vec![within])
+            .difference(self)
+            .intervals
+            .into_iter()
+    }
+}
+
+/// Appends `next` to sorted `intervals`, merging it with the last one if they touch
+fn push_merged<T: Bound>(intervals: &mut Vec<Interval<T>>, next: Interval<T>) {
+    match intervals.last_mut() {
+        Some(last) if last.touches(next) => last.end = last.end.max(next.end),
+        _ => intervals.push(next),
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut sorted: Vec<_> = iter.into_iter().collect();
+        sorted.sort_unstable();
+
+        let mut merged = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            push_merged(&mut merged, interval);
+        }
+        Self::from_merged(merged)
+    }
+}
+
+impl<T: Bound> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        *self = self.union(&iter.into_iter().collect());
+    }
+}
+
+/// A function that shifts parts of the number line by a constant offset.
+/// Numbers that aren't covered by any piece map to themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PiecewiseMap<T> {
+    /// Sorted and non-overlapping `(source, destination start)` pairs
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T: Bound> PiecewiseMap<T> {
+    /// Panics if two source intervals overlap
+    pub fn new(pieces: impl IntoIterator<Item = (Interval<T>, T)>) -> Self {
+        let mut pieces: Vec<_> = pieces.into_iter().collect();
+        pieces.sort_unstable();
+        assert!(
+            pieces.windows(2).all(|w| !w[0].0.overlaps(w[1].0)),
+            "source intervals of a piecewise map can't overlap"
+        );
+        Self { pieces }
+    }
+
+    /// The `(source, destination start)` pieces, sorted by source
+    #[must_use]
+    pub fn pieces(&self) -> &[(Interval<T>, T)] {
+        &self.pieces
+    }
+
+    /// Moves `x` along the piece it's in
+    #[must_use]
+    pub fn map(&self, x: T) -> T {
+        let i = self.pieces.partition_point(|(source, _)| source.end < x);
+        match self.pieces.get(i) {
+            Some(&(source, destination)) if source.contains(x) => destination + (x - source.start),
+            _ => x,
+        }
+    }
+
+    /// Maps every number in `set`, splitting intervals where they cross pieces
+    #[must_use]
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut mapped = Vec::new();
+
+        for interval in set.iter() {
+            let mut rest = Some(interval);
+            let first = self
+                .pieces
+                .partition_point(|(source, _)| source.end < interval.start);
+
+            for &(source, destination) in &self.pieces[first..] {
+                let Some(current) = rest else { break };
+                if source.start > current.end {
+                    break;
+                }
+                if current.start < source.start {
+                    mapped.push(Interval::new(current.start, source.start - T::ONE));
+                }
+                let overlap = source.intersection(current).expect("pieces are sorted");
+                mapped.push(Interval::new(
+                    destination + (overlap.start - source.start),
+                    destination + (overlap.end - source.start),
+                ));
+                rest = (overlap.end < current.end)
+                    .then(|| Interval::new(overlap.end + T::ONE, current.end));
+            }
+
+            mapped.extend(rest);
+        }
+
+        mapped.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals
+            .iter()
+            .map(|&(a, b)| Interval::new(a, b))
+            .collect()
+    }
+
+    #[test]
+    fn merges_overlapping_and_adjacent() {
+        let s = set(&[(5, 8), (1, 3), (4, 4), (10, 12), (11, 20)]);
+        assert_eq!(s, set(&[(1, 8), (10, 20)]));
+        assert_eq!(s.len(), Some(19));
+        assert!(s.contains(8) && !s.contains(9) && s.contains(10));
+    }
+
+    #[test]
+    fn insert() {
+        let mut s = set(&[(1, 2), (6, 7), (20, 30)]);
+        s.insert(Interval::new(3, 5));
+        assert_eq!(s, set(&[(1, 7), (20, 30)]));
+        s.insert(Interval::new(10, 12));
+        assert_eq!(s, set(&[(1, 7), (10, 12), (20, 30)]));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (40, 41)]);
+
+        assert_eq!(a.union(&b), set(&[(0, 30), (40, 41)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 4), (26, 30)]));
+        assert_eq!(b.difference(&a), set(&[(11, 19), (40, 41)]));
+    }
+
+    #[test]
+    fn gaps() {
+        let s = set(&[(2, 4), (7, 9)]);
+        let gaps: Vec<_> = s.gaps(Interval::new(0, 10)).collect();
+        assert_eq!(
+            gaps,
+            vec![
+                Interval::new(0, 1),
+                Interval::new(5, 6),
+                Interval::new(10, 10)
+            ]
+        );
+    }
+
+    #[test]
+    fn unsigned_edges() {
+        let s: IntervalSet<u8> = [Interval::new(250, 255), Interval::new(0, 249)]
+            .into_iter()
+            .collect();
+        assert_eq!(s.intervals(), &[Interval::new(0, 255)]);
+        assert_eq!(s.len(), None);
+        assert_eq!(Interval::new(1u8, 255).len(), Some(255));
+        assert_eq!(Interval::new(0, u64::MAX).len(), None);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), None);
+        assert_eq!(Interval::new(i64::MIN, -2).len(), Some(i64::MAX));
+    }
+
+    #[test]
+    fn piecewise_map() {
+        // seed-to-soil map from 2023/05's example
+        let map = PiecewiseMap::new([
+            (Interval::with_len(98u64, 2).unwrap(), 50),
+            (Interval::with_len(50u64, 48).unwrap(), 52),
+        ]);
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(14), 14);
+        assert_eq!(map.map(99), 51);
+
+        let seeds: IntervalSet<u64> = [Interval::new(45, 55), Interval::new(97, 100)]
+            .into_iter()
+            .collect();
+        let expected: IntervalSet<u64> = [
+            Interval::new(45, 49),
+            Interval::new(52, 57),
+            Interval::new(99, 99),
+            Interval::new(50, 51),
+            Interval::new(100, 100),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(map.map_set(&seeds), expected);
+    }
+}
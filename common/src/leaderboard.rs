@@ -0,0 +1,672 @@
+//! Private leaderboards, from the JSON the site exports at
+//! `https://adventofcode.com/<year>/leaderboard/private/view/<id>.json`.
+//!
+//! A [`Leaderboard`] knows when every member got every star. From that it works out star
+//! timelines, how long members took from part 1 to part 2, scores under a few [`Scoring`]
+//! rules and rankings per day. A [`Report`] puts all of that next to the days this repository
+//! has solutions for, and renders as text tables or as JSON.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Seconds since the Unix epoch, like the site's `get_star_ts`
+pub type Timestamp = i64;
+
+/// When a puzzle unlocks: midnight EST, which is 05:00 UTC
+#[must_use]
+pub fn unlocks_at(year: u16, day: u8) -> Timestamp {
+    days_since_epoch(i64::from(year), 12, i64::from(day)) * 24 * 60 * 60 + 5 * 60 * 60
+}
+
+/// Days from 1970-01-01 to a date in the proleptic Gregorian calendar
+fn days_since_epoch(year: i64, month: i64, day: i64) -> i64 {
+    // Counting from March makes the leap day the last day of the year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Seconds as `1:02:03`, or `2d 01:02:03` from a day on
+fn duration(seconds: i64) -> String {
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    let (hours, minutes, seconds) = (rest / 3600, rest / 60 % 60, rest % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/// The stars of a member on one day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayStars {
+    pub part_1: Timestamp,
+    pub part_2: Option<Timestamp>,
+}
+
+impl DayStars {
+    /// Seconds from part 1 to part 2
+    #[must_use]
+    pub fn delta(&self) -> Option<i64> {
+        self.part_2.map(|part_2| part_2 - self.part_1)
+    }
+
+    #[must_use]
+    pub fn part(&self, part: u8) -> Option<Timestamp> {
+        match part {
+            1 => Some(self.part_1),
+            2 => self.part_2,
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous members
+    pub name: Option<String>,
+    /// The local score according to the site
+    pub local_score: u32,
+    pub days: BTreeMap<u8, DayStars>,
+}
+
+impl Member {
+    /// The name, or what the site shows for anonymous members
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    #[must_use]
+    pub fn stars(&self) -> usize {
+        self.days
+            .values()
+            .map(|stars| 1 + usize::from(stars.part_2.is_some()))
+            .sum()
+    }
+
+    /// Every star, in the order they were got
+    #[must_use]
+    pub fn timeline(&self, year: u16) -> Vec<Star> {
+        let mut stars: Vec<Star> = self
+            .days
+            .iter()
+            .flat_map(|(&day, stars)| {
+                (1..=2).filter_map(move |part| {
+                    let at = stars.part(part)?;
+                    Some(Star {
+                        day,
+                        part,
+                        at,
+                        after: at - unlocks_at(year, day),
+                    })
+                })
+            })
+            .collect();
+        stars.sort_by_key(|star| (star.at, star.day, star.part));
+        stars
+    }
+}
+
+/// A star on a member's timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Star {
+    pub day: u8,
+    pub part: u8,
+    pub at: Timestamp,
+    /// Seconds since the puzzle unlocked
+    pub after: i64,
+}
+
+/// How stars turn into points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scoring {
+    /// The site's local score: with `n` members, the first to get a star gets `n` points,
+    /// the second `n - 1`, and so on
+    #[default]
+    Local,
+    /// A point per star
+    Stars,
+    /// Like [`Scoring::Local`], but for the time from part 1 to part 2 of every day
+    Deltas,
+}
+
+impl fmt::Display for Scoring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Scoring::Local => "local",
+            Scoring::Stars => "stars",
+            Scoring::Deltas => "deltas",
+        })
+    }
+}
+
+impl FromStr for Scoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(Scoring::Local),
+            "stars" => Ok(Scoring::Stars),
+            "deltas" => Ok(Scoring::Deltas),
+            _ => Err(format!("{s:?} isn't a scoring, try local, stars or deltas")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub owner_id: u64,
+    /// Ordered by id
+    pub members: Vec<Member>,
+}
+
+/// The leaderboard as the site has it
+#[derive(Deserialize)]
+struct RawLeaderboard {
+    event: String,
+    owner_id: u64,
+    members: HashMap<String, RawMember>,
+}
+
+#[derive(Deserialize)]
+struct RawMember {
+    id: u64,
+    name: Option<String>,
+    #[serde(default)]
+    local_score: u32,
+    #[serde(default)]
+    completion_day_level: HashMap<u8, HashMap<u8, RawStar>>,
+}
+
+#[derive(Deserialize)]
+struct RawStar {
+    get_star_ts: Timestamp,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
+        let raw: RawLeaderboard = serde_json::from_str(json)?;
+        let year = raw
+            .event
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("{:?} isn't a year", raw.event)))?;
+
+        let mut members: Vec<Member> = raw
+            .members
+            .into_values()
+            .map(|member| Member {
+                id: member.id,
+                name: member.name,
+                local_score: member.local_score,
+                days: member
+                    .completion_day_level
+                    .into_iter()
+                    .filter_map(|(day, parts)| {
+                        let part = |part| parts.get(&part).map(|star| star.get_star_ts);
+                        Some((
+                            day,
+                            DayStars {
+                                part_1: part(1)?,
+                                part_2: part(2),
+                            },
+                        ))
+                    })
+                    .collect(),
+            })
+            .collect();
+        members.sort_by_key(|member| member.id);
+
+        Ok(Leaderboard {
+            year,
+            owner_id: raw.owner_id,
+            members,
+        })
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// The days anyone has a star for
+    #[must_use]
+    pub fn days(&self) -> BTreeSet<u8> {
+        self.members
+            .iter()
+            .flat_map(|member| member.days.keys().copied())
+            .collect()
+    }
+
+    /// The score of every member by id
+    #[must_use]
+    pub fn scores(&self, scoring: Scoring) -> HashMap<u64, u32> {
+        let mut scores: HashMap<u64, u32> = self.members.iter().map(|m| (m.id, 0)).collect();
+        let mut award = |mut times: Vec<(i64, u64)>| {
+            times.sort_unstable();
+            for (rank, (_, id)) in times.into_iter().enumerate() {
+                *scores.entry(id).or_default() += (self.members.len() - rank) as u32;
+            }
+        };
+
+        match scoring {
+            Scoring::Local => {
+                for day in self.days() {
+                    for part in 1..=2 {
+                        award(self.times(day, |stars| stars.part(part)));
+                    }
+                }
+            }
+            Scoring::Deltas => {
+                for day in self.days() {
+                    award(self.times(day, DayStars::delta));
+                }
+            }
+            Scoring::Stars => {
+                for member in &self.members {
+                    scores.insert(member.id, member.stars() as u32);
+                }
+            }
+        }
+        scores
+    }
+
+    /// `time` of every member that has it for `day`, with their id
+    fn times(&self, day: u8, time: impl Fn(&DayStars) -> Option<i64>) -> Vec<(i64, u64)> {
+        self.members
+            .iter()
+            .filter_map(|member| Some((time(member.days.get(&day)?)?, member.id)))
+            .collect()
+    }
+
+    /// The members with stars on `day`: first the ones with both, by when they got part 2,
+    /// then the others by when they got part 1
+    #[must_use]
+    pub fn day_ranking(&self, day: u8) -> Vec<(&Member, DayStars)> {
+        let mut ranking: Vec<(&Member, DayStars)> = self
+            .members
+            .iter()
+            .filter_map(|member| Some((member, *member.days.get(&day)?)))
+            .collect();
+        ranking.sort_by_key(|(member, stars)| {
+            (
+                stars.part_2.is_none(),
+                stars.part_2,
+                stars.part_1,
+                member.id,
+            )
+        });
+        ranking
+    }
+}
+
+/// Everything about a leaderboard, ready to print or to serialise
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub year: u16,
+    pub scoring: Scoring,
+    /// The days this repository has solutions for
+    pub solved_here: BTreeSet<u8>,
+    pub standings: Vec<Standing>,
+    pub days: Vec<DayReport>,
+    pub timelines: Vec<Timeline>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Standing {
+    pub rank: usize,
+    pub id: u64,
+    pub name: String,
+    pub score: u32,
+    pub stars: usize,
+    /// The number of stars on every day, starting with day 1
+    pub days: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayReport {
+    pub day: u8,
+    pub solved_here: bool,
+    pub ranking: Vec<DayEntry>,
+}
+
+/// A member's stars on a day, in seconds after the puzzle unlocked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayEntry {
+    pub rank: usize,
+    pub id: u64,
+    pub name: String,
+    pub part_1: i64,
+    pub part_2: Option<i64>,
+    pub delta: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Timeline {
+    pub id: u64,
+    pub name: String,
+    pub stars: Vec<Star>,
+}
+
+impl Report {
+    #[must_use]
+    pub fn new(board: &Leaderboard, scoring: Scoring, solved_here: BTreeSet<u8>) -> Self {
+        let scores = board.scores(scoring);
+        let mut members: Vec<&Member> = board.members.iter().collect();
+        members.sort_by_key(|member| (std::cmp::Reverse(scores[&member.id]), member.id));
+
+        let standings = members
+            .iter()
+            .enumerate()
+            .map(|(i, member)| Standing {
+                rank: i + 1,
+                id: member.id,
+                name: member.display_name(),
+                score: scores[&member.id],
+                stars: member.stars(),
+                days: (1..=25)
+                    .map(|day| {
+                        member
+                            .days
+                            .get(&day)
+                            .map_or(0, |stars| 1 + u8::from(stars.part_2.is_some()))
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let days = board
+            .days()
+            .into_iter()
+            .map(|day| {
+                let unlock = unlocks_at(board.year, day);
+                DayReport {
+                    day,
+                    solved_here: solved_here.contains(&day),
+                    ranking: board
+                        .day_ranking(day)
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, (member, stars))| DayEntry {
+                            rank: i + 1,
+                            id: member.id,
+                            name: member.display_name(),
+                            part_1: stars.part_1 - unlock,
+                            part_2: stars.part_2.map(|part_2| part_2 - unlock),
+                            delta: stars.delta(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let timelines = members
+            .iter()
+            .map(|member| Timeline {
+                id: member.id,
+                name: member.display_name(),
+                stars: member.timeline(board.year),
+            })
+            .collect();
+
+        Report {
+            year: board.year,
+            scoring,
+            solved_here,
+            standings,
+            days,
+            timelines,
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("reports only have plain data")
+    }
+}
+
+/// Rows with every column right aligned, except the last one
+fn table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut table = String::new();
+    for row in rows {
+        let mut line = String::new();
+        for (column, cell) in row.iter().enumerate() {
+            if column + 1 == columns {
+                line.push_str(cell);
+            } else {
+                line.push_str(&format!("{cell:>width$}  ", width = widths[column]));
+            }
+        }
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}, scored by {}", self.year, self.scoring)?;
+
+        // The day numbers over two lines, like on the site
+        let tens: String = (1..=25u8)
+            .map(|day| match day / 10 {
+                0 => ' ',
+                tens => char::from(b'0' + tens),
+            })
+            .collect();
+        let units: String = (1..=25u8).map(|day| char::from(b'0' + day % 10)).collect();
+        let mut rows = vec![
+            vec![
+                String::new(),
+                String::new(),
+                String::new(),
+                tens,
+                String::new(),
+            ],
+            ["rank", "score", "stars", &units, "name"]
+                .map(String::from)
+                .to_vec(),
+        ];
+        for standing in &self.standings {
+            rows.push(vec![
+                standing.rank.to_string(),
+                standing.score.to_string(),
+                standing.stars.to_string(),
+                standing
+                    .days
+                    .iter()
+                    .map(|&stars| ['.', '+', '*'][usize::from(stars)])
+                    .collect(),
+                standing.name.clone(),
+            ]);
+        }
+        rows.push(vec![
+            String::new(),
+            String::new(),
+            String::new(),
+            (1..=25)
+                .map(|day| {
+                    if self.solved_here.contains(&day) {
+                        '^'
+                    } else {
+                        ' '
+                    }
+                })
+                .collect(),
+            String::from("solved in this repository"),
+        ]);
+        write!(f, "{}", table(&rows))?;
+
+        for day in &self.days {
+            let here = if day.solved_here {
+                " (solved here)"
+            } else {
+                ""
+            };
+            writeln!(f, "\nDay {}{here}", day.day)?;
+            let mut rows = vec![["rank", "part 1", "part 2", "delta", "name"]
+                .map(String::from)
+                .to_vec()];
+            let optional = |seconds: Option<i64>| seconds.map_or(String::from("-"), duration);
+            for entry in &day.ranking {
+                rows.push(vec![
+                    entry.rank.to_string(),
+                    duration(entry.part_1),
+                    optional(entry.part_2),
+                    optional(entry.delta),
+                    entry.name.clone(),
+                ]);
+            }
+            write!(f, "{}", table(&rows))?;
+        }
+
+        writeln!(f, "\nTimelines (day.part, time after the unlock)")?;
+        for timeline in &self.timelines {
+            let stars: Vec<String> = timeline
+                .stars
+                .iter()
+                .map(|star| format!("{}.{} {}", star.day, star.part, duration(star.after)))
+                .collect();
+            if stars.is_empty() {
+                writeln!(f, "{}: no stars", timeline.name)?;
+            } else {
+                writeln!(f, "{}: {}", timeline.name, stars.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_1: Timestamp = 1_669_870_800;
+    const DAY_2: Timestamp = DAY_1 + 86_400;
+
+    fn board() -> Leaderboard {
+        let json = format!(
+            r#"{{"event":"2022","owner_id":1,"members":{{
+"1":{{"id":1,"name":"Alice","stars":3,"local_score":11,"global_score":0,"last_star_ts":{},
+  "completion_day_level":{{"1":{{"1":{{"get_star_ts":{},"star_index":1}},"2":{{"get_star_ts":{},"star_index":4}}}},
+                          "2":{{"1":{{"get_star_ts":{},"star_index":7}}}}}}}},
+"2":{{"id":2,"name":"Bob","stars":2,"local_score":7,"global_score":0,"last_star_ts":0,
+  "completion_day_level":{{"1":{{"1":{{"get_star_ts":{},"star_index":0}},"2":{{"get_star_ts":{},"star_index":5}}}}}}}},
+"3":{{"id":3,"name":null,"stars":1,"local_score":2,"global_score":0,"last_star_ts":0,
+  "completion_day_level":{{"1":{{"1":{{"get_star_ts":{},"star_index":2}}}}}}}},
+"4":{{"id":4,"name":"Lurker","stars":0,"local_score":0,"global_score":0,"last_star_ts":0,
+  "completion_day_level":{{}}}}}}}}"#,
+            DAY_2 + 1000,
+            DAY_1 + 300,
+            DAY_1 + 600,
+            DAY_2 + 1000,
+            DAY_1 + 200,
+            DAY_1 + 900,
+            DAY_1 + 400,
+        );
+        Leaderboard::parse(&json).unwrap()
+    }
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(unlocks_at(2022, 1), DAY_1);
+        assert_eq!(unlocks_at(2024, 25), 1_735_102_800);
+        assert_eq!(duration(3723), "1:02:03");
+        assert_eq!(duration(2 * 86_400 + 5), "2d 00:00:05");
+    }
+
+    #[test]
+    fn parses_members() {
+        let board = board();
+        assert_eq!(board.year, 2022);
+        assert_eq!(board.members.len(), 4);
+        assert_eq!(board.members[2].display_name(), "(anonymous user #3)");
+        assert_eq!(board.members[0].stars(), 3);
+        assert_eq!(board.members[0].days[&1].delta(), Some(300));
+        assert_eq!(board.days(), BTreeSet::from([1, 2]));
+
+        let timeline = board.members[0].timeline(2022);
+        let stars: Vec<_> = timeline.iter().map(|s| (s.day, s.part, s.after)).collect();
+        assert_eq!(stars, [(1, 1, 300), (1, 2, 600), (2, 1, 1000)]);
+
+        assert!(Leaderboard::parse(r#"{"event":"twenty","owner_id":1,"members":{}}"#).is_err());
+    }
+
+    #[test]
+    fn scoring_rules() {
+        let board = board();
+        let scores = |scoring| {
+            let scores = board.scores(scoring);
+            board
+                .members
+                .iter()
+                .map(|m| scores[&m.id])
+                .collect::<Vec<_>>()
+        };
+
+        // The same as the site's
+        let reported: Vec<u32> = board.members.iter().map(|m| m.local_score).collect();
+        assert_eq!(scores(Scoring::Local), reported);
+        assert_eq!(scores(Scoring::Stars), [3, 2, 1, 0]);
+        assert_eq!(scores(Scoring::Deltas), [4, 3, 0, 0]);
+        assert_eq!("deltas".parse(), Ok(Scoring::Deltas));
+        assert!("global".parse::<Scoring>().is_err());
+    }
+
+    #[test]
+    fn reports() {
+        let board = board();
+        let ranking: Vec<u64> = board.day_ranking(1).iter().map(|(m, _)| m.id).collect();
+        assert_eq!(ranking, [1, 2, 3]);
+
+        let report = Report::new(&board, Scoring::Local, BTreeSet::from([1]));
+        let names: Vec<&str> = report.standings.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Alice", "Bob", "(anonymous user #3)", "Lurker"]);
+        assert_eq!(report.standings[0].days[..3], [2, 1, 0]);
+        assert!(report.days[0].solved_here && !report.days[1].solved_here);
+        assert_eq!(report.days[0].ranking[1].delta, Some(700));
+
+        let text = report.to_string();
+        assert!(text.contains("   1     11      3  *+.......................  Alice\n"));
+        assert!(text.contains(
+            "                    ^                          solved in this repository\n"
+        ));
+        assert!(text.contains("\nDay 1 (solved here)\n"));
+        assert!(text.contains("   2  0:03:20  0:15:00  0:11:40  Bob\n"));
+        assert!(text.contains("Alice: 1.1 0:05:00, 1.2 0:10:00, 2.1 0:16:40\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["scoring"], "local");
+        assert_eq!(json["standings"][1]["score"], 7);
+        assert_eq!(
+            json["days"][0]["ranking"][2]["part_2"],
+            serde_json::Value::Null
+        );
+        assert_eq!(json["solved_here"], serde_json::json!([1]));
+    }
+}
@@ -0,0 +1,42 @@
+//! Shared utilities used by the solutions of every year.
+//!
+//! Without the default `std` feature this is a `no_std` crate that needs `alloc`, with only
+//! the modules that don't talk to an operating system: [`point`], [`grid`], [`tile`],
+//! [`flood`], [`cycle`], [`automaton`], [`geometry`], [`interval`] and [`numtheory`].
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+// The code generated by aoc-derive refers to this crate by name
+extern crate self as aoc_common;
+
+#[cfg(feature = "std")]
+pub mod answers;
+pub mod automaton;
+pub mod cycle;
+#[cfg(feature = "export")]
+pub mod export;
+pub mod flood;
+pub mod geometry;
+pub mod grid;
+#[cfg(feature = "std")]
+pub mod input;
+pub mod interval;
+#[cfg(feature = "leaderboard")]
+pub mod leaderboard;
+#[cfg(feature = "std")]
+pub mod memo;
+pub mod numtheory;
+#[cfg(feature = "std")]
+pub mod parallel;
+#[cfg(feature = "parsers")]
+pub mod parsers;
+#[cfg(feature = "player")]
+pub mod player;
+pub mod point;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod site;
+pub mod tile;
+#[cfg(feature = "std")]
+pub mod trace;
@@ -0,0 +1,83 @@
+//! Memoisation for recursive functions, without global state.
+//!
+//! The function takes the [`Memo`] as an argument and passes it on to its recursive calls:
+//!
+//! ```
+//! use aoc_common::memo::Memo;
+//!
+//! fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+//!     memo.get_or(n, |memo| match n {
+//!         0 | 1 => n,
+//!         _ => fibonacci(memo, n - 1) + fibonacci(memo, n - 2),
+//!     })
+//! }
+//!
+//! assert_eq!(fibonacci(&mut Memo::new(), 90), 2_880_067_194_370_816_120);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Results by their arguments
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    results: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            results: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The result for `key`, from `compute` the first time. `compute` gets the memo back for
+    /// its recursive calls.
+    pub fn get_or(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(result) = self.results.get(&key) {
+            return result.clone();
+        }
+        let result = compute(self);
+        self.results.insert(key, result.clone());
+        result
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_once() {
+        let mut calls = 0;
+        let mut memo = Memo::new();
+        for _ in 0..3 {
+            assert_eq!(
+                memo.get_or((2, 3), |_| {
+                    calls += 1;
+                    8
+                }),
+                8
+            );
+        }
+        assert_eq!(calls, 1);
+        assert_eq!(memo.len(), 1);
+    }
+}
@@ -0,0 +1,340 @@
+//! Number theory helpers: overflow-checked gcd/lcm folds, extended Euclid,
+//! modular arithmetic and a Chinese remainder theorem that allows non-coprime moduli.
+
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+/// The integer types the gcd/lcm helpers work on.
+pub trait Integer:
+    Copy
+    + Ord
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),+) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        })+
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// The greatest common divisor, always non-negative. `gcd(0, 0)` is 0.
+#[must_use]
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    if a < T::ZERO {
+        T::ZERO - a
+    } else {
+        a
+    }
+}
+
+/// The least common multiple, or `None` if it doesn't fit in `T`
+#[must_use]
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    let lcm = (a / gcd(a, b)).checked_mul(b)?;
+    Some(if lcm < T::ZERO { T::ZERO - lcm } else { lcm })
+}
+
+/// The least common multiple of all numbers (1 if there are none), or `None` on overflow
+pub fn lcm_all<T: Integer>(numbers: impl IntoIterator<Item = T>) -> Option<T> {
+    numbers
+        .into_iter()
+        .try_fold(T::ONE, |acc, n| checked_lcm(acc, n))
+}
+
+/// The product of all numbers (1 if there are none), or `None` on overflow
+pub fn checked_product<T: Integer>(numbers: impl IntoIterator<Item = T>) -> Option<T> {
+    numbers
+        .into_iter()
+        .try_fold(T::ONE, |acc, n| acc.checked_mul(n))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g = gcd(a, b)`
+#[must_use]
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime
+#[must_use]
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive");
+    let (g, x, _) = extended_gcd(i128::from(a), i128::from(m));
+    (g == 1).then(|| x.rem_euclid(i128::from(m)) as i64)
+}
+
+/// `a * b mod m` without overflowing
+#[must_use]
+pub fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+/// `base ^ exp mod m` by repeated squaring
+#[must_use]
+pub fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    assert!(m > 0, "modulus must be positive");
+    let mut result = 1 % m;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+    /// The congruences contradict each other
+    NoSolution,
+    /// The combined modulus doesn't fit in an `i64`
+    Overflow,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrtError::NoSolution => write!(f, "the congruences have no common solution"),
+            CrtError::Overflow => write!(f, "the combined modulus overflows an i64"),
+        }
+    }
+}
+
+impl core::error::Error for CrtError {}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair.
+///
+/// The moduli don't need to be coprime. Returns `(x, m)` where `m` is the lcm of the
+/// moduli and `x` in `0..m` is the smallest non-negative solution; every solution is
+/// `x + k * m`. Panics if a modulus isn't positive.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Result<(i64, i64), CrtError> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus must be positive");
+        let (residue, modulus) = (
+            i128::from(residue).rem_euclid(i128::from(modulus)),
+            i128::from(modulus),
+        );
+
+        let (g, p, _) = extended_gcd(m, modulus);
+        let difference = residue - x;
+        if difference % g != 0 {
+            return Err(CrtError::NoSolution);
+        }
+
+        let lcm = m / g * modulus;
+        if lcm > i128::from(i64::MAX) {
+            return Err(CrtError::Overflow);
+        }
+
+        // x + m * k satisfies the new congruence when m * k ≡ difference (mod modulus)
+        let step = modulus / g;
+        let k = (difference / g % step * (p % step)).rem_euclid(step);
+        x = (x + m * k).rem_euclid(lcm);
+        m = lcm;
+    }
+
+    Ok((x as i64, m as i64))
+}
+
+/// An integer modulo `M`, always kept in `0..M`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    #[must_use]
+    pub const fn new(value: u64) -> Self {
+        Self(value % M)
+    }
+
+    #[must_use]
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    #[must_use]
+    pub fn pow(self, exp: u64) -> Self {
+        Self(pow_mod(self.0, exp, M))
+    }
+
+    /// The multiplicative inverse, if `self` and `M` are coprime
+    #[must_use]
+    pub fn inverse(self) -> Option<Self> {
+        let m = i64::try_from(M).expect("modulus too large for inverses");
+        mod_inverse(self.0 as i64, m).map(|x| Self(x as u64))
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const M: u64> From<i64> for ModInt<M> {
+    fn from(value: i64) -> Self {
+        Self(i128::from(value).rem_euclid(i128::from(M)) as u64)
+    }
+}
+
+impl<const M: u64> fmt::Debug for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {M})", self.0)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(((u128::from(self.0) + u128::from(rhs.0)) % u128::from(M)) as u64)
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self((M - self.0) % M)
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(mul_mod(self.0, rhs.0, M))
+    }
+}
+
+/// Panics if `rhs` has no inverse modulo `M`
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse().expect("division by a non-invertible value")
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(lcm_all([4u64, 6, 10]), Some(60));
+        assert_eq!(lcm_all([u64::MAX, 2]), None);
+        assert_eq!(checked_product([u8::MAX, 2]), None);
+    }
+
+    #[test]
+    fn inverses() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(pow_mod(2, 62, u64::MAX), 1 << 62);
+    }
+
+    #[test]
+    fn crt_coprime() {
+        // 2020/13's example: 7,13,x,x,59,x,31,19
+        let buses = [(0, 7), (1, 13), (4, 59), (6, 31), (7, 19)];
+        let congruences = buses.iter().map(|&(i, id)| (-i, id));
+        assert_eq!(crt(congruences), Ok((1_068_781, 7 * 13 * 59 * 31 * 19)));
+    }
+
+    #[test]
+    fn crt_non_coprime() {
+        assert_eq!(crt([(2, 6), (8, 10)]), Ok((8, 30)));
+        assert_eq!(crt([(1, 6), (2, 4)]), Err(CrtError::NoSolution));
+        assert_eq!(crt([(0, i64::MAX), (1, 2)]), Err(CrtError::Overflow));
+        assert_eq!(crt([]), Ok((0, 1)));
+    }
+
+    #[test]
+    fn mod_int() {
+        type M7 = ModInt<7>;
+        assert_eq!(M7::new(5) + M7::new(4), M7::new(2));
+        assert_eq!(M7::new(2) - M7::new(5), M7::new(4));
+        assert_eq!(M7::from(-1i64), M7::new(6));
+        assert_eq!(M7::new(3) / M7::new(5) * M7::new(5), M7::new(3));
+        assert_eq!(M7::new(3).pow(6), M7::new(1));
+    }
+}
@@ -0,0 +1,93 @@
+//! Parallel iterators that can be sequential.
+//!
+//! `use aoc_common::parallel::prelude::*` instead of rayon's prelude. With the `parallel`
+//! feature, that is rayon's prelude. WebAssembly builds (or builds without the feature) get
+//! the same method names on plain iterators instead, so the same code runs on one thread.
+//!
+//! Only the parts of rayon that solutions use are there: `par_iter`, `into_par_iter` and
+//! `find_map_first`, after which the usual iterator methods apply.
+
+#[cfg(all(feature = "parallel", not(target_family = "wasm")))]
+pub mod prelude {
+    pub use rayon::prelude::*;
+}
+
+#[cfg(not(all(feature = "parallel", not(target_family = "wasm"))))]
+pub mod prelude {
+    pub use super::sequential::*;
+}
+
+#[cfg_attr(
+    all(feature = "parallel", not(target_family = "wasm")),
+    allow(dead_code)
+)]
+mod sequential {
+    /// `into_par_iter`, as `into_iter`
+    pub trait IntoParallelIterator: IntoIterator + Sized {
+        fn into_par_iter(self) -> Self::IntoIter {
+            self.into_iter()
+        }
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I {}
+
+    /// `par_iter`, as `iter`
+    pub trait IntoParallelRefIterator<'a> {
+        type Iter: Iterator;
+
+        fn par_iter(&'a self) -> Self::Iter;
+    }
+
+    impl<'a, C: ?Sized + 'a> IntoParallelRefIterator<'a> for C
+    where
+        &'a C: IntoIterator,
+    {
+        type Iter = <&'a C as IntoIterator>::IntoIter;
+
+        fn par_iter(&'a self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+
+    /// rayon's methods that plain iterators don't have
+    pub trait ParallelIterator: Iterator + Sized {
+        /// `find_map`: the first match in order, like rayon's
+        fn find_map_first<R>(mut self, f: impl FnMut(Self::Item) -> Option<R>) -> Option<R> {
+            self.find_map(f)
+        }
+    }
+
+    impl<I: Iterator> ParallelIterator for I {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prelude::*;
+    use super::sequential;
+
+    #[test]
+    fn same_results() {
+        let numbers: Vec<u32> = (1..=100).collect();
+        let sum: u32 = numbers.par_iter().map(|n| n * 2).sum();
+        assert_eq!(sum, 10_100);
+        assert_eq!(
+            (0..1000)
+                .into_par_iter()
+                .find_map_first(|n| (n * n > 500).then_some(n)),
+            Some(23)
+        );
+
+        // The stand-ins, whichever prelude is in use
+        let doubled: Vec<u32> = sequential::IntoParallelRefIterator::par_iter(&numbers)
+            .map(|n| n * 2)
+            .collect();
+        assert_eq!(doubled.len(), 100);
+        assert_eq!(
+            sequential::ParallelIterator::find_map_first(
+                sequential::IntoParallelIterator::into_par_iter(0..10),
+                |n| (n > 3).then_some(n)
+            ),
+            Some(4)
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! nom combinators for the shapes puzzle inputs keep coming in: one item per line, blocks
+//! separated by blank lines, `x=-3, y=12` coordinates, grids of tiles and numbers in prose.
+//!
+//! Line breaks are matched with `line_ending`, so CRLF input works everywhere. None of the
+//! combinators eat the whitespace after them (that would swallow the blank line separating
+//! blocks); [`parse_all`] skips trailing whitespace once the whole input was parsed.
+
+use std::fmt;
+use std::str::FromStr;
+
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, digit1, line_ending, multispace0, one_of, satisfy, space0};
+use nom::combinator::{all_consuming, map, map_opt, map_res, opt, recognize};
+use nom::error::{ErrorKind, FromExternalError, ParseError as NomParseError};
+use nom::multi::{many1, many1_count, separated_list0, separated_list1};
+use nom::sequence::{pair, preceded, separated_pair, terminated, tuple};
+use nom::{Finish, IResult, Parser};
+
+use crate::grid::Grid;
+use crate::point::Point2;
+
+/// One `item` per line
+pub fn lines_of<'a, O, E, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: NomParseError<&'a str>,
+{
+    separated_list0(line_ending, item)
+}
+
+/// A line that's empty or only has spaces and tabs, with the line break before it
+fn blank_lines<'a, E: NomParseError<&'a str>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    recognize(pair(line_ending, many1_count(pair(space0, line_ending))))(input)
+}
+
+/// Blocks separated by one or more blank lines, like `"1\n2\n\n3\n"`
+pub fn blocks_of<'a, O, E, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: NomParseError<&'a str>,
+{
+    separated_list0(blank_lines, block)
+}
+
+/// An integer with an optional `+` or `-` sign, of any integer type
+pub fn signed_int<'a, T, E>(input: &'a str) -> IResult<&'a str, T, E>
+where
+    T: FromStr,
+    E: NomParseError<&'a str> + FromExternalError<&'a str, T::Err>,
+{
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// An integer without a sign, of any integer type
+pub fn unsigned_int<'a, T, E>(input: &'a str) -> IResult<&'a str, T, E>
+where
+    T: FromStr,
+    E: NomParseError<&'a str> + FromExternalError<&'a str, T::Err>,
+{
+    map_res(digit1, str::parse)(input)
+}
+
+/// A value after a label and `=` or `:`, like `x=-3` or `Time:  7`
+pub fn labelled_value<'a, O, E, F>(
+    label: &'a str,
+    value: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: NomParseError<&'a str>,
+{
+    preceded(tuple((tag(label), space0, one_of("=:"), space0)), value)
+}
+
+/// A point written as `x=-3, y=12`
+pub fn labelled_point<'a, E>(input: &'a str) -> IResult<&'a str, Point2, E>
+where
+    E: NomParseError<&'a str> + FromExternalError<&'a str, std::num::ParseIntError>,
+{
+    map(
+        separated_pair(
+            labelled_value("x", signed_int),
+            pair(char(','), space0),
+            labelled_value("y", signed_int),
+        ),
+        |(x, y)| Point2::new(x, y),
+    )(input)
+}
+
+/// A single character on a line that `T` parses from, like a `#[derive(Tile)]` enum
+pub fn tile<'a, T, E>(input: &'a str) -> IResult<&'a str, T, E>
+where
+    T: TryFrom<char>,
+    E: NomParseError<&'a str> + FromExternalError<&'a str, T::Error>,
+{
+    map_res(satisfy(|c| c != '\r' && c != '\n'), T::try_from)(input)
+}
+
+/// A rectangular grid with one `cell` per character, like `grid_of(tile::<Square, _>)`.
+/// Fails if the lines don't all have the same amount of cells.
+pub fn grid_of<'a, T, E, F>(cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>, E>
+where
+    F: Parser<&'a str, T, E>,
+    E: NomParseError<&'a str>,
+{
+    map_opt(separated_list1(line_ending, many1(cell)), |rows| {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        rows.iter()
+            .all(|row| row.len() == width)
+            .then(|| Grid::from_vec(width, height, rows.into_iter().flatten().collect()))
+    })
+}
+
+/// Every integer in `input`, ignoring everything around them.
+/// A `-` is a sign when it's right before the digits and not right after other digits, so
+/// `"x=-3"` has -3, but `"2-4"` has 2 and 4.
+pub fn ints_anywhere<T: FromStr>(input: &str) -> Vec<T> {
+    let bytes = input.as_bytes();
+    let mut ints = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let mut start = i;
+        if start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_digit())
+        {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if let Ok(int) = input[start..i].parse() {
+            ints.push(int);
+        }
+    }
+    ints
+}
+
+/// Why [`parse_all`] failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Where the parser got stuck, counting from 0
+    pub position: Point2,
+    pub kind: ErrorKind,
+    /// The rest of the line from there
+    pub found: String,
+}
+
+impl ParseError {
+    fn at(input: &str, error: nom::error::Error<&str>) -> Self {
+        let parsed = &input[..input.len() - error.input.len()];
+        let line = parsed.matches('\n').count();
+        let column = parsed
+            .rsplit_once('\n')
+            .map_or(parsed, |(_, last_line)| last_line)
+            .chars()
+            .count();
+        let found = error.input.lines().next().unwrap_or_default();
+
+        ParseError {
+            position: Point2::new(column as i64, line as i64),
+            kind: error.code,
+            found: found.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: ",
+            self.position.y + 1,
+            self.position.x + 1
+        )?;
+        if self.found.is_empty() {
+            write!(f, "unexpected end of line ({})", self.kind.description())
+        } else {
+            write!(
+                f,
+                "unexpected {:?} ({})",
+                self.found,
+                self.kind.description()
+            )
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs `parser` on all of `input`, apart from trailing whitespace
+pub fn parse_all<'a, O, F>(mut parser: F, input: &'a str) -> Result<O, ParseError>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    all_consuming(terminated(|i| parser.parse(i), multispace0))(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|error| ParseError::at(input, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::Tile;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Tile)]
+    enum Square {
+        #[tile('#')]
+        Wall,
+        #[tile('.')]
+        Open,
+    }
+
+    #[test]
+    fn lines_and_blocks() {
+        let numbers = |input| parse_all(lines_of(signed_int::<i32, _>), input);
+        assert_eq!(numbers("1\n-2\n+3\n"), Ok(vec![1, -2, 3]));
+        assert_eq!(numbers("1\r\n-2\r\n+3\r\n\r\n"), Ok(vec![1, -2, 3]));
+        assert_eq!(numbers(""), Ok(vec![]));
+
+        let blocks = parse_all(
+            blocks_of(lines_of(unsigned_int::<u8, _>)),
+            "1\r\n2\r\n\r\n3\n \n\n4\n",
+        );
+        assert_eq!(blocks, Ok(vec![vec![1, 2], vec![3], vec![4]]));
+    }
+
+    #[test]
+    fn labelled() {
+        let sensor = parse_all(
+            separated_pair(labelled_point, tag(": "), labelled_point),
+            "x=2, y=-18: x=-2,y=15",
+        );
+        assert_eq!(sensor, Ok((Point2::new(2, -18), Point2::new(-2, 15))));
+
+        let time = parse_all(
+            labelled_value("Time", unsigned_int::<u64, _>),
+            "Time:   71530",
+        );
+        assert_eq!(time, Ok(71530));
+    }
+
+    #[test]
+    fn grids() {
+        let grid = parse_all(grid_of(tile::<Square, _>), "#.\r\n.#\r\n").unwrap();
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid[Point2::new(1, 0)], Square::Open);
+
+        assert!(parse_all(grid_of(tile::<Square, _>), "#.\n.\n").is_err());
+        assert!(parse_all(grid_of(tile::<Square, _>), "#.\n.@\n").is_err());
+    }
+
+    #[test]
+    fn ints_in_text() {
+        assert_eq!(
+            ints_anywhere::<i64>("Sensor at x=-3, y=12: 2-4,6-8 and -1"),
+            [-3, 12, 2, 4, 6, 8, -1]
+        );
+        assert!(ints_anywhere::<u8>("none here").is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let error = parse_all(lines_of(signed_int::<i32, _>), "1\n2\nthree\n").unwrap_err();
+        assert_eq!(error.position, Point2::new(0, 2));
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: unexpected \"three\" (End of file)"
+        );
+    }
+}
@@ -0,0 +1,424 @@
+//! Plays a sequence of [`Frame`]s as an animation in the terminal.
+//!
+//! Frames are pulled from the iterator only when they're first shown, and kept (rendered) so
+//! playback can step back. Controls:
+//!
+//! | key                 | action                          |
+//! |---------------------|---------------------------------|
+//! | `space`, `p`        | pause / resume                  |
+//! | `→`, `l`            | next frame (pauses)             |
+//! | `←`, `h`            | previous frame (pauses)         |
+//! | `↑`, `+`            | faster                          |
+//! | `↓`, `-`            | slower                          |
+//! | `g`, digits, `enter`| jump to a frame                 |
+//! | `q`, `esc`          | quit                            |
+//!
+//! When stdout isn't a terminal, only the last frame is printed, without colours. WebAssembly
+//! builds have no terminal to play in, so they always do that.
+
+use std::collections::VecDeque;
+use std::io;
+#[cfg(not(target_family = "wasm"))]
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
+
+#[cfg(not(target_family = "wasm"))]
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+#[cfg(not(target_family = "wasm"))]
+use crossterm::{cursor, execute, queue, terminal};
+
+use crate::render::Frame;
+
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// Whether the program was started with `--animate`
+#[must_use]
+pub fn animate_requested() -> bool {
+    std::env::args().skip(1).any(|arg| arg == "--animate")
+}
+
+/// Something the viewer asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    TogglePause,
+    Forward,
+    Back,
+    Faster,
+    Slower,
+    /// Jump to a frame, counting from 0
+    Jump(usize),
+    Quit,
+}
+
+/// The state of an animation, without any terminal handling.
+pub struct Playback<I> {
+    frames: I,
+    /// Rendered frames, the first one being frame `first_kept`
+    history: VecDeque<String>,
+    first_kept: usize,
+    max_history: usize,
+    ansi: bool,
+    index: usize,
+    finished: bool,
+    pub paused: bool,
+    pub delay: Duration,
+}
+
+impl<I: Iterator<Item = Frame>> Playback<I> {
+    /// `None` if there are no frames at all.
+    /// Rendered frames are kept for stepping back, up to `max_history` of them.
+    pub fn new(
+        frames: impl IntoIterator<IntoIter = I>,
+        delay: Duration,
+        max_history: usize,
+        ansi: bool,
+    ) -> Option<Self> {
+        let mut frames = frames.into_iter();
+        let first = frames.next()?.render(ansi);
+        Some(Playback {
+            frames,
+            history: VecDeque::from([first]),
+            first_kept: 0,
+            max_history: max_history.max(1),
+            ansi,
+            index: 0,
+            finished: false,
+            paused: false,
+            delay,
+        })
+    }
+
+    /// The frame on screen, counting from 0
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The amount of frames, once the last one has been seen
+    #[must_use]
+    pub fn frame_count(&self) -> Option<usize> {
+        self.finished
+            .then_some(self.first_kept + self.history.len())
+    }
+
+    /// The rendered frame on screen
+    #[must_use]
+    pub fn current(&self) -> &str {
+        &self.history[self.index - self.first_kept]
+    }
+
+    /// Makes sure frame `index` is loaded, as far as there are frames. Returns whether it is.
+    fn load(&mut self, index: usize) -> bool {
+        while !self.finished && self.first_kept + self.history.len() <= index {
+            match self.frames.next() {
+                Some(frame) => {
+                    self.history.push_back(frame.render(self.ansi));
+                    if self.history.len() > self.max_history {
+                        self.history.pop_front();
+                        self.first_kept += 1;
+                    }
+                }
+                None => self.finished = true,
+            }
+        }
+        index < self.first_kept + self.history.len()
+    }
+
+    fn go_to(&mut self, index: usize) {
+        let index = index.max(self.first_kept);
+        if self.load(index) {
+            self.index = index;
+        } else {
+            // Past the end: show the last frame
+            self.index = self.first_kept + self.history.len() - 1;
+            self.paused = true;
+        }
+    }
+
+    /// Advances one frame unless paused. Pauses at the last frame.
+    pub fn tick(&mut self) {
+        if !self.paused {
+            self.go_to(self.index + 1);
+        }
+    }
+
+    /// Returns `false` when the viewer wants to quit
+    pub fn apply(&mut self, control: Control) -> bool {
+        match control {
+            Control::TogglePause => self.paused = !self.paused,
+            Control::Forward => {
+                self.paused = true;
+                self.go_to(self.index + 1);
+            }
+            Control::Back => {
+                self.paused = true;
+                self.go_to(self.index.saturating_sub(1));
+            }
+            Control::Faster => self.delay = (self.delay / 2).max(MIN_DELAY),
+            Control::Slower => self.delay = (self.delay * 2).min(MAX_DELAY),
+            Control::Jump(index) => {
+                self.paused = true;
+                self.go_to(index);
+            }
+            Control::Quit => return false,
+        }
+        true
+    }
+}
+
+/// Plays frames in the terminal, see the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Player {
+    delay: Duration,
+    max_history: usize,
+    paused: bool,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Player {
+            delay: Duration::from_millis(50),
+            max_history: 10_000,
+            paused: false,
+        }
+    }
+}
+
+impl Player {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The time between frames when playing
+    #[must_use]
+    pub fn delay(self, delay: Duration) -> Self {
+        Player { delay, ..self }
+    }
+
+    /// How many frames to keep for stepping back
+    #[must_use]
+    pub fn max_history(self, frames: usize) -> Self {
+        Player {
+            max_history: frames,
+            ..self
+        }
+    }
+
+    /// Whether to start paused on the first frame
+    #[must_use]
+    pub fn paused(self, paused: bool) -> Self {
+        Player { paused, ..self }
+    }
+
+    /// Plays the frames until the viewer quits
+    pub fn play(&self, frames: impl IntoIterator<Item = Frame>) -> io::Result<()> {
+        #[cfg(not(target_family = "wasm"))]
+        if io::stdout().is_terminal() {
+            return self.play_in_terminal(frames);
+        }
+
+        if let Some(last) = frames.into_iter().last() {
+            print!("{last}");
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn play_in_terminal(&self, frames: impl IntoIterator<Item = Frame>) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        let Some(mut playback) = Playback::new(frames, self.delay, self.max_history, true) else {
+            return Ok(());
+        };
+        playback.paused = self.paused;
+
+        let _terminal = RawTerminal::enter(&mut stdout)?;
+        let mut jump: Option<String> = None;
+
+        loop {
+            draw(&mut stdout, &playback, jump.as_deref())?;
+
+            // Paused playback just waits for the next key
+            if !playback.paused && !event::poll(playback.delay)? {
+                playback.tick();
+                continue;
+            }
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+
+            if let Some(digits) = &mut jump {
+                match key.code {
+                    KeyCode::Char(c) if c.is_ascii_digit() => digits.push(c),
+                    KeyCode::Backspace => {
+                        digits.pop();
+                    }
+                    KeyCode::Enter => {
+                        if let Ok(frame) = digits.parse() {
+                            playback.apply(Control::Jump(frame));
+                        }
+                        jump = None;
+                    }
+                    _ => jump = None,
+                }
+                continue;
+            }
+
+            if key.code == KeyCode::Char('g') {
+                jump = Some(String::new());
+            } else if let Some(control) = control_for(key) {
+                if !playback.apply(control) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn control_for(key: KeyEvent) -> Option<Control> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        return Some(Control::Quit);
+    }
+
+    Some(match key.code {
+        KeyCode::Char(' ' | 'p') => Control::TogglePause,
+        KeyCode::Right | KeyCode::Char('l') => Control::Forward,
+        KeyCode::Left | KeyCode::Char('h') => Control::Back,
+        KeyCode::Up | KeyCode::Char('+' | '=') => Control::Faster,
+        KeyCode::Down | KeyCode::Char('-') => Control::Slower,
+        KeyCode::Esc | KeyCode::Char('q') => Control::Quit,
+        _ => return None,
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn draw<I: Iterator<Item = Frame>>(
+    out: &mut impl Write,
+    playback: &Playback<I>,
+    jump: Option<&str>,
+) -> io::Result<()> {
+    queue!(out, cursor::MoveTo(0, 0))?;
+    for line in playback.current().lines() {
+        write!(out, "{line}")?;
+        queue!(out, terminal::Clear(terminal::ClearType::UntilNewLine))?;
+        write!(out, "\r\n")?;
+    }
+
+    let total = playback
+        .frame_count()
+        .map_or_else(|| String::from("?"), |len| len.to_string());
+    let state = if playback.paused { "paused" } else { "playing" };
+    write!(
+        out,
+        "frame {}/{total}  {state}  {}ms  ",
+        playback.index(),
+        playback.delay.as_millis()
+    )?;
+    match jump {
+        Some(digits) => write!(out, "jump to frame: {digits}")?,
+        None => write!(
+            out,
+            "[space] pause  [←/→] step  [↑/↓] speed  [g] jump  [q] quit"
+        )?,
+    }
+    queue!(out, terminal::Clear(terminal::ClearType::FromCursorDown))?;
+    out.flush()
+}
+
+/// Raw mode on the alternate screen, restored when dropped
+#[cfg(not(target_family = "wasm"))]
+struct RawTerminal;
+
+#[cfg(not(target_family = "wasm"))]
+impl RawTerminal {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(RawTerminal)
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::render::Glyph;
+
+    fn frames(n: usize) -> impl Iterator<Item = Frame> {
+        (0..n).map(|i| {
+            let digit = char::from_digit(i as u32 % 10, 10).unwrap();
+            Frame::from_grid(&Grid::filled(1, 1, ()), |()| Glyph::plain(digit))
+        })
+    }
+
+    fn playback(n: usize, max_history: usize) -> Playback<impl Iterator<Item = Frame>> {
+        Playback::new(frames(n), Duration::from_millis(40), max_history, false).unwrap()
+    }
+
+    #[test]
+    fn plays_and_stops_at_the_end() {
+        let mut playback = playback(3, 10);
+        assert_eq!(playback.current(), "0\n");
+        assert_eq!(playback.frame_count(), None);
+
+        playback.tick();
+        playback.tick();
+        assert_eq!(playback.current(), "2\n");
+        assert!(!playback.paused);
+
+        playback.tick();
+        assert_eq!(playback.index(), 2);
+        assert!(playback.paused);
+        assert_eq!(playback.frame_count(), Some(3));
+    }
+
+    #[test]
+    fn steps_and_jumps() {
+        let mut playback = playback(20, 100);
+        assert!(playback.apply(Control::Forward));
+        assert!(playback.paused);
+        playback.tick();
+        assert_eq!(playback.index(), 1);
+
+        playback.apply(Control::Jump(12));
+        assert_eq!(playback.current(), "2\n");
+        playback.apply(Control::Back);
+        assert_eq!(playback.index(), 11);
+
+        playback.apply(Control::Jump(500));
+        assert_eq!(playback.index(), 19);
+        assert!(!playback.apply(Control::Quit));
+    }
+
+    #[test]
+    fn speed_and_history() {
+        let mut playback = playback(20, 5);
+        playback.apply(Control::Slower);
+        assert_eq!(playback.delay, Duration::from_millis(80));
+        for _ in 0..20 {
+            playback.apply(Control::Faster);
+        }
+        assert_eq!(playback.delay, MIN_DELAY);
+
+        // Only the last 5 frames can be stepped back to
+        playback.apply(Control::Jump(10));
+        playback.apply(Control::Jump(0));
+        assert_eq!(playback.index(), 6);
+
+        assert!(Playback::new(frames(0), MIN_DELAY, 5, false).is_none());
+    }
+}
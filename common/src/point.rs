@@ -0,0 +1,152 @@
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point on an integer 2D grid. `y` grows downwards, like the puzzle inputs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A point on an integer 3D grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    /// North, east, south, west
+    pub const ORTHOGONAL: [Self; 4] = [
+        Self::new(0, -1),
+        Self::new(1, 0),
+        Self::new(0, 1),
+        Self::new(-1, 0),
+    ];
+
+    /// All eight directions, clockwise starting at north
+    pub const ADJACENT: [Self; 8] = [
+        Self::new(0, -1),
+        Self::new(1, -1),
+        Self::new(1, 0),
+        Self::new(1, 1),
+        Self::new(0, 1),
+        Self::new(-1, 1),
+        Self::new(-1, 0),
+        Self::new(-1, -1),
+    ];
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    #[must_use]
+    pub fn orthogonal_neighbours(self) -> [Self; 4] {
+        Self::ORTHOGONAL.map(|d| self + d)
+    }
+
+    #[must_use]
+    pub fn neighbours(self) -> [Self; 8] {
+        Self::ADJACENT.map(|d| self + d)
+    }
+
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Self = Self::new(0, 0, 0);
+
+    /// The six face-adjacent directions
+    pub const ORTHOGONAL: [Self; 6] = [
+        Self::new(1, 0, 0),
+        Self::new(-1, 0, 0),
+        Self::new(0, 1, 0),
+        Self::new(0, -1, 0),
+        Self::new(0, 0, 1),
+        Self::new(0, 0, -1),
+    ];
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    #[must_use]
+    pub fn orthogonal_neighbours(self) -> [Self; 6] {
+        Self::ORTHOGONAL.map(|d| self + d)
+    }
+
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl Add for $point {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)+
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)+
+            }
+        }
+
+        impl Neg for $point {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = Self;
+
+            fn mul(self, rhs: i64) -> Self {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Self::new(x, y, z)
+    }
+}
@@ -0,0 +1,480 @@
+//! Drawing grids for humans: per-cell styles, overlays, a border with a title and caption,
+//! and a legend.
+//!
+//! A [`Frame`] renders to a string, with ANSI escapes or as plain text.
+//! [`Frame::print`] only uses escapes when stdout is a terminal and `NO_COLOR` isn't set.
+
+use std::fmt;
+use std::io::{self, IsTerminal};
+
+use crate::flood::Bounds;
+use crate::grid::Grid;
+use crate::point::Point2;
+
+/// A terminal colour: one of the 256 ANSI palette colours or a true colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Ansi(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    pub const BLACK: Color = Color::Ansi(0);
+    pub const RED: Color = Color::Ansi(1);
+    pub const GREEN: Color = Color::Ansi(2);
+    pub const YELLOW: Color = Color::Ansi(3);
+    pub const BLUE: Color = Color::Ansi(4);
+    pub const MAGENTA: Color = Color::Ansi(5);
+    pub const CYAN: Color = Color::Ansi(6);
+    pub const WHITE: Color = Color::Ansi(7);
+    pub const GREY: Color = Color::Ansi(8);
+
+    /// The colour as RGB, using the xterm values for the palette colours
+    #[must_use]
+    pub fn rgb(self) -> (u8, u8, u8) {
+        const BASIC: [(u8, u8, u8); 16] = [
+            (0, 0, 0),
+            (205, 0, 0),
+            (0, 205, 0),
+            (205, 205, 0),
+            (0, 0, 238),
+            (205, 0, 205),
+            (0, 205, 205),
+            (229, 229, 229),
+            (127, 127, 127),
+            (255, 0, 0),
+            (0, 255, 0),
+            (255, 255, 0),
+            (92, 92, 255),
+            (255, 0, 255),
+            (0, 255, 255),
+            (255, 255, 255),
+        ];
+        const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+        match self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Ansi(n @ 0..=15) => BASIC[n as usize],
+            Color::Ansi(n @ 16..=231) => {
+                let n = n - 16;
+                (
+                    CUBE[(n / 36) as usize],
+                    CUBE[(n / 6 % 6) as usize],
+                    CUBE[(n % 6) as usize],
+                )
+            }
+            Color::Ansi(n) => {
+                let grey = 8 + 10 * (n - 232);
+                (grey, grey, grey)
+            }
+        }
+    }
+
+    fn sgr(self, background: bool) -> String {
+        let layer = if background { 48 } else { 38 };
+        match self {
+            Color::Ansi(n) => format!("{layer};5;{n}"),
+            Color::Rgb(r, g, b) => format!("{layer};2;{r};{g};{b}"),
+        }
+    }
+}
+
+/// How text is drawn. Unset colours use the terminal's default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+}
+
+impl Style {
+    pub const PLAIN: Style = Style {
+        fg: None,
+        bg: None,
+        bold: false,
+    };
+
+    #[must_use]
+    pub const fn fg(color: Color) -> Self {
+        Style {
+            fg: Some(color),
+            ..Style::PLAIN
+        }
+    }
+
+    #[must_use]
+    pub const fn on(self, background: Color) -> Self {
+        Style {
+            bg: Some(background),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn bold(self) -> Self {
+        Style { bold: true, ..self }
+    }
+
+    /// `over` drawn on top of `self`: whatever `over` sets wins
+    #[must_use]
+    pub fn patch(self, over: Style) -> Self {
+        Style {
+            fg: over.fg.or(self.fg),
+            bg: over.bg.or(self.bg),
+            bold: self.bold || over.bold,
+        }
+    }
+
+    fn paint(self, text: &str, ansi: bool, out: &mut String) {
+        if !ansi || self == Style::PLAIN {
+            out.push_str(text);
+            return;
+        }
+
+        let codes: Vec<String> = [
+            self.bold.then(|| String::from("1")),
+            self.fg.map(|c| c.sgr(false)),
+            self.bg.map(|c| c.sgr(true)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        out.push_str(&format!("\x1b[{}m{text}\x1b[0m", codes.join(";")));
+    }
+}
+
+/// A character with a style: what a single grid cell looks like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Glyph {
+    pub ch: char,
+    pub style: Style,
+}
+
+impl Glyph {
+    #[must_use]
+    pub const fn new(ch: char, style: Style) -> Self {
+        Glyph { ch, style }
+    }
+
+    #[must_use]
+    pub const fn plain(ch: char) -> Self {
+        Glyph::new(ch, Style::PLAIN)
+    }
+}
+
+/// A grid of glyphs with decorations, ready to be rendered.
+///
+/// Positions are in the coordinates of the world it was made from:
+/// a frame made with [`Frame::from_bounds`] has its top left corner at `bounds.min`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    origin: Point2,
+    glyphs: Grid<Glyph>,
+    cell_width: usize,
+    border: Option<Style>,
+    title: Option<(String, Style)>,
+    caption: Option<(String, Style)>,
+    legend: Vec<(Glyph, String)>,
+}
+
+impl Frame {
+    /// A frame showing every cell of `grid`
+    pub fn from_grid<T>(grid: &Grid<T>, mut glyph: impl FnMut(&T) -> Glyph) -> Self {
+        Self::new(Point2::ORIGIN, grid.map(|cell| glyph(cell)))
+    }
+
+    /// A frame showing every point in `bounds`, for worlds that aren't stored as a grid
+    pub fn from_bounds(bounds: &Bounds<Point2>, mut glyph: impl FnMut(Point2) -> Glyph) -> Self {
+        let width = (bounds.max.x - bounds.min.x + 1).max(0) as usize;
+        let height = (bounds.max.y - bounds.min.y + 1).max(0) as usize;
+        let glyphs = (0..height as i64)
+            .flat_map(|y| (0..width as i64).map(move |x| Point2::new(x, y)))
+            .map(|p| glyph(bounds.min + p))
+            .collect();
+        Self::new(bounds.min, Grid::from_vec(width, height, glyphs))
+    }
+
+    fn new(origin: Point2, glyphs: Grid<Glyph>) -> Self {
+        Frame {
+            origin,
+            glyphs,
+            cell_width: 1,
+            border: None,
+            title: None,
+            caption: None,
+            legend: Vec::new(),
+        }
+    }
+
+    /// The glyphs without any decoration, with the top left one at `(0, 0)`
+    #[must_use]
+    pub fn glyphs(&self) -> &Grid<Glyph> {
+        &self.glyphs
+    }
+
+    #[must_use]
+    pub fn get(&self, p: Point2) -> Option<&Glyph> {
+        self.glyphs.get(p - self.origin)
+    }
+
+    /// Restyles the glyphs at `points`, like a path or a set of visited cells.
+    /// Overlays apply in order, so later ones win. Points outside the frame are ignored.
+    #[must_use]
+    pub fn highlight(mut self, points: impl IntoIterator<Item = Point2>, style: Style) -> Self {
+        for p in points {
+            if let Some(glyph) = self.glyphs.get_mut(p - self.origin) {
+                glyph.style = glyph.style.patch(style);
+            }
+        }
+        self
+    }
+
+    /// Replaces the glyphs at `points`. Points outside the frame are ignored.
+    #[must_use]
+    pub fn draw(mut self, points: impl IntoIterator<Item = Point2>, glyph: Glyph) -> Self {
+        for p in points {
+            if let Some(cell) = self.glyphs.get_mut(p - self.origin) {
+                *cell = glyph;
+            }
+        }
+        self
+    }
+
+    /// How many columns every cell takes. Terminal characters are about twice as tall as
+    /// they're wide, so 2 gives square cells.
+    #[must_use]
+    pub fn cell_width(self, columns: usize) -> Self {
+        Frame {
+            cell_width: columns.max(1),
+            ..self
+        }
+    }
+
+    /// Draws a box around the grid
+    #[must_use]
+    pub fn border(self, style: Style) -> Self {
+        Frame {
+            border: Some(style),
+            ..self
+        }
+    }
+
+    /// Text in the top left of the border
+    #[must_use]
+    pub fn title(self, text: impl Into<String>, style: Style) -> Self {
+        Frame {
+            title: Some((text.into(), style)),
+            ..self
+        }
+    }
+
+    /// Text in the bottom right of the border, like a round counter
+    #[must_use]
+    pub fn caption(self, text: impl Into<String>, style: Style) -> Self {
+        Frame {
+            caption: Some((text.into(), style)),
+            ..self
+        }
+    }
+
+    /// Adds an entry to the legend below the frame
+    #[must_use]
+    pub fn legend(mut self, glyph: Glyph, label: impl Into<String>) -> Self {
+        self.legend.push((glyph, label.into()));
+        self
+    }
+
+    /// The frame as text, with ANSI escapes if `ansi` is set
+    #[must_use]
+    pub fn render(&self, ansi: bool) -> String {
+        let mut out = String::new();
+
+        if let Some(border) = self.border {
+            self.render_edge(('┏', '┓'), self.title.as_ref(), false, ansi, &mut out);
+            for row in self.glyphs.rows() {
+                border.paint("┃", ansi, &mut out);
+                self.render_row(row, ansi, &mut out);
+                border.paint("┃", ansi, &mut out);
+                out.push('\n');
+            }
+            self.render_edge(('┗', '┛'), self.caption.as_ref(), true, ansi, &mut out);
+        } else {
+            for (text, style) in [&self.title, &self.caption].into_iter().flatten() {
+                style.paint(text, ansi, &mut out);
+                out.push('\n');
+            }
+            for row in self.glyphs.rows() {
+                self.render_row(row, ansi, &mut out);
+                out.push('\n');
+            }
+        }
+
+        if !self.legend.is_empty() {
+            for (i, (glyph, label)) in self.legend.iter().enumerate() {
+                if i > 0 {
+                    out.push_str("   ");
+                }
+                glyph.style.paint(&glyph.ch.to_string(), ansi, &mut out);
+                out.push(' ');
+                out.push_str(label);
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Prints the frame, with colours if stdout is a terminal
+    pub fn print(&self) {
+        print!("{}", self.render(use_colour()));
+    }
+
+    fn render_row(&self, row: &[Glyph], ansi: bool, out: &mut String) {
+        // Paint runs of equally styled cells at once, to keep the escapes down
+        let mut run = String::new();
+        let mut run_style = None;
+        for glyph in row {
+            if run_style != Some(glyph.style) {
+                if let Some(style) = run_style {
+                    Style::paint(style, &run, ansi, out);
+                }
+                run.clear();
+                run_style = Some(glyph.style);
+            }
+            run.push(glyph.ch);
+            run.extend(std::iter::repeat_n(' ', self.cell_width - 1));
+        }
+        if let Some(style) = run_style {
+            style.paint(&run, ansi, out);
+        }
+    }
+
+    fn render_edge(
+        &self,
+        (left, right): (char, char),
+        text: Option<&(String, Style)>,
+        align_right: bool,
+        ansi: bool,
+        out: &mut String,
+    ) {
+        let border = self.border.unwrap_or_default();
+        let inner_width = self.glyphs.width() * self.cell_width;
+
+        let Some((text, style)) = text else {
+            let edge: String = std::iter::repeat_n('━', inner_width).collect();
+            border.paint(&format!("{left}{edge}{right}"), ansi, out);
+            out.push('\n');
+            return;
+        };
+
+        // Keep one line segment between the corner and the text, and cut off what doesn't fit
+        let text: String = format!(" {text} ")
+            .chars()
+            .take(inner_width.saturating_sub(1))
+            .collect();
+        let fill = inner_width.saturating_sub(text.chars().count() + 1);
+        let (before, after) = if align_right { (fill, 1) } else { (1, fill) };
+
+        let line = |n| std::iter::repeat_n('━', n).collect::<String>();
+        border.paint(&format!("{left}{}", line(before)), ansi, out);
+        style.paint(&text, ansi, out);
+        border.paint(&format!("{}{right}", line(after)), ansi, out);
+        out.push('\n');
+    }
+}
+
+/// The frame without colours
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(false))
+    }
+}
+
+/// Whether to use ANSI escapes on stdout: only for terminals, and not when `NO_COLOR` is set
+#[must_use]
+pub fn use_colour() -> bool {
+    std::env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Grid<bool> {
+        Grid::parse("#.#\n.#.\n", |c| c == '#')
+    }
+
+    fn glyph(&wall: &bool) -> Glyph {
+        if wall {
+            Glyph::new('#', Style::fg(Color::YELLOW))
+        } else {
+            Glyph::plain('.')
+        }
+    }
+
+    #[test]
+    fn plain_text() {
+        let frame = Frame::from_grid(&checkerboard(), glyph)
+            .cell_width(3)
+            .border(Style::fg(Color::GREEN))
+            .title("Map", Style::PLAIN)
+            .caption("R-1", Style::PLAIN.bold())
+            .legend(Glyph::new('#', Style::fg(Color::YELLOW)), "wall");
+
+        let expected = "\
+┏━ Map ━━━┓
+┃#  .  #  ┃
+┃.  #  .  ┃
+┗━━━ R-1 ━┛
+# wall
+";
+        assert_eq!(frame.render(false), expected);
+        assert_eq!(frame.to_string(), expected);
+
+        let narrow = Frame::from_grid(&checkerboard(), glyph)
+            .border(Style::PLAIN)
+            .title("Long title", Style::PLAIN);
+        assert_eq!(narrow.render(false), "┏━ L┓\n┃#.#┃\n┃.#.┃\n┗━━━┛\n");
+    }
+
+    #[test]
+    fn ansi_runs() {
+        let frame = Frame::from_grid(&Grid::parse("##.", |c| c == '#'), glyph);
+        assert_eq!(frame.render(true), "\x1b[38;5;3m##\x1b[0m.\n");
+
+        let wide = Frame::from_grid(&Grid::parse("#.", |c| c == '#'), glyph).cell_width(2);
+        assert_eq!(wide.render(false), "# . \n");
+    }
+
+    #[test]
+    fn overlays() {
+        let bounds = Bounds {
+            min: Point2::new(-1, -1),
+            max: Point2::new(1, 0),
+        };
+        let frame = Frame::from_bounds(&bounds, |_| Glyph::plain('.'))
+            .highlight(
+                [Point2::new(-1, -1), Point2::new(0, 0)],
+                Style::fg(Color::RED),
+            )
+            .highlight([Point2::new(0, 0)], Style::PLAIN.on(Color::BLUE))
+            .draw([Point2::new(1, 0), Point2::new(5, 5)], Glyph::plain('@'));
+
+        assert_eq!(frame.render(false), "...\n..@\n");
+        assert_eq!(
+            frame.get(Point2::new(0, 0)).map(|g| g.style),
+            Some(Style::fg(Color::RED).on(Color::BLUE))
+        );
+        assert_eq!(
+            frame.get(Point2::new(-1, -1)).map(|g| g.style),
+            Some(Style::fg(Color::RED))
+        );
+    }
+
+    #[test]
+    fn palette() {
+        assert_eq!(Color::Ansi(196).rgb(), (255, 0, 0));
+        assert_eq!(Color::Ansi(232).rgb(), (8, 8, 8));
+        assert_eq!(Color::RED.rgb(), (205, 0, 0));
+    }
+}
@@ -0,0 +1,751 @@
+//! Talking to adventofcode.com: the local store of puzzle inputs, and submitting answers.
+//!
+//! Requests go through a [`Transport`], so tests can point the [`Client`] at a stand-in.
+//! With the `fetch` feature, [`UreqTransport`] does real HTTP(S), except in WebAssembly
+//! builds. Every request carries the session cookie and a User-Agent saying who's asking,
+//! and requests are spaced out by at least [`Client::min_interval`].
+//!
+//! The [`InputStore`] keeps inputs in `inputs/<year>/<day>.txt`, like `inputs/2022/01.txt`,
+//! and only asks the site for the ones it doesn't have yet. Inputs never change, so they're
+//! kept forever.
+//!
+//! [`submit`] checks the [`AnswerDb`] first, and doesn't send answers whose verdict follows
+//! from earlier ones. What the site says about the others is recorded there.
+
+use std::cell::Cell;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::answers::{AnswerDb, Puzzle, Verdict};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Who's asking, so the site knows whom to contact about misbehaving requests
+pub const USER_AGENT: &str = concat!(
+    "aoc-common/",
+    env!("CARGO_PKG_VERSION"),
+    " (Advent of Code solutions of cor@pruijs.nl)"
+);
+
+/// What the site answered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends requests to the site. Error statuses are responses too, not errors.
+pub trait Transport {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> io::Result<Response>;
+
+    /// Posts a form, like `level=1&answer=42`
+    fn post_form(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        form: &[(&str, &str)],
+    ) -> io::Result<Response>;
+}
+
+#[derive(Debug)]
+pub enum SiteError {
+    Io(io::Error),
+    /// There's no session token to log in with
+    NoSession,
+    /// The site didn't accept the session token
+    BadSession,
+    NotUnlocked {
+        year: u16,
+        day: u8,
+    },
+    Status {
+        status: u16,
+        body: String,
+    },
+}
+
+impl fmt::Display for SiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SiteError::Io(e) => write!(f, "{e}"),
+            SiteError::NoSession => write!(
+                f,
+                "no session token: set AOC_SESSION or put it in {}",
+                session_path(&InputStore::default_root()).display()
+            ),
+            SiteError::BadSession => write!(f, "the session token was rejected, log in again"),
+            SiteError::NotUnlocked { year, day } => {
+                write!(f, "{year} day {day} isn't unlocked yet")
+            }
+            SiteError::Status { status, body } => {
+                let body = body.lines().next().unwrap_or_default();
+                write!(f, "the site answered {status}: {body}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SiteError {}
+
+impl From<io::Error> for SiteError {
+    fn from(e: io::Error) -> Self {
+        SiteError::Io(e)
+    }
+}
+
+/// What the site said about a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Judged(Verdict),
+    /// An answer was submitted too recently, try again after this long
+    Wait(Duration),
+    /// The part was already solved, so the answer wasn't judged
+    AlreadySolved,
+    /// A response that doesn't look like any of the above, as text
+    Unrecognised(String),
+}
+
+impl Outcome {
+    /// Reads the `<article>` of the page the site answers a submission with
+    #[must_use]
+    pub fn parse(html: &str) -> Self {
+        let message = html
+            .split_once("<article")
+            .and_then(|(_, article)| article.split_once("</article>"))
+            .map_or(html, |(article, _)| article);
+
+        if message.contains("That's the right answer") {
+            Outcome::Judged(Verdict::Right)
+        } else if message.contains("That's not the right answer") {
+            Outcome::Judged(if message.contains("too high") {
+                Verdict::TooHigh
+            } else if message.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            })
+        } else if message.contains("You gave an answer too recently") {
+            Outcome::Wait(wait_time(message).unwrap_or(Duration::from_secs(60)))
+        } else if message.contains("Did you already complete it?") {
+            Outcome::AlreadySolved
+        } else {
+            let text: String = message
+                .split('<')
+                .map(|part| part.split_once('>').map_or(part, |(_, text)| text))
+                .collect();
+            Outcome::Unrecognised(text.split_whitespace().collect::<Vec<_>>().join(" "))
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Judged(Verdict::Right) => write!(f, "that's the right answer"),
+            Outcome::Judged(Verdict::Wrong) => write!(f, "that's not the right answer"),
+            Outcome::Judged(Verdict::TooHigh) => write!(f, "that's too high"),
+            Outcome::Judged(Verdict::TooLow) => write!(f, "that's too low"),
+            Outcome::Wait(wait) => write!(f, "answered too recently, wait {}s", wait.as_secs()),
+            Outcome::AlreadySolved => write!(f, "that part is solved already"),
+            Outcome::Unrecognised(text) => write!(f, "unexpected response: {text}"),
+        }
+    }
+}
+
+/// The time in `You have 1m 23s left to wait`
+fn wait_time(message: &str) -> Option<Duration> {
+    let (_, rest) = message.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .try_fold(Duration::ZERO, |total, amount| {
+            let unit = amount.trim_start_matches(|c: char| c.is_ascii_digit());
+            let number: u64 = amount[..amount.len() - unit.len()].parse().ok()?;
+            let seconds = match unit {
+                "s" => number,
+                "m" => number * 60,
+                "h" => number * 60 * 60,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs(seconds))
+        })
+}
+
+/// A logged in client for the site
+pub struct Client {
+    transport: Box<dyn Transport>,
+    session: String,
+    base_url: String,
+    user_agent: String,
+    min_interval: Duration,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl Client {
+    /// A client for the real site that waits 5 seconds between requests
+    pub fn new(transport: impl Transport + 'static, session: impl Into<String>) -> Self {
+        Client {
+            transport: Box::new(transport),
+            session: session.into(),
+            base_url: String::from(BASE_URL),
+            user_agent: String::from(USER_AGENT),
+            min_interval: Duration::from_secs(5),
+            last_request: Cell::new(None),
+        }
+    }
+
+    /// A client for the real site, if there's a session token in `$AOC_SESSION` or the
+    /// [input store](InputStore::default_root)'s `.session`
+    #[cfg(all(feature = "fetch", not(target_family = "wasm")))]
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let session = session(&InputStore::default_root())?;
+        Some(Client::new(UreqTransport::new(), session))
+    }
+
+    /// Where the site is, like `http://127.0.0.1:8080` for a stand-in
+    #[must_use]
+    pub fn base_url(self, url: impl Into<String>) -> Self {
+        let url: String = url.into();
+        Client {
+            base_url: url.trim_end_matches('/').to_owned(),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn user_agent(self, user_agent: impl Into<String>) -> Self {
+        Client {
+            user_agent: user_agent.into(),
+            ..self
+        }
+    }
+
+    /// The least time between two requests
+    #[must_use]
+    pub fn min_interval(self, min_interval: Duration) -> Self {
+        Client {
+            min_interval,
+            ..self
+        }
+    }
+
+    /// Waits for the rate limit, then gets `path` from the site, or posts `form` to it
+    fn request(&self, path: &str, form: Option<&[(&str, &str)]>) -> Result<Response, SiteError> {
+        if let Some(last) = self.last_request.get() {
+            std::thread::sleep(self.min_interval.saturating_sub(last.elapsed()));
+        }
+        self.last_request.set(Some(Instant::now()));
+
+        let url = format!("{}{path}", self.base_url);
+        let cookie = format!("session={}", self.session);
+        let headers = [
+            ("Cookie", cookie.as_str()),
+            ("User-Agent", self.user_agent.as_str()),
+        ];
+        Ok(match form {
+            None => self.transport.get(&url, &headers)?,
+            Some(form) => self.transport.post_form(&url, &headers, form)?,
+        })
+    }
+
+    /// Downloads an input. Use an [`InputStore`] so it's only downloaded once.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, SiteError> {
+        let response = self.request(&format!("/{year}/day/{day}/input"), None)?;
+        Self::check(response, year, day)
+    }
+
+    /// Sends an answer without checking it first, see [`submit`]
+    pub fn submit(&self, puzzle: Puzzle, answer: &str) -> Result<Outcome, SiteError> {
+        let Puzzle { year, day, part } = puzzle;
+        let level = part.to_string();
+        let form = [("level", level.as_str()), ("answer", answer.trim())];
+        let response = self.request(&format!("/{year}/day/{day}/answer"), Some(&form))?;
+        Self::check(response, year, day).map(|page| Outcome::parse(&page))
+    }
+
+    fn check(response: Response, year: u16, day: u8) -> Result<String, SiteError> {
+        match response.status {
+            200 => Ok(response.body),
+            404 => Err(SiteError::NotUnlocked { year, day }),
+            400 | 401 | 403 | 500 if response.body.contains("log in") => Err(SiteError::BadSession),
+            status => Err(SiteError::Status {
+                status,
+                body: response.body,
+            }),
+        }
+    }
+}
+
+/// What [`submit`] did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    /// The answer wasn't sent, the database already knows what the site would say
+    Known(Verdict),
+    Sent(Outcome),
+}
+
+/// Sends an answer, unless `db` already knows its verdict, and records the site's verdict
+/// in `db`. Saving `db` is up to the caller.
+pub fn submit(
+    client: &Client,
+    db: &mut AnswerDb,
+    puzzle: Puzzle,
+    answer: &str,
+) -> Result<Submission, SiteError> {
+    if let Some(verdict) = db.judge(puzzle, answer) {
+        return Ok(Submission::Known(verdict));
+    }
+
+    let outcome = client.submit(puzzle, answer)?;
+    if let Outcome::Judged(verdict) = outcome {
+        db.record(puzzle, answer, verdict);
+    }
+    Ok(Submission::Sent(outcome))
+}
+
+/// Puzzle inputs on disk, in `<root>/<year>/<day>.txt`, fetched when they're missing
+pub struct InputStore {
+    root: PathBuf,
+    client: Option<Client>,
+}
+
+impl InputStore {
+    /// A store that only has what's on disk already
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        InputStore {
+            root: root.into(),
+            client: None,
+        }
+    }
+
+    /// Fetches missing inputs with `client`
+    #[must_use]
+    pub fn client(self, client: Client) -> Self {
+        InputStore {
+            client: Some(client),
+            ..self
+        }
+    }
+
+    /// `$AOC_INPUTS`, or the `inputs` directory at the root of this repository
+    #[must_use]
+    pub fn default_root() -> PathBuf {
+        std::env::var_os("AOC_INPUTS").map_or_else(
+            || Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs"),
+            PathBuf::from,
+        )
+    }
+
+    /// The store at [`InputStore::default_root`]. With the `fetch` feature and a session token
+    /// from `$AOC_SESSION` or `<root>/.session`, it fetches missing inputs from the site.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let store = InputStore::new(Self::default_root());
+        #[cfg(all(feature = "fetch", not(target_family = "wasm")))]
+        if let Some(client) = Client::from_env() {
+            return store.client(client);
+        }
+        store
+    }
+
+    #[must_use]
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("{day:02}.txt"))
+    }
+
+    /// The input of a day, fetched and saved if the store doesn't have it yet
+    pub fn get(&self, year: u16, day: u8) -> Result<String, SiteError> {
+        let path = self.path(year, day);
+        match fs::read_to_string(&path) {
+            Ok(input) => return Ok(input),
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+            Err(_) => {}
+        }
+
+        let client = self.client.as_ref().ok_or(SiteError::NoSession)?;
+        let input = client.input(year, day)?;
+
+        // Write it under another name first, so an interrupted write isn't taken as the input
+        fs::create_dir_all(path.parent().unwrap_or(&self.root))?;
+        let partial = path.with_extension("txt.partial");
+        fs::write(&partial, &input)?;
+        fs::rename(&partial, &path)?;
+        Ok(input)
+    }
+}
+
+fn session_path(root: &Path) -> PathBuf {
+    root.join(".session")
+}
+
+/// The session token from `$AOC_SESSION` or `<root>/.session`
+#[must_use]
+pub fn session(root: &Path) -> Option<String> {
+    std::env::var("AOC_SESSION")
+        .ok()
+        .or_else(|| fs::read_to_string(session_path(root)).ok())
+        .map(|session| session.trim().to_owned())
+        .filter(|session| !session.is_empty())
+}
+
+/// The year and day of a binary named like `aoc-2022-14`
+#[must_use]
+pub fn puzzle_from_binary_name(name: &str) -> Option<(u16, u8)> {
+    let (year, day) = name.strip_prefix("aoc-")?.split_once('-')?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+/// Real HTTP(S) requests
+#[cfg(all(feature = "fetch", not(target_family = "wasm")))]
+pub struct UreqTransport {
+    agent: ureq::Agent,
+}
+
+#[cfg(all(feature = "fetch", not(target_family = "wasm")))]
+impl UreqTransport {
+    #[must_use]
+    pub fn new() -> Self {
+        UreqTransport {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+}
+
+#[cfg(all(feature = "fetch", not(target_family = "wasm")))]
+impl Default for UreqTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(feature = "fetch", not(target_family = "wasm")))]
+impl Transport for UreqTransport {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> io::Result<Response> {
+        let request = headers
+            .iter()
+            .fold(self.agent.get(url), |request, (name, value)| {
+                request.set(name, value)
+            });
+        into_response(request.call())
+    }
+
+    fn post_form(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        form: &[(&str, &str)],
+    ) -> io::Result<Response> {
+        let request = headers
+            .iter()
+            .fold(self.agent.post(url), |request, (name, value)| {
+                request.set(name, value)
+            });
+        into_response(request.send_form(form))
+    }
+}
+
+#[cfg(all(feature = "fetch", not(target_family = "wasm")))]
+fn into_response(result: Result<ureq::Response, ureq::Error>) -> io::Result<Response> {
+    let response = match result {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(io::Error::other(e)),
+    };
+    Ok(Response {
+        status: response.status(),
+        body: response.into_string()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Pairs = Vec<(String, String)>;
+    /// The URL and headers of every request
+    type Requests = Rc<RefCell<Vec<(String, Pairs)>>>;
+
+    fn owned(pairs: &[(&str, &str)]) -> Pairs {
+        pairs
+            .iter()
+            .map(|&(name, value)| (name.to_owned(), value.to_owned()))
+            .collect()
+    }
+
+    /// Answers every request with the same response, and remembers the requests
+    #[derive(Clone)]
+    struct StandIn {
+        response: Response,
+        requests: Requests,
+        /// The forms that were posted
+        forms: Rc<RefCell<Vec<Pairs>>>,
+    }
+
+    impl StandIn {
+        fn new(status: u16, body: &str) -> Self {
+            StandIn {
+                response: Response {
+                    status,
+                    body: body.to_owned(),
+                },
+                requests: Rc::default(),
+                forms: Rc::default(),
+            }
+        }
+    }
+
+    impl Transport for StandIn {
+        fn get(&self, url: &str, headers: &[(&str, &str)]) -> io::Result<Response> {
+            self.requests
+                .borrow_mut()
+                .push((url.to_owned(), owned(headers)));
+            Ok(self.response.clone())
+        }
+
+        fn post_form(
+            &self,
+            url: &str,
+            headers: &[(&str, &str)],
+            form: &[(&str, &str)],
+        ) -> io::Result<Response> {
+            self.forms.borrow_mut().push(owned(form));
+            self.get(url, headers)
+        }
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc-site-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn fetches_once_and_keeps_inputs() {
+        let root = temp_root("store");
+        let site = StandIn::new(200, "1\n2\n3\n");
+        let client = Client::new(site.clone(), "secret")
+            .base_url("http://127.0.0.1:1/")
+            .min_interval(Duration::ZERO);
+        let store = InputStore::new(&root).client(client);
+
+        assert_eq!(store.get(2022, 1).unwrap(), "1\n2\n3\n");
+        assert_eq!(store.get(2022, 1).unwrap(), "1\n2\n3\n");
+        assert_eq!(
+            fs::read_to_string(root.join("2022/01.txt")).unwrap(),
+            "1\n2\n3\n"
+        );
+
+        let requests = site.requests.borrow();
+        assert_eq!(requests.len(), 1);
+        let (url, headers) = &requests[0];
+        assert_eq!(url, "http://127.0.0.1:1/2022/day/1/input");
+        assert!(headers.contains(&("Cookie".into(), "session=secret".into())));
+        assert!(headers.contains(&("User-Agent".into(), USER_AGENT.into())));
+
+        // Without a client, only what's on disk is there
+        let offline = InputStore::new(&root);
+        assert!(offline.get(2022, 1).is_ok());
+        assert!(matches!(offline.get(2022, 2), Err(SiteError::NoSession)));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn errors() {
+        let root = temp_root("errors");
+        let store = |status, body| {
+            let client = Client::new(StandIn::new(status, body), "s").min_interval(Duration::ZERO);
+            InputStore::new(&root).client(client)
+        };
+
+        let locked = store(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        assert!(matches!(
+            locked.get(2030, 1),
+            Err(SiteError::NotUnlocked { year: 2030, day: 1 })
+        ));
+        let logged_out = store(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        );
+        assert!(matches!(
+            logged_out.get(2022, 1),
+            Err(SiteError::BadSession)
+        ));
+        assert!(matches!(
+            store(502, "Bad gateway").get(2022, 1),
+            Err(SiteError::Status { status: 502, .. })
+        ));
+        // Nothing is saved for failed requests
+        assert!(!root.join("2022/01.txt").exists());
+    }
+
+    #[test]
+    fn rate_limit() {
+        let client =
+            Client::new(StandIn::new(200, ""), "s").min_interval(Duration::from_millis(30));
+        let start = Instant::now();
+        for day in 1..=3 {
+            client.input(2022, day).unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[test]
+    fn submission_responses() {
+        let article = |text: &str| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+
+        assert_eq!(
+            Outcome::parse(&article(
+                "That's the right answer!  You are <span>one gold star</span> closer."
+            )),
+            Outcome::Judged(Verdict::Right)
+        );
+        assert_eq!(
+            Outcome::parse(&article("That's not the right answer; your answer is too high.  Please wait one minute before trying again.")),
+            Outcome::Judged(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Outcome::parse(&article(
+                "That's not the right answer; your answer is too low."
+            )),
+            Outcome::Judged(Verdict::TooLow)
+        );
+        assert_eq!(
+            Outcome::parse(&article(
+                "That's not the right answer.  If you're stuck, ..."
+            )),
+            Outcome::Judged(Verdict::Wrong)
+        );
+        assert_eq!(
+            Outcome::parse(&article("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.")),
+            Outcome::Wait(Duration::from_secs(83))
+        );
+        assert_eq!(
+            Outcome::parse(&article("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2022/day/14\">[Return to Day 14]</a>")),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(
+            Outcome::parse(&article("Something <em>new</em>\n  happened.")),
+            Outcome::Unrecognised(String::from("Something new happened."))
+        );
+    }
+
+    #[test]
+    fn submits_unknown_answers_only() {
+        let part_1 = Puzzle::new(2022, 14, 1);
+        let mut db = AnswerDb::default();
+        db.record(part_1, "700", Verdict::TooHigh);
+
+        let site = StandIn::new(
+            200,
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        );
+        let client = Client::new(site.clone(), "s").min_interval(Duration::ZERO);
+
+        // Known to be too high, so it isn't sent
+        assert_eq!(
+            submit(&client, &mut db, part_1, "701").unwrap(),
+            Submission::Known(Verdict::TooHigh)
+        );
+        assert!(site.requests.borrow().is_empty());
+
+        assert_eq!(
+            submit(&client, &mut db, part_1, "600\n").unwrap(),
+            Submission::Sent(Outcome::Judged(Verdict::TooLow))
+        );
+        assert_eq!(
+            site.requests.borrow()[0].0,
+            format!("{BASE_URL}/2022/day/14/answer")
+        );
+        assert_eq!(
+            site.forms.borrow()[0],
+            [
+                ("level".into(), "1".into()),
+                ("answer".into(), "600".into())
+            ]
+        );
+        assert_eq!(db.judge(part_1, "599"), Some(Verdict::TooLow));
+        assert_eq!(
+            submit(&client, &mut db, part_1, "600").unwrap(),
+            Submission::Known(Verdict::TooLow)
+        );
+        assert_eq!(site.requests.borrow().len(), 1);
+
+        // Waiting isn't a verdict
+        let busy = StandIn::new(
+            200,
+            "<article><p>You gave an answer too recently. You have 30s left to wait.</p></article>",
+        );
+        let client = Client::new(busy, "s").min_interval(Duration::ZERO);
+        assert_eq!(
+            submit(&client, &mut db, part_1, "650").unwrap(),
+            Submission::Sent(Outcome::Wait(Duration::from_secs(30)))
+        );
+        assert_eq!(db.judge(part_1, "650"), None);
+    }
+
+    #[test]
+    fn binary_names() {
+        assert_eq!(puzzle_from_binary_name("aoc-2022-14"), Some((2022, 14)));
+        assert_eq!(puzzle_from_binary_name("aoc-2024-01"), Some((2024, 1)));
+        assert_eq!(puzzle_from_binary_name("aoc"), None);
+        assert_eq!(puzzle_from_binary_name("aoc-2022-common"), None);
+    }
+
+    /// A real HTTP server on localhost, answering one request
+    #[cfg(all(feature = "fetch", not(target_family = "wasm")))]
+    #[test]
+    fn over_http() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                request.push(line.trim().to_owned());
+            }
+            let body = "498,4 -> 498,6\n";
+            write!(
+                &stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+
+        let root = temp_root("http");
+        let client =
+            Client::new(UreqTransport::new(), "secret").base_url(format!("http://{address}"));
+        let store = InputStore::new(&root).client(client);
+        assert_eq!(store.get(2022, 14).unwrap(), "498,4 -> 498,6\n");
+
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2022/day/14/input HTTP/1.1");
+        assert!(request.iter().any(|h| h == "Cookie: session=secret"));
+        assert!(request
+            .iter()
+            .any(|h| h == &format!("User-Agent: {USER_AGENT}")));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,103 @@
+//! Tiles: enums that are parsed from and shown as a single character.
+//!
+//! `#[derive(Tile)]` generates `TryFrom<char>`, `From<Self> for char` and `Display`,
+//! so a tile enum works with [`Grid::parse_tiles`](crate::grid::Grid::parse_tiles).
+//!
+//! ```ignore
+//! #[derive(Tile)]
+//! enum Tile {
+//!     #[tile('#')]
+//!     Wall,
+//!     #[tile('.')]
+//!     Empty,
+//!     #[tile(delegate)]
+//!     Pipe(Pipe),
+//! }
+//! ```
+
+use core::fmt;
+
+pub use aoc_derive::Tile;
+
+/// The error of a derived `TryFrom<char>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownTile {
+    /// The name of the tile type
+    pub tile: &'static str,
+    pub found: char,
+    /// The characters (and delegated types) that would have been valid
+    pub expected: &'static str,
+}
+
+impl fmt::Display for UnknownTile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} isn't a {}, expected {}",
+            self.found, self.tile, self.expected
+        )
+    }
+}
+
+impl core::error::Error for UnknownTile {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::point::Point2;
+
+    #[derive(Tile, Debug, Clone, Copy, PartialEq, Eq)]
+    enum Pipe {
+        #[tile('|')]
+        Vertical,
+        #[tile('-')]
+        Horizontal,
+    }
+
+    #[derive(Tile, Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        #[tile('#')]
+        Wall,
+        #[tile('.', ' ')]
+        Empty,
+        #[tile(delegate)]
+        Pipe(Pipe),
+    }
+
+    #[test]
+    fn round_trips() {
+        for c in ['#', '.', '|', '-'] {
+            let tile = Tile::try_from(c).unwrap();
+            assert_eq!(char::from(tile), c);
+            assert_eq!(tile.to_string(), c.to_string());
+        }
+        assert_eq!(Tile::try_from('|'), Ok(Tile::Pipe(Pipe::Vertical)));
+        assert_eq!(Tile::try_from(' '), Ok(Tile::Empty));
+        assert_eq!(char::from(Tile::Empty), '.');
+    }
+
+    #[test]
+    fn unknown_characters() {
+        let error = Tile::try_from('@').unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "'@' isn't a Tile, expected '#', '.', ' ' or a Pipe"
+        );
+
+        let error = Grid::<Tile>::parse_tiles("#.#\n#x#\n").unwrap_err();
+        assert_eq!(error.position, Point2::new(1, 1));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: 'x' isn't a Tile, expected '#', '.', ' ' or a Pipe"
+        );
+    }
+
+    #[test]
+    fn grids_of_tiles() {
+        let input = "#-#\n.|.\n";
+        let grid = Grid::<Tile>::parse_tiles(input).unwrap();
+        assert_eq!(grid[Point2::new(1, 0)], Tile::Pipe(Pipe::Horizontal));
+        assert_eq!(grid.to_string(), input);
+    }
+}
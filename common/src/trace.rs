@@ -0,0 +1,167 @@
+//! Logging what solutions are up to, with `tracing`.
+//!
+//! Solutions put their phases in `parse`, `part_1` and `part_2` spans, and log the state of
+//! their simulations at debug and trace level. Nothing is shown unless the command line asks
+//! for it: `-v` shows the phases and how long they took, `-vv` adds debug events and `-vvv`
+//! trace events. `--log-json` logs a JSON object per line instead. Logs go to standard error,
+//! so the answers on standard output stay as they are.
+//!
+//! The year crates' `challenge_input` calls [`init`], so spans have to be entered after the
+//! input is read to be shown.
+
+/// The logging flags on a command line
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Verbosity {
+    /// How many `v`s were given
+    pub level: u8,
+    pub json: bool,
+}
+
+impl Verbosity {
+    #[must_use]
+    pub fn from_args<S: AsRef<str>>(args: impl IntoIterator<Item = S>) -> Self {
+        args.into_iter()
+            .fold(Verbosity::default(), |mut verbosity, arg| {
+                let arg = arg.as_ref();
+                if arg == "--log-json" {
+                    verbosity.json = true;
+                } else if let Some(count) = vs(arg) {
+                    verbosity.level = verbosity.level.saturating_add(count);
+                }
+                verbosity
+            })
+    }
+
+    /// Whether `arg` is one of the flags, which aren't for the solution itself
+    #[must_use]
+    pub fn is_flag(arg: &str) -> bool {
+        arg == "--log-json" || vs(arg).is_some()
+    }
+}
+
+/// How many `v`s there are in `-v`, `-vv`, …
+fn vs(arg: &str) -> Option<u8> {
+    let vs = arg.strip_prefix('-')?;
+    (!vs.is_empty() && vs.bytes().all(|b| b == b'v'))
+        .then(|| vs.len().try_into().unwrap_or(u8::MAX))
+}
+
+#[cfg(feature = "trace")]
+mod subscriber {
+    use std::io::{self, IsTerminal};
+
+    use tracing::level_filters::LevelFilter;
+    use tracing::Subscriber;
+    use tracing_subscriber::fmt::format::FmtSpan;
+    use tracing_subscriber::fmt::MakeWriter;
+
+    use super::Verbosity;
+
+    impl Verbosity {
+        /// The most detailed events shown: warnings and errors only without any `v`s
+        #[must_use]
+        pub fn filter(self) -> LevelFilter {
+            match self.level {
+                0 => LevelFilter::WARN,
+                1 => LevelFilter::INFO,
+                2 => LevelFilter::DEBUG,
+                _ => LevelFilter::TRACE,
+            }
+        }
+    }
+
+    /// A subscriber writing to `writer` as `verbosity` asks, with a line for every span that
+    /// closes, with its timings, once there's a `v`
+    pub fn subscriber<W>(verbosity: Verbosity, writer: W) -> Box<dyn Subscriber + Send + Sync>
+    where
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    {
+        let spans = if verbosity.level > 0 {
+            FmtSpan::CLOSE
+        } else {
+            FmtSpan::NONE
+        };
+        let builder = tracing_subscriber::fmt()
+            .with_writer(writer)
+            .with_max_level(verbosity.filter())
+            .with_span_events(spans);
+        if verbosity.json {
+            Box::new(builder.json().finish())
+        } else {
+            Box::new(builder.with_ansi(io::stderr().is_terminal()).finish())
+        }
+    }
+
+    /// Logs to standard error as the command line asks. Only the first call does anything.
+    pub fn init() {
+        let verbosity = Verbosity::from_args(std::env::args().skip(1));
+        let _ = tracing::subscriber::set_global_default(subscriber(verbosity, io::stderr));
+    }
+}
+
+#[cfg(feature = "trace")]
+pub use subscriber::{init, subscriber};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags() {
+        assert_eq!(Verbosity::from_args(["input/1.txt"]), Verbosity::default());
+        assert_eq!(
+            Verbosity::from_args(["-v", "input/1.txt", "-vv", "--log-json"]),
+            Verbosity {
+                level: 3,
+                json: true
+            }
+        );
+        assert!(Verbosity::is_flag("-vvv"));
+        assert!(!Verbosity::is_flag("-"));
+        assert!(!Verbosity::is_flag("-x"));
+        assert!(!Verbosity::is_flag("--animate"));
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn json_lines() {
+        use std::io;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+        impl io::Write for Buffer {
+            fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(bytes)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let logs = Buffer::default();
+        let writer = logs.clone();
+        let verbosity = Verbosity {
+            level: 2,
+            json: true,
+        };
+        tracing::subscriber::with_default(subscriber(verbosity, move || writer.clone()), || {
+            tracing::info_span!("part_1").in_scope(|| {
+                tracing::debug!(round = 1, "moved");
+                tracing::trace!("not shown");
+            });
+        });
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = logs.lines().collect();
+        assert_eq!(lines.len(), 2, "{logs}");
+        assert!(lines[0].contains(r#""level":"DEBUG""#));
+        assert!(lines[0].contains(r#""round":1"#));
+        assert!(lines[0].contains(r#""name":"part_1""#));
+        assert!(lines[1].contains(r#""message":"close""#));
+        assert!(lines[1].contains(r#""span":{"name":"part_1"}"#));
+        assert!(lines[1].contains("time.busy"));
+    }
+}
@@ -0,0 +1,188 @@
+//! Derive macros for `aoc-common`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitChar, Token, Variant};
+
+/// Derives `TryFrom<char>`, `From<Self> for char` and `Display` for an enum of grid tiles.
+///
+/// Unit variants get their character with `#[tile('#')]`. Extra characters that should parse
+/// into the same variant can follow, like `#[tile('.', 'O', 'I')]`; it's shown as the first one.
+/// A variant with a single field can be `#[tile(delegate)]`: any character the field type
+/// parses from (after the unit variants were tried) becomes that variant, and the field
+/// decides how it's displayed. The field type needs `TryFrom<char>`, `Display` and `char: From`,
+/// so it can be another `Tile` or just `char`.
+///
+/// ```ignore
+/// #[derive(Tile)]
+/// enum Square {
+///     #[tile('#')]
+///     Tree,
+///     #[tile('.')]
+///     Open,
+/// }
+/// ```
+#[proc_macro_derive(Tile, attributes(tile))]
+pub fn derive_tile(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    tile(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum TileKind {
+    Chars(Vec<LitChar>),
+    Delegate(Box<syn::Type>),
+}
+
+fn tile_kind(variant: &Variant) -> syn::Result<TileKind> {
+    let name = &variant.ident;
+    let attribute = variant
+        .attrs
+        .iter()
+        .find(|a| a.path().is_ident("tile"))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                variant,
+                format!("{name} needs #[tile('c')] or #[tile(delegate)]"),
+            )
+        })?;
+
+    if let Ok(chars) = attribute.parse_args_with(Punctuated::<LitChar, Token![,]>::parse_terminated)
+    {
+        return match &variant.fields {
+            Fields::Unit => Ok(TileKind::Chars(chars.into_iter().collect())),
+            _ => Err(syn::Error::new_spanned(
+                &variant.fields,
+                format!(
+                    "{name} has fields, so it can't be a single character. Try #[tile(delegate)]"
+                ),
+            )),
+        };
+    }
+
+    let ident: syn::Ident = attribute.parse_args().map_err(|_| {
+        syn::Error::new_spanned(attribute, "expected #[tile('c')] or #[tile(delegate)]")
+    })?;
+    if ident != "delegate" {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "expected a character or `delegate`",
+        ));
+    }
+
+    match &variant.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            Ok(TileKind::Delegate(Box::new(fields.unnamed[0].ty.clone())))
+        }
+        _ => Err(syn::Error::new_spanned(
+            variant,
+            format!("#[tile(delegate)] needs {name} to have exactly one unnamed field"),
+        )),
+    }
+}
+
+fn tile(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "Tile can only be derived for enums",
+        ));
+    };
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut chars: Vec<(char, &syn::Ident)> = Vec::new();
+    let mut parse_chars = Vec::new();
+    let mut parse_delegates = Vec::new();
+    let mut to_char = Vec::new();
+    let mut display = Vec::new();
+    let mut expected = Vec::new();
+
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        match tile_kind(variant)? {
+            TileKind::Chars(cs) => {
+                for c in &cs {
+                    if let Some((_, other)) = chars.iter().find(|(used, _)| *used == c.value()) {
+                        return Err(syn::Error::new_spanned(
+                            c,
+                            format!("{c:?} is already used by {other}", c = c.value()),
+                        ));
+                    }
+                    chars.push((c.value(), ident));
+                    expected.push(format!("{:?}", c.value()));
+                }
+
+                let shown = cs.first().ok_or_else(|| {
+                    syn::Error::new_spanned(variant, format!("{ident} needs a character"))
+                })?;
+                parse_chars.push(quote! { #(#cs)|* => ::core::result::Result::Ok(Self::#ident), });
+                to_char.push(quote! { #name::#ident => #shown, });
+                display.push(quote! { Self::#ident => ::core::fmt::Write::write_char(f, #shown), });
+            }
+            TileKind::Delegate(ty) => {
+                let type_name = quote!(#ty).to_string();
+                expected.push(format!("a {type_name}"));
+
+                parse_delegates.push(quote! {
+                    if let ::core::result::Result::Ok(inner) = <#ty as ::core::convert::TryFrom<char>>::try_from(c) {
+                        return ::core::result::Result::Ok(Self::#ident(inner));
+                    }
+                });
+                to_char.push(quote! { #name::#ident(inner) => char::from(inner), });
+                display
+                    .push(quote! { Self::#ident(inner) => ::core::fmt::Display::fmt(inner, f), });
+            }
+        }
+    }
+
+    let expected = match expected.split_last() {
+        None => String::from("nothing"),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+    };
+    let tile_name = name.to_string();
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::TryFrom<char> for #name #type_generics #where_clause {
+            type Error = ::aoc_common::tile::UnknownTile;
+
+            fn try_from(c: char) -> ::core::result::Result<Self, Self::Error> {
+                #[allow(clippy::match_single_binding)]
+                let unit: ::core::result::Result<Self, ()> = match c {
+                    #(#parse_chars)*
+                    _ => ::core::result::Result::Err(()),
+                };
+                if let ::core::result::Result::Ok(tile) = unit {
+                    return ::core::result::Result::Ok(tile);
+                }
+                #(#parse_delegates)*
+                ::core::result::Result::Err(::aoc_common::tile::UnknownTile {
+                    tile: #tile_name,
+                    found: c,
+                    expected: #expected,
+                })
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #type_generics> for char #where_clause {
+            fn from(tile: #name #type_generics) -> char {
+                match tile {
+                    #(#to_char)*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #name #type_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #(#display)*
+                }
+            }
+        }
+    })
+}
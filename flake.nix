@@ -57,13 +57,21 @@
 
             rust-stable = inputs'.rust-overlay.packages.rust.override {
               extensions = [ "rust-src" "rust-analyzer" "clippy" ];
+              targets = [ "wasm32-wasip1" ];
             };
           # TODO: generate for each year
           } // (builtins.listToAttrs (map (day: {
             name = "2022-${day}";
             value = let
               build = crane.stable.buildPackage {
-                src = ./2022;
+                # the whole repo, so the shared ./common crate is available
+                src = ./.;
+                postUnpack = ''
+                  cd $sourceRoot/2022
+                  sourceRoot="."
+                '';
+                cargoLock = ./2022/Cargo.lock;
+                cargoToml = ./2022/Cargo.toml;
                 cargoBuildCommand = "cargo build --release -p aoc-2022-${day}";
               };
             in pkgs.writeShellApplication {
@@ -78,7 +86,13 @@
             name = "2023-${day}";
             value = let
               build = let pname = "aoc-2023-${day}"; in crane.stable.buildPackage {
-                src = ./2023;
+                src = ./.;
+                postUnpack = ''
+                  cd $sourceRoot/2023
+                  sourceRoot="."
+                '';
+                cargoLock = ./2023/Cargo.lock;
+                cargoToml = ./2023/Cargo.toml;
                 cargoBuildCommand = "cargo build --release -p ${pname}";
                 version = "0.1.0";
                 inherit pname;
@@ -95,7 +109,13 @@
             name = "2024-${day}";
             value = let
               build = let pname = "aoc-2024-${day}"; in crane.stable.buildPackage {
-                src = ./2024;
+                src = ./.;
+                postUnpack = ''
+                  cd $sourceRoot/2024
+                  sourceRoot="."
+                '';
+                cargoLock = ./2024/Cargo.lock;
+                cargoToml = ./2024/Cargo.toml;
                 cargoBuildCommand = "cargo build --release -p ${pname}";
                 version = "0.1.0";
                 inherit pname;
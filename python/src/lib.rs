@@ -0,0 +1,265 @@
+//! `aoc`, the solutions from Python. `solve` runs a day's release build on an input, as
+//! `aoc-serve` does, and the classes are the parsed inputs of some of the days:
+//!
+//! ```python
+//! import aoc
+//!
+//! aoc.solve(2022, 15, open("2022/15/input/1.txt").read())
+//! # {'year': 2022, 'day': 15, 'answers': ['5125700', '11379394658764'], 'runtime_ms': 940.7}
+//! sensors = aoc.sensors(open("2022/15/input/example.txt").read())
+//! max(sensor.radius() for sensor in sensors)
+//! ```
+
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use pyo3::exceptions::{PyOverflowError, PyRuntimeError, PyTimeoutError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use aoc_common::interval::{Interval, IntervalSet};
+use aoc_common::parsers::parse_all;
+use aoc_tools::registry::{self, repository, Language, Solution};
+
+/// The days built since the module was imported, so only the first `solve` of a day waits
+/// for cargo
+static BUILT: Mutex<BTreeSet<(u16, u8)>> = Mutex::new(BTreeSet::new());
+
+/// The Rust days from 2022 on. The 2020 days read their own input, so they can't be given one.
+fn rust_solutions() -> Vec<Solution> {
+    let root = repository();
+    registry::years(&root)
+        .into_iter()
+        .filter(|&year| year > 2020)
+        .flat_map(|year| registry::solutions(&root, year))
+        .filter(|s| s.language == Language::Rust)
+        .collect()
+}
+
+/// The days `solve` can solve, as `(year, day)` pairs, oldest first
+#[pyfunction]
+fn solutions() -> Vec<(u16, u8)> {
+    let mut days: Vec<_> = rust_solutions().iter().map(|s| (s.year, s.day)).collect();
+    days.sort_unstable();
+    days
+}
+
+/// Solves the day on `input`, building it first if it hasn't been yet. Gives
+/// `{"year", "day", "answers", "runtime_ms"}`, with the lines the solution printed as answers.
+///
+/// Raises `ValueError` for days without a solution, `TimeoutError` when it runs longer than
+/// `timeout` seconds and `RuntimeError` when it doesn't build or fails.
+#[pyfunction]
+#[pyo3(signature = (year, day, input, timeout = 30.0))]
+fn solve<'py>(
+    py: Python<'py>,
+    year: u16,
+    day: u8,
+    input: &str,
+    timeout: f64,
+) -> PyResult<Bound<'py, PyDict>> {
+    let solution = rust_solutions()
+        .into_iter()
+        .find(|s| (s.year, s.day) == (year, day))
+        .ok_or_else(|| PyValueError::new_err(format!("no solution for {year} day {day}")))?;
+    let timeout = Duration::try_from_secs_f64(timeout)
+        .map_err(|e| PyValueError::new_err(format!("not a timeout: {e}")))?;
+
+    let run = py
+        .allow_threads(|| {
+            {
+                let mut built = BUILT.lock().unwrap_or_else(|e| e.into_inner());
+                if !built.contains(&(year, day)) {
+                    solution.build()?;
+                    built.insert((year, day));
+                }
+            }
+            solution.run_on(input.as_bytes(), timeout)
+        })
+        .map_err(PyRuntimeError::new_err)?;
+    let run = match run {
+        Some(run) if run.success => run,
+        Some(run) => return Err(PyRuntimeError::new_err(run.output)),
+        None => return Err(PyTimeoutError::new_err(format!("took over {timeout:?}"))),
+    };
+
+    let solved = PyDict::new(py);
+    solved.set_item("year", year)?;
+    solved.set_item("day", day)?;
+    solved.set_item("answers", run.answers())?;
+    solved.set_item("runtime_ms", run.runtime.as_secs_f64() * 1000.0)?;
+    Ok(solved)
+}
+
+/// A sensor of 2022 day 15, and the beacon closest to it
+#[pyclass(frozen)]
+#[derive(Clone)]
+struct Sensor(aoc_2022_15::Sensor);
+
+#[pymethods]
+impl Sensor {
+    /// `(x, y)`
+    #[getter]
+    fn position(&self) -> (i64, i64) {
+        (self.0.position.x, self.0.position.y)
+    }
+
+    /// `(x, y)` of the closest beacon
+    #[getter]
+    fn beacon(&self) -> (i64, i64) {
+        (self.0.beacon.x, self.0.beacon.y)
+    }
+
+    /// The Manhattan distance to the beacon, so how far it sees
+    fn radius(&self) -> i64 {
+        self.0.radius()
+    }
+
+    /// The `(start, end)` it sees of row `y`, inclusive, or `None`
+    fn intersection(&self, y: i64) -> Option<(i64, i64)> {
+        self.0.intersection(y).map(|i| (i.start, i.end))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Sensor(position={:?}, beacon={:?})",
+            self.position(),
+            self.beacon()
+        )
+    }
+}
+
+/// Parses the sensors of 2022 day 15
+#[pyfunction]
+fn sensors(input: &str) -> PyResult<Vec<Sensor>> {
+    let sensors = aoc_2022_15::parse(input).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(sensors.into_iter().map(Sensor).collect())
+}
+
+fn unwrap(sensors: Vec<Sensor>) -> Vec<aoc_2022_15::Sensor> {
+    sensors.into_iter().map(|s| s.0).collect()
+}
+
+/// How many positions on row `y` can't have a beacon, part 1 of 2022 day 15
+#[pyfunction]
+fn no_beacon_positions(sensors: Vec<Sensor>, y: i64) -> i64 {
+    aoc_2022_15::part_1(&unwrap(sensors), y)
+}
+
+/// The tuning frequency of the only position in `0..=limit` that no sensor sees, part 2 of
+/// 2022 day 15
+#[pyfunction]
+fn tuning_frequency(py: Python<'_>, sensors: Vec<Sensor>, limit: i64) -> Option<i64> {
+    let sensors = unwrap(sensors);
+    py.allow_threads(|| aoc_2022_15::part_2(&sensors, limit))
+}
+
+/// One of the maps of a 2023 day 5 almanac, like `seed-to-soil`
+#[pyclass(frozen)]
+#[derive(Clone)]
+struct Map(aoc_2023_05::Map);
+
+#[pymethods]
+impl Map {
+    #[getter]
+    fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// `(destination start, source start, length)`, like the lines of the almanac, sorted by
+    /// source
+    #[getter]
+    fn ranges(&self) -> Vec<(u64, u64, u64)> {
+        self.0
+            .ranges
+            .pieces()
+            .iter()
+            .map(|&(source, destination)| {
+                let len = source
+                    .len()
+                    .expect("the ranges come from lengths in the almanac");
+                (destination, source.start, len)
+            })
+            .collect()
+    }
+
+    /// Where `x` goes. Numbers outside of the ranges stay where they are.
+    fn map(&self, x: u64) -> u64 {
+        self.0.ranges.map(x)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Map(name={:?}, ranges={:?})", self.0.name, self.ranges())
+    }
+}
+
+/// The almanac of 2023 day 5: the seeds and the maps they go through
+#[pyclass(frozen)]
+struct Almanac(aoc_2023_05::Almanac);
+
+#[pymethods]
+impl Almanac {
+    #[getter]
+    fn seeds(&self) -> Vec<u64> {
+        self.0.seeds.clone()
+    }
+
+    /// In the order they're applied in
+    #[getter]
+    fn maps(&self) -> Vec<Map> {
+        self.0.maps.iter().cloned().map(Map).collect()
+    }
+
+    /// Where `seed` ends up after all the maps
+    fn destination(&self, seed: u64) -> u64 {
+        self.0.destination(seed)
+    }
+
+    /// Where the `(start, length)` ranges of seeds end up after all the maps, as
+    /// `(start, length)` ranges. Raises `OverflowError` when they join up into all of the
+    /// 2^64 numbers, whose length doesn't fit.
+    fn destinations(&self, seeds: Vec<(u64, u64)>) -> PyResult<Vec<(u64, u64)>> {
+        let seeds: IntervalSet<u64> = seeds
+            .into_iter()
+            .filter_map(|(start, len)| Interval::with_len(start, len))
+            .collect();
+        self.0
+            .destinations(&seeds)
+            .iter()
+            .map(|i| {
+                let len = i
+                    .len()
+                    .ok_or_else(|| PyOverflowError::new_err("2^64 destinations"))?;
+                Ok((i.start, len))
+            })
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        let names: Vec<&str> = self.0.maps.iter().map(|m| m.name.as_str()).collect();
+        format!("Almanac(seeds={:?}, maps={names:?})", self.0.seeds)
+    }
+}
+
+/// Parses the almanac of 2023 day 5
+#[pyfunction]
+fn almanac(input: &str) -> PyResult<Almanac> {
+    parse_all(aoc_2023_05::Almanac::parse, input)
+        .map(Almanac)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+#[pymodule]
+fn aoc(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(solutions, module)?)?;
+    module.add_function(wrap_pyfunction!(solve, module)?)?;
+    module.add_class::<Sensor>()?;
+    module.add_function(wrap_pyfunction!(sensors, module)?)?;
+    module.add_function(wrap_pyfunction!(no_beacon_positions, module)?)?;
+    module.add_function(wrap_pyfunction!(tuning_frequency, module)?)?;
+    module.add_class::<Map>()?;
+    module.add_class::<Almanac>()?;
+    module.add_function(wrap_pyfunction!(almanac, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,89 @@
+"""The Python module against the examples of the days it has classes for."""
+
+import unittest
+from pathlib import Path
+
+import aoc
+
+ROOT = Path(__file__).resolve().parents[2]
+
+
+def example(year, day):
+    return (ROOT / str(year) / f"{day:02}" / "input" / "example.txt").read_text()
+
+
+class SolveTest(unittest.TestCase):
+    def test_solve(self):
+        solved = aoc.solve(2023, 5, example(2023, 5))
+        self.assertEqual(solved["year"], 2023)
+        self.assertEqual(solved["day"], 5)
+        self.assertEqual(solved["answers"], ["35", "46"])
+        self.assertGreater(solved["runtime_ms"], 0)
+
+    def test_solutions(self):
+        solutions = aoc.solutions()
+        self.assertIn((2022, 15), solutions)
+        self.assertIn((2023, 5), solutions)
+        self.assertEqual(solutions, sorted(solutions))
+
+    def test_unknown_day(self):
+        with self.assertRaises(ValueError):
+            aoc.solve(2022, 25, "")
+
+    def test_failing_input(self):
+        with self.assertRaises(RuntimeError):
+            aoc.solve(2023, 5, "not an almanac")
+
+
+class SensorsTest(unittest.TestCase):
+    def setUp(self):
+        self.sensors = aoc.sensors(example(2022, 15))
+
+    def test_parse(self):
+        self.assertEqual(len(self.sensors), 14)
+        first = self.sensors[0]
+        self.assertEqual(first.position, (2, 18))
+        self.assertEqual(first.beacon, (-2, 15))
+        self.assertEqual(first.radius(), 7)
+        self.assertEqual(first.intersection(18), (-5, 9))
+        self.assertIsNone(first.intersection(26))
+
+    def test_parts(self):
+        self.assertEqual(aoc.no_beacon_positions(self.sensors, 10), 26)
+        self.assertEqual(aoc.tuning_frequency(self.sensors, 20), 56000011)
+
+    def test_invalid(self):
+        with self.assertRaises(ValueError):
+            aoc.sensors("Sensor at x=2")
+
+
+class AlmanacTest(unittest.TestCase):
+    def setUp(self):
+        self.almanac = aoc.almanac(example(2023, 5))
+
+    def test_parse(self):
+        self.assertEqual(self.almanac.seeds, [79, 14, 55, 13])
+        names = [map.name for map in self.almanac.maps]
+        self.assertEqual(names[0], "seed-to-soil")
+        self.assertEqual(names[-1], "humidity-to-location")
+        self.assertEqual(self.almanac.maps[0].ranges, [(52, 50, 48), (50, 98, 2)])
+        self.assertEqual(self.almanac.maps[0].map(79), 81)
+        self.assertEqual(self.almanac.maps[0].map(10), 10)
+
+    def test_parts(self):
+        seeds = self.almanac.seeds
+        self.assertEqual(min(self.almanac.destination(seed) for seed in seeds), 35)
+        ranges = list(zip(seeds[::2], seeds[1::2]))
+        self.assertEqual(min(start for start, _ in self.almanac.destinations(ranges)), 46)
+
+    def test_every_seed(self):
+        with self.assertRaises(OverflowError):
+            self.almanac.destinations([(0, 2**63), (2**63, 2**63)])
+
+    def test_invalid(self):
+        with self.assertRaises(ValueError):
+            aoc.almanac("seeds: 1 2")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,253 @@
+//! Running a day on a directory of inputs, like everyone's on the team, to find the inputs it
+//! gets wrong or panics on.
+//!
+//! Every `*.txt` in the directory is an input, with its recorded answers in the `*.answers`
+//! sidecar next to it, like the examples in a day's `input/`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc_common::input::Answers;
+use aoc_tools::registry::Solution;
+
+/// What a run on one input gave
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The lines it printed: part 1's answer first and part 2's last, with whatever it drew
+    /// in between
+    Answered(Vec<String>),
+    /// The panic message, with where it panicked
+    Panicked(String),
+    /// It exited with a failure, with the last thing it printed
+    Failed(String),
+    TimedOut,
+}
+
+/// A run on one input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// The input's file name
+    pub input: String,
+    pub recorded: Answers,
+    pub outcome: Outcome,
+    pub runtime: Option<Duration>,
+}
+
+impl Row {
+    /// The answer it gave for part 1 or 2
+    fn answer(&self, part: u8) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Answered(answers) if part == 1 => answers.first(),
+            Outcome::Answered(answers) if answers.len() > 1 => answers.last(),
+            _ => None,
+        }
+        .map(String::as_str)
+    }
+
+    /// Whether the answer for `part` isn't the recorded one, when one is recorded
+    fn disagrees(&self, part: u8) -> bool {
+        self.recorded
+            .part(part)
+            .is_some_and(|recorded| self.answer(part) != Some(recorded))
+    }
+
+    /// Whether it didn't answer, or disagrees with a recorded answer
+    pub fn flagged(&self) -> bool {
+        !matches!(self.outcome, Outcome::Answered(_)) || [1, 2].iter().any(|&p| self.disagrees(p))
+    }
+
+    /// `35`, `35 ✓` when that's the recorded answer, or `36 ✗ 35` when 35 is
+    fn cell(&self, part: u8) -> String {
+        let answer = self.answer(part).unwrap_or("–");
+        match self.recorded.part(part) {
+            None => answer.to_owned(),
+            Some(_) if !self.disagrees(part) => format!("{answer} ✓"),
+            Some(recorded) => format!("{answer} ✗ {recorded}"),
+        }
+    }
+
+    fn status(&self) -> String {
+        match &self.outcome {
+            Outcome::Answered(_) if self.flagged() => String::from("wrong"),
+            Outcome::Answered(_) => String::new(),
+            Outcome::Panicked(message) => format!("panicked {message}"),
+            Outcome::Failed(message) => format!("failed: {message}"),
+            Outcome::TimedOut => String::from("timed out"),
+        }
+    }
+}
+
+/// The `*.txt` files in `dir`, by name
+pub fn inputs(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("couldn't read {}: {e}", dir.display()))?;
+    let mut inputs: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// Runs the built `solution` on `input`, killing it after `timeout`
+pub fn run(
+    solution: &Solution,
+    input: &Path,
+    recorded: Answers,
+    timeout: Duration,
+) -> Result<Row, String> {
+    let bytes = fs::read(input).map_err(|e| format!("couldn't read {}: {e}", input.display()))?;
+    let run = solution.run_on(&bytes, timeout)?;
+    let outcome = match &run {
+        Some(run) if run.success => Outcome::Answered(run.answers()),
+        Some(run) => match panic_message(&run.output) {
+            Some(message) => Outcome::Panicked(message),
+            None => Outcome::Failed(run.answers().pop().unwrap_or_default()),
+        },
+        None => Outcome::TimedOut,
+    };
+    Ok(Row {
+        input: input
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned()),
+        recorded,
+        outcome,
+        runtime: run.filter(|run| run.success).map(|run| run.runtime),
+    })
+}
+
+/// `at src/main.rs:10:5: the message` from what a panicking Rust program printed
+fn panic_message(output: &str) -> Option<String> {
+    let mut lines = output.lines();
+    let line = lines.find(|line| line.contains("panicked at "))?;
+    let (_, at) = line.split_once("panicked at ")?;
+    // Since Rust 1.73 the message is on the next line, and the location ends with a colon
+    Some(match at.strip_suffix(':') {
+        Some(location) => format!("at {location}: {}", lines.next().unwrap_or_default()),
+        None => format!("at {at}"),
+    })
+}
+
+/// The answers and runtimes of every input, a row each, aligned
+pub fn matrix(rows: &[Row]) -> String {
+    let mut cells = vec![["input", "part 1", "part 2", "runtime", ""].map(String::from)];
+    for row in rows {
+        cells.push([
+            row.input.clone(),
+            row.cell(1),
+            row.cell(2),
+            row.runtime
+                .map_or_else(|| String::from("–"), |runtime| format!("{runtime:.1?}")),
+            row.status(),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..5)
+        .map(|column| {
+            cells
+                .iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let mut matrix = String::new();
+    for row in &cells {
+        let line = format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {:>w3$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        );
+        matrix.push_str(line.trim_end());
+        matrix.push('\n');
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(input: &str, recorded: [Option<&str>; 2], outcome: Outcome) -> Row {
+        Row {
+            input: input.to_owned(),
+            recorded: Answers {
+                part_1: recorded[0].map(String::from),
+                part_2: recorded[1].map(String::from),
+            },
+            runtime: matches!(outcome, Outcome::Answered(_)).then(|| Duration::from_millis(12)),
+            outcome,
+        }
+    }
+
+    fn answered(answers: &[&str]) -> Outcome {
+        Outcome::Answered(answers.iter().map(|&a| String::from(a)).collect())
+    }
+
+    #[test]
+    fn flags_panics_and_disagreements() {
+        let rows = [
+            row("1.txt", [Some("35"), Some("46")], answered(&["35", "46"])),
+            row(
+                "alice.txt",
+                [None, None],
+                answered(&["7", "┏━┓", "┗━┛", "8"]),
+            ),
+            row("bob.txt", [Some("35"), Some("47")], answered(&["35", "46"])),
+            row("carol.txt", [Some("1"), None], answered(&["1"])),
+            row(
+                "example.txt",
+                [Some("4"), None],
+                Outcome::Panicked(String::from("at src/main.rs:1:2: no start")),
+            ),
+            row("slow.txt", [None, None], Outcome::TimedOut),
+        ];
+        let flagged: Vec<bool> = rows.iter().map(Row::flagged).collect();
+        assert_eq!(flagged, [false, false, true, false, true, true]);
+
+        assert_eq!(
+            matrix(&rows),
+            "\
+input        part 1  part 2   runtime
+1.txt        35 ✓    46 ✓      12.0ms
+alice.txt    7       8         12.0ms
+bob.txt      35 ✓    46 ✗ 47   12.0ms  wrong
+carol.txt    1 ✓     –         12.0ms
+example.txt  – ✗ 4   –              –  panicked at src/main.rs:1:2: no start
+slow.txt     –       –              –  timed out
+"
+        );
+    }
+
+    #[test]
+    fn panic_messages() {
+        assert_eq!(
+            panic_message("thread 'main' panicked at src/main.rs:45:14:\nno start\nnote: …"),
+            Some(String::from("at src/main.rs:45:14: no start"))
+        );
+        assert_eq!(
+            panic_message("thread 'main' panicked at 'no start', src/main.rs:45:14"),
+            Some(String::from("at 'no start', src/main.rs:45:14"))
+        );
+        assert_eq!(panic_message("error: no input"), None);
+    }
+
+    #[test]
+    fn finds_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-compare-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["bob.txt", "alice.txt", "alice.answers", "notes.md"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let found = inputs(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(found, Ok(vec![dir.join("alice.txt"), dir.join("bob.txt")]));
+    }
+}
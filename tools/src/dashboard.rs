@@ -0,0 +1,475 @@
+//! `aoc dashboard`: every year and day in one place, to run solutions and look at what they
+//! print.
+//!
+//! | key          | action                                                     |
+//! |--------------|------------------------------------------------------------|
+//! | `←` `→`      | previous / next year                                       |
+//! | `↑` `↓`      | previous / next day                                        |
+//! | `r`          | run the day, and check its answers against the recorded ones |
+//! | `1`, `2`     | run the day, and check that part                           |
+//! | `v`          | show the visualisation: the last frame of `--animate`, or what the day logs with `-vv` |
+//! | `PgUp` `PgDn`| scroll the output                                          |
+//! | `q`, `esc`   | quit                                                       |
+//!
+//! Days print both parts in one run, so running a part runs the whole day. Solutions are
+//! built in release mode first, in a background thread, so the list stays responsive.
+
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::answers::AnswerDb;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Tabs};
+use ratatui::{DefaultTerminal, Frame};
+
+use aoc_tools::registry::{self, Benchmarks, Language, Run, Solution};
+
+/// What to do with a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Job {
+    /// Run it, and check one part or both
+    Run(Option<u8>),
+    Visualise,
+}
+
+/// A day in the list
+pub struct Day {
+    pub solution: Solution,
+    /// The accepted answers for part 1 and 2
+    pub answers: [Option<String>; 2],
+    pub benchmark: Option<Duration>,
+    pub last: Option<(Job, Result<Run, String>)>,
+}
+
+impl Day {
+    /// How a part's answer compares to the last run: `None` when there's nothing to compare
+    pub fn verdict(&self, part: u8) -> Option<bool> {
+        let answer = self.answers[usize::from(part - 1)].as_ref()?;
+        match &self.last {
+            Some((Job::Run(_), Ok(run))) => Some(run.success && run.gave(answer)),
+            _ => None,
+        }
+    }
+
+    fn stars(&self) -> String {
+        self.answers
+            .iter()
+            .map(|answer| if answer.is_some() { '★' } else { '☆' })
+            .collect()
+    }
+}
+
+pub struct Year {
+    pub year: u16,
+    pub days: Vec<Day>,
+}
+
+pub struct App {
+    pub years: Vec<Year>,
+    pub year: usize,
+    pub list: ListState,
+    pub scroll: u16,
+    /// The year, day and job that's running
+    pub running: Option<(usize, usize, Job)>,
+    pub quit: bool,
+}
+
+impl App {
+    #[must_use]
+    pub fn new(years: Vec<Year>) -> Self {
+        App {
+            years,
+            year: 0,
+            list: ListState::default().with_selected(Some(0)),
+            scroll: 0,
+            running: None,
+            quit: false,
+        }
+    }
+
+    /// Every year in the repository, newest first
+    pub fn load(root: &Path) -> io::Result<Self> {
+        let db = AnswerDb::load(&AnswerDb::default_path())?;
+        let benchmarks = Benchmarks::load(&Benchmarks::path(root))?;
+        let years = registry::years(root)
+            .into_iter()
+            .map(|year| Year {
+                year,
+                days: registry::solutions(root, year)
+                    .into_iter()
+                    .map(|solution| Day {
+                        answers: solution.answers(&db),
+                        benchmark: benchmarks.0.get(&(year, solution.day)).copied(),
+                        solution,
+                        last: None,
+                    })
+                    .collect(),
+            })
+            .filter(|year| !year.days.is_empty())
+            .collect();
+        Ok(App::new(years))
+    }
+
+    #[must_use]
+    pub fn selected(&self) -> Option<&Day> {
+        self.years.get(self.year)?.days.get(self.list.selected()?)
+    }
+
+    fn change_year(&mut self, by: isize) {
+        if self.years.is_empty() {
+            return;
+        }
+        self.year = self
+            .year
+            .saturating_add_signed(by)
+            .min(self.years.len() - 1);
+        self.list.select(Some(0));
+        self.scroll = 0;
+    }
+
+    fn change_day(&mut self, by: isize) {
+        let days = self.years.get(self.year).map_or(0, |year| year.days.len());
+        let day = self.list.selected().unwrap_or(0).saturating_add_signed(by);
+        self.list.select(Some(day.min(days.saturating_sub(1))));
+        self.scroll = 0;
+    }
+
+    /// Handles a key. Returns a job to start for the selected day, if any.
+    pub fn key(&mut self, key: KeyCode) -> Option<Job> {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Left | KeyCode::Char('h') => self.change_year(-1),
+            KeyCode::Right | KeyCode::Char('l') => self.change_year(1),
+            KeyCode::Up | KeyCode::Char('k') => self.change_day(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.change_day(1),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::Char('r') => return self.job(Job::Run(None)),
+            KeyCode::Char('1') => return self.job(Job::Run(Some(1))),
+            KeyCode::Char('2') => return self.job(Job::Run(Some(2))),
+            KeyCode::Char('v') => return self.job(Job::Visualise),
+            _ => {}
+        }
+        None
+    }
+
+    /// `job`, unless something is running already
+    fn job(&mut self, job: Job) -> Option<Job> {
+        if self.running.is_some() {
+            return None;
+        }
+        let day = self.list.selected()?;
+        self.selected()?;
+        self.running = Some((self.year, day, job));
+        self.scroll = 0;
+        Some(job)
+    }
+
+    /// Stores the result of the running job
+    pub fn finished(&mut self, result: Result<Run, String>) {
+        if let Some((year, day, job)) = self.running.take() {
+            self.years[year].days[day].last = Some((job, result));
+        }
+    }
+}
+
+/// Builds and runs a day
+fn perform(solution: &Solution, job: Job) -> Result<Run, String> {
+    solution.build()?;
+    let args: &[&str] = match job {
+        Job::Visualise if solution.animates() => &["--animate"],
+        Job::Visualise => &["-vv"],
+        _ => &[],
+    };
+    solution.run(args)
+}
+
+pub fn run(root: &Path) -> Result<(), String> {
+    let mut app = App::load(root).map_err(|e| format!("couldn't load the solutions: {e}"))?;
+    if app.years.is_empty() {
+        return Err(String::from("there are no solutions"));
+    }
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result.map_err(|e| e.to_string())
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> io::Result<()> {
+    let mut job: Option<Receiver<Result<Run, String>>> = None;
+
+    while !app.quit {
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if let Some(receiver) = &job {
+            if let Ok(result) = receiver.try_recv() {
+                app.finished(result);
+                job = None;
+            }
+        }
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(new_job) = app.key(key.code) {
+                let solution = app.selected().map(|day| day.solution.clone());
+                if let Some(solution) = solution {
+                    let (sender, receiver) = mpsc::channel();
+                    thread::spawn(move || {
+                        let _ = sender.send(perform(&solution, new_job));
+                    });
+                    job = Some(receiver);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `12 ms` or `1.5 s`
+fn runtime(runtime: Duration) -> String {
+    if runtime < Duration::from_secs(1) {
+        format!("{} ms", runtime.as_millis())
+    } else {
+        format!("{:.1} s", runtime.as_secs_f64())
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let [tabs, main, help] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [list, right] =
+        Layout::horizontal([Constraint::Length(24), Constraint::Min(0)]).areas(main);
+    let [details, output] =
+        Layout::vertical([Constraint::Length(8), Constraint::Min(0)]).areas(right);
+
+    let years: Vec<String> = app.years.iter().map(|y| y.year.to_string()).collect();
+    frame.render_widget(
+        Tabs::new(years)
+            .select(app.year)
+            .highlight_style(Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        tabs,
+    );
+
+    let Some(year) = app.years.get(app.year) else {
+        return;
+    };
+    let items: Vec<ListItem> = year
+        .days
+        .iter()
+        .map(|day| {
+            let benchmark = day.benchmark.map(runtime).unwrap_or_default();
+            ListItem::new(format!(
+                "{:>2} {} {benchmark:>9}",
+                day.solution.day,
+                day.stars()
+            ))
+        })
+        .collect();
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title("days"))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+        list,
+        &mut app.list,
+    );
+
+    let Some(day) = app.selected() else {
+        return;
+    };
+    let running = app
+        .running
+        .is_some_and(|(y, d, _)| y == app.year && Some(d) == app.list.selected());
+    frame.render_widget(
+        Paragraph::new(detail_lines(day, running)).block(
+            Block::bordered().title(format!("{} day {}", day.solution.year, day.solution.day)),
+        ),
+        details,
+    );
+
+    let (title, text) = match &day.last {
+        Some((Job::Visualise, Ok(run))) => ("visualisation", run.output.clone()),
+        Some((_, Ok(run))) => ("output", run.output.clone()),
+        Some((_, Err(error))) => ("error", error.clone()),
+        None => ("output", String::new()),
+    };
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(Block::bordered().title(title))
+            .scroll((app.scroll, 0)),
+        output,
+    );
+
+    frame.render_widget(
+        Line::from("←→ year  ↑↓ day  r run  1/2 run a part  v visualise  PgUp/PgDn scroll  q quit")
+            .style(Style::new().fg(Color::DarkGray)),
+        help,
+    );
+}
+
+fn detail_lines(day: &Day, running: bool) -> Vec<Line<'static>> {
+    let solution = &day.solution;
+    let mut lines = vec![Line::from(format!(
+        "{} · {}",
+        solution.language,
+        solution.dir.display()
+    ))];
+    if solution.language == Language::Swift {
+        lines.push(Line::from("Swift playgrounds run in Xcode"));
+    }
+    lines.push(Line::from(format!(
+        "benchmark  {}",
+        day.benchmark.map_or(String::from("–"), runtime)
+    )));
+
+    let checked = match &day.last {
+        Some((Job::Run(part), _)) => *part,
+        _ => None,
+    };
+    for part in 1..=2 {
+        let answer = day.answers[usize::from(part - 1)].as_deref();
+        let (mark, style) = match day.verdict(part) {
+            Some(true) => ("✓", Style::new().fg(Color::Green)),
+            Some(false) => ("✗ not in the output", Style::new().fg(Color::Red)),
+            None => ("", Style::new()),
+        };
+        let style = if checked == Some(part) {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!(
+                "part {part}     {} ",
+                answer.unwrap_or("no recorded answer")
+            )),
+            Span::styled(mark, style),
+        ]));
+    }
+
+    lines.push(Line::from(match (&day.last, running) {
+        (_, true) => String::from("running…"),
+        (Some((_, Ok(run))), _) if !run.success => {
+            format!("last run   failed after {}", runtime(run.runtime))
+        }
+        (Some((_, Ok(run))), _) => format!("last run   {}", runtime(run.runtime)),
+        (Some((_, Err(_))), _) => String::from("last run   didn't build"),
+        (None, _) => String::new(),
+    }));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(number: u8, answers: [Option<&str>; 2]) -> Day {
+        Day {
+            solution: Solution {
+                year: 2022,
+                day: number,
+                language: Language::Rust,
+                dir: format!("2022/{number:02}").into(),
+            },
+            answers: answers.map(|answer| answer.map(String::from)),
+            benchmark: None,
+            last: None,
+        }
+    }
+
+    fn app() -> App {
+        App::new(vec![
+            Year {
+                year: 2024,
+                days: vec![day(1, [None, None])],
+            },
+            Year {
+                year: 2022,
+                days: vec![day(1, [None, None]), day(14, [Some("692"), Some("93")])],
+            },
+        ])
+    }
+
+    #[test]
+    fn navigation() {
+        let mut app = app();
+        app.key(KeyCode::Down);
+        assert_eq!(app.selected().unwrap().solution.day, 1);
+        app.key(KeyCode::Right);
+        app.key(KeyCode::Down);
+        app.key(KeyCode::Down);
+        assert_eq!(app.selected().unwrap().solution.year, 2022);
+        assert_eq!(app.selected().unwrap().solution.day, 14);
+        app.key(KeyCode::Right);
+        assert_eq!(app.year, 1);
+
+        assert_eq!(app.key(KeyCode::Char('2')), Some(Job::Run(Some(2))));
+        // One job at a time
+        assert_eq!(app.key(KeyCode::Char('v')), None);
+        app.key(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn verdicts() {
+        let mut app = app();
+        app.key(KeyCode::Right);
+        app.key(KeyCode::Down);
+        app.key(KeyCode::Char('r'));
+        app.finished(Ok(Run {
+            output: String::from("692\n94\n"),
+            runtime: Duration::from_millis(5),
+            success: true,
+        }));
+
+        let day = app.selected().unwrap();
+        assert_eq!(day.verdict(1), Some(true));
+        assert_eq!(day.verdict(2), Some(false));
+        assert_eq!(app.years[1].days[0].verdict(1), None);
+        assert!(app.running.is_none());
+    }
+
+    #[test]
+    fn draws() {
+        let mut app = app();
+        app.key(KeyCode::Right);
+        app.key(KeyCode::Down);
+        app.key(KeyCode::Char('1'));
+        app.finished(Ok(Run {
+            output: String::from("692\n94\n"),
+            runtime: Duration::from_millis(5),
+            success: true,
+        }));
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 16)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let screen: Vec<String> = terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(80)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect();
+        let screen = screen.join("\n");
+        assert!(screen.contains("2022 day 14"));
+        assert!(screen.contains("part 1     692 ✓"));
+        assert!(screen.contains("part 2     93 ✗ not in the output"));
+        assert!(screen.contains("last run   5 ms"));
+        assert!(screen.contains("│14 ★★"));
+    }
+}
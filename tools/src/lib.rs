@@ -0,0 +1,4 @@
+//! What the `aoc` and `aoc-serve` binaries and the Python module in `python/` share: the
+//! solutions in this repository.
+
+pub mod registry;
@@ -0,0 +1,380 @@
+//! `aoc`: tooling around the solutions.
+//!
+//! ```text
+//! aoc examples <page.html> <day directory> [--force]
+//! aoc input <year> <day>
+//! aoc submit <year> <day> <part> <answer>
+//! aoc leaderboard <leaderboard.json> [--scoring=local|stars|deltas] [--json]
+//! aoc readme [--bench]
+//! aoc compare <year> <day> [inputs directory] [--timeout=<seconds>]
+//! aoc dashboard
+//! aoc wasm [year …]
+//! ```
+
+mod compare;
+mod dashboard;
+mod page;
+mod readme;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc_common::answers::{AnswerDb, Puzzle};
+use aoc_common::input::Answers;
+use aoc_common::leaderboard::{Leaderboard, Report, Scoring};
+use aoc_common::site::{self, Client, InputStore, SiteError, Submission};
+use aoc_tools::registry::{self, repository, Benchmarks, Language};
+
+use page::PuzzlePage;
+use readme::Star;
+
+const USAGE: &str = "\
+usage: aoc <command>
+
+commands:
+  examples <page.html> <day directory> [--force]
+      Saves the examples of a saved puzzle page as input/example.txt, input/example_2.txt, …
+      with their likely answers in input/example.answers, … and the answers for the real
+      input in input/1.answers when the page has them. Existing files are kept, unless
+      --force is given.
+  input <year> <day>
+      Fetches a day's input into the input store, unless it's there already, and prints
+      where it is. The session token comes from AOC_SESSION or inputs/.session.
+  submit <year> <day> <part> <answer>
+      Submits an answer, unless inputs/answers.tsv shows it's wrong already, and records
+      the verdict there.
+  leaderboard <leaderboard.json> [--scoring=local|stars|deltas] [--json]
+      Shows the standings, rankings per day and star timelines of a private leaderboard
+      exported from the site, next to the days this repository has solutions for.
+  readme [--bench]
+      Regenerates the star calendar of every year in README.md, from the solutions, the
+      accepted answers in inputs/answers.tsv and input/1.answers, and the runtimes in
+      benchmarks.tsv. The release builds are run on their input/1.txt, and parts they
+      answer without an accepted answer get a ☆. --bench also times them.
+  compare <year> <day> [inputs directory] [--timeout=<seconds>]
+      Runs a day on every *.txt in a directory, its own input/ unless another is given,
+      and shows a matrix of their answers and runtimes. Runs that panic, time out (after
+      60 seconds by default) or disagree with the answers recorded next to their input,
+      in *.answers files or inputs/answers.tsv for input/1.txt, are flagged.
+  dashboard
+      A terminal dashboard of every year and day, to run them, check their answers and
+      look at their visualisations.
+  wasm [year …]
+      Builds the Rust solutions of the years, or of all of them, for wasm32-wasip1, runs
+      them in an embedded WebAssembly runtime and checks they print what they do natively.
+      Needs the tools built with --features wasm.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let force = args.iter().any(|arg| arg == "--force");
+    let json = args.iter().any(|arg| arg == "--json");
+    let bench = args.iter().any(|arg| arg == "--bench");
+    let scoring = args
+        .iter()
+        .find_map(|arg| arg.strip_prefix("--scoring="))
+        .map_or(Ok(Scoring::default()), str::parse);
+    let timeout = args
+        .iter()
+        .find_map(|arg| arg.strip_prefix("--timeout="))
+        .map_or(Ok(Duration::from_secs(60)), |secs| {
+            secs.parse()
+                .ok()
+                .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                .ok_or(format!("{secs} isn't a number of seconds"))
+        });
+    let positional: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+
+    let result = match positional.as_slice() {
+        ["examples", page, day] => examples(Path::new(page), Path::new(day), force),
+        ["input", year, day] => puzzle(year, day).and_then(|(year, day)| input(year, day)),
+        ["submit", year, day, part, answer] => {
+            puzzle(year, day).and_then(|(year, day)| match part.parse() {
+                Ok(part @ 1..=2) => submit(Puzzle::new(year, day, part), answer),
+                _ => Err(format!("{part} isn't a part")),
+            })
+        }
+        ["leaderboard", path] => {
+            scoring.and_then(|scoring| leaderboard(Path::new(path), scoring, json))
+        }
+        ["readme"] => readme(bench),
+        ["compare", year, day, dir @ ..] if dir.len() <= 1 => puzzle(year, day)
+            .and_then(|(year, day)| compare(year, day, dir.first().map(Path::new), timeout?)),
+        ["dashboard"] => dashboard::run(&repository()),
+        ["wasm", years @ ..] => wasm(years),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn puzzle(year: &str, day: &str) -> Result<(u16, u8), String> {
+    match (year.parse(), day.parse()) {
+        (Ok(year), Ok(day @ 1..=25)) => Ok((year, day)),
+        _ => Err(format!("{year} day {day} isn't a puzzle")),
+    }
+}
+
+fn input(year: u16, day: u8) -> Result<(), String> {
+    let store = InputStore::from_env();
+    store.get(year, day).map_err(|e| e.to_string())?;
+    println!("{}", store.path(year, day).display());
+    Ok(())
+}
+
+fn submit(puzzle: Puzzle, answer: &str) -> Result<(), String> {
+    let client = Client::from_env().ok_or(SiteError::NoSession.to_string())?;
+    let path = AnswerDb::default_path();
+    let mut db =
+        AnswerDb::load(&path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+
+    match site::submit(&client, &mut db, puzzle, answer).map_err(|e| e.to_string())? {
+        Submission::Known(verdict) => {
+            println!("{puzzle}: not sent, {answer} is known to be {verdict}");
+        }
+        Submission::Sent(outcome) => {
+            println!("{puzzle}: {outcome}");
+            db.save(&path)
+                .map_err(|e| format!("couldn't write {}: {e}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn leaderboard(path: &Path, scoring: Scoring, json: bool) -> Result<(), String> {
+    let board =
+        Leaderboard::load(path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+    let report = Report::new(
+        &board,
+        scoring,
+        registry::solved_days(&repository(), board.year),
+    );
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        print!("{report}");
+    }
+    Ok(())
+}
+
+fn readme(bench: bool) -> Result<(), String> {
+    let root = repository();
+    let benchmarks_path = Benchmarks::path(&root);
+    let mut benchmarks = Benchmarks::load(&benchmarks_path)
+        .map_err(|e| format!("couldn't read {}: {e}", benchmarks_path.display()))?;
+    let answers_path = AnswerDb::default_path();
+    let db = AnswerDb::load(&answers_path)
+        .map_err(|e| format!("couldn't read {}: {e}", answers_path.display()))?;
+
+    let readme_path = root.join("README.md");
+    let mut text = fs::read_to_string(&readme_path)
+        .map_err(|e| format!("couldn't read {}: {e}", readme_path.display()))?;
+
+    for year in registry::years(&root) {
+        let solutions = registry::solutions(&root, year);
+        if solutions.is_empty() {
+            continue;
+        }
+
+        let rust = solutions.iter().any(|s| s.language == Language::Rust);
+        if rust {
+            println!("building {year}");
+            registry::build(&root, year, &solutions)?;
+        }
+        if bench && rust {
+            println!("benchmarking {year}");
+            for solution in &solutions {
+                if let Some(runtime) = registry::time(solution) {
+                    println!("  day {}: {runtime:?}", solution.day);
+                    benchmarks.0.insert((year, solution.day), runtime);
+                }
+            }
+            benchmarks
+                .save(&benchmarks_path)
+                .map_err(|e| format!("couldn't write {}: {e}", benchmarks_path.display()))?;
+        }
+
+        let days: Vec<readme::Day> = solutions
+            .iter()
+            .map(|solution| {
+                let accepted = solution.answers(&db);
+                let printed = solution.printed_answers().unwrap_or_default();
+                readme::Day {
+                    day: solution.day,
+                    path: solution
+                        .dir
+                        .strip_prefix(&root)
+                        .unwrap_or(&solution.dir)
+                        .to_string_lossy()
+                        .into_owned(),
+                    language: solution.language,
+                    stars: [0, 1].map(|part| match (&accepted[part], &printed[part]) {
+                        (Some(_), _) => Star::Accepted,
+                        (None, Some(_)) => Star::Answered,
+                        (None, None) => Star::Unsolved,
+                    }),
+                    runtime: benchmarks.0.get(&(year, solution.day)).copied(),
+                }
+            })
+            .collect();
+        text = readme::splice(&text, year, &readme::calendar(year, &days));
+    }
+
+    fs::write(&readme_path, text)
+        .map_err(|e| format!("couldn't write {}: {e}", readme_path.display()))?;
+    println!("wrote {}", readme_path.display());
+    Ok(())
+}
+
+fn compare(year: u16, day: u8, dir: Option<&Path>, timeout: Duration) -> Result<(), String> {
+    let solution = registry::solutions(&repository(), year)
+        .into_iter()
+        .find(|s| s.day == day && s.language == Language::Rust)
+        .ok_or(format!("{year} day {day} doesn't have a Rust solution"))?;
+    if year == 2020 {
+        return Err(String::from("the 2020 days can only read their own input"));
+    }
+    let own_inputs = solution.dir.join("input");
+    let dir = dir.unwrap_or(&own_inputs);
+    let inputs = compare::inputs(dir)?;
+    if inputs.is_empty() {
+        return Err(format!("{} doesn't have any *.txt inputs", dir.display()));
+    }
+    let answers_path = AnswerDb::default_path();
+    let db = AnswerDb::load(&answers_path)
+        .map_err(|e| format!("couldn't read {}: {e}", answers_path.display()))?;
+
+    solution.build()?;
+    let mut rows = Vec::new();
+    for input in &inputs {
+        let [part_1, part_2] = if input.canonicalize().ok() == solution.input().canonicalize().ok()
+        {
+            solution.answers(&db)
+        } else {
+            let answers = Answers::load(input)
+                .map_err(|e| format!("couldn't read the answers for {}: {e}", input.display()))?;
+            answers.map_or([None, None], |a| [a.part_1, a.part_2])
+        };
+        rows.push(compare::run(
+            &solution,
+            input,
+            Answers { part_1, part_2 },
+            timeout,
+        )?);
+    }
+    print!("{}", compare::matrix(&rows));
+
+    let flagged: Vec<&str> = rows
+        .iter()
+        .filter(|row| row.flagged())
+        .map(|row| row.input.as_str())
+        .collect();
+    if flagged.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("wrong or no answers for {}", flagged.join(", ")))
+    }
+}
+
+#[cfg(feature = "wasm")]
+fn wasm(years: &[&str]) -> Result<(), String> {
+    let root = repository();
+    let years = if years.is_empty() {
+        registry::years(&root)
+    } else {
+        years
+            .iter()
+            .map(|year| year.parse().map_err(|_| format!("{year} isn't a year")))
+            .collect::<Result<_, _>>()?
+    };
+    wasm::compare(&root, &years)
+}
+
+#[cfg(not(feature = "wasm"))]
+fn wasm(_years: &[&str]) -> Result<(), String> {
+    Err(String::from(
+        "built without the wasm feature, try: cargo run --release --features wasm -- wasm",
+    ))
+}
+
+fn examples(page: &Path, day: &Path, force: bool) -> Result<(), String> {
+    let html =
+        fs::read_to_string(page).map_err(|e| format!("couldn't read {}: {e}", page.display()))?;
+    let page = PuzzlePage::parse(&html);
+    if page.parts.is_empty() {
+        return Err(String::from("that doesn't look like a puzzle page"));
+    }
+    if let Some(title) = &page.title {
+        println!("{title}");
+    }
+
+    let input_dir = day.join("input");
+    fs::create_dir_all(&input_dir)
+        .map_err(|e| format!("couldn't create {}: {e}", input_dir.display()))?;
+
+    let examples = page.examples();
+    if examples.is_empty() {
+        println!("no examples found");
+    }
+    for (i, example) in examples.iter().enumerate() {
+        let path = input_dir.join(match i {
+            0 => String::from("example.txt"),
+            i => format!("example_{}.txt", i + 1),
+        });
+        if write(&path, &example.input, force)? {
+            write_answers(&path, &example.answers, force)?;
+        }
+    }
+
+    let answers = page.puzzle_answers();
+    if answers != Answers::default() {
+        write_answers(&input_dir.join("1.txt"), &answers, force)?;
+    }
+    Ok(())
+}
+
+fn write_answers(input: &Path, answers: &Answers, force: bool) -> Result<(), String> {
+    let shown = |answer: Option<&str>| answer.unwrap_or("?").to_owned();
+    println!(
+        "  {}: part 1 {}, part 2 {}",
+        input.display(),
+        shown(answers.part(1)),
+        shown(answers.part(2))
+    );
+    write(&Answers::path_for(input), &answers.to_string(), force).map(|_| ())
+}
+
+/// Writes `contents` to `path`, unless a different file is already there.
+/// Returns whether `path` has `contents` now.
+fn write(path: &Path, contents: &str, force: bool) -> Result<bool, String> {
+    match fs::read_to_string(path) {
+        Ok(existing) if existing == contents => return Ok(true),
+        Ok(_) if !force => {
+            println!(
+                "  kept {}, it's different (--force overwrites it)",
+                path.display()
+            );
+            return Ok(false);
+        }
+        _ => {}
+    }
+    fs::write(path, contents).map_err(|e| format!("couldn't write {}: {e}", path.display()))?;
+    println!("  wrote {}", path.display());
+    Ok(true)
+}
@@ -0,0 +1,352 @@
+//! Reads puzzle pages saved from `https://adventofcode.com/<year>/day/<day>`.
+//!
+//! Every part of the puzzle is an `<article class="day-desc">`. The examples are its
+//! `<pre><code>` blocks, and the answer for the example is, by convention, the last
+//! emphasised code (`<code><em>24</em></code>`) in the article. Once a part is solved, the
+//! page also says `Your puzzle answer was <code>…</code>` after it.
+//!
+//! This is a scanner for the markup those pages use, not a general HTML parser.
+
+use aoc_common::input::{normalise, Answers};
+
+/// A `<pre><code>` block
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Block {
+    /// The text between the previous block (or the start of the part) and this one
+    pub lead_in: String,
+    pub content: String,
+}
+
+impl Block {
+    /// Whether the text before the block introduces a new example, rather than showing the
+    /// previous example in some later state
+    #[must_use]
+    pub fn introduces_example(&self) -> bool {
+        let lead_in = self.lead_in.to_lowercase();
+        [
+            "for example",
+            "new example",
+            "another example",
+            "larger example",
+            "second example",
+        ]
+        .iter()
+        .any(|phrase| lead_in.contains(phrase))
+    }
+}
+
+/// One part of the puzzle
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Part {
+    pub blocks: Vec<Block>,
+    /// The last emphasised code, most likely the example's answer
+    pub example_answer: Option<String>,
+    /// The answer for the real input, if the part was solved when the page was saved
+    pub puzzle_answer: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PuzzlePage {
+    /// Like `Day 14: Regolith Reservoir`
+    pub title: Option<String>,
+    pub parts: Vec<Part>,
+}
+
+/// An example input and what it should give
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Answers,
+}
+
+impl PuzzlePage {
+    #[must_use]
+    pub fn parse(html: &str) -> Self {
+        let mut parts = Vec::new();
+        let mut title = None;
+        let mut rest = html;
+
+        while let Some((article, after)) = element(rest, "article") {
+            let mut part = Part::default();
+            if title.is_none() {
+                title = element(article, "h2")
+                    .map(|(h2, _)| text(h2).trim_matches(|c| c == '-' || c == ' ').to_owned());
+            }
+
+            let mut blocks = article;
+            while let Some((pre, after)) = element(blocks, "pre") {
+                let code = element(pre, "code").map_or(pre, |(code, _)| code);
+                let lead_in = blocks.find("<pre").map_or("", |i| &blocks[..i]);
+                part.blocks.push(Block {
+                    lead_in: text(lead_in),
+                    content: text(code),
+                });
+                blocks = after;
+            }
+            part.example_answer = emphasised_code(article).last().map(|answer| text(answer));
+
+            // The solved answer is between this article and the next one
+            let until_next = after.find("<article").map_or(after, |i| &after[..i]);
+            part.puzzle_answer = until_next
+                .find("Your puzzle answer was")
+                .and_then(|i| element(&until_next[i..], "code"))
+                .map(|(code, _)| text(code));
+
+            parts.push(part);
+            rest = after;
+        }
+
+        PuzzlePage { title, parts }
+    }
+
+    /// The examples with their likely answers.
+    /// The first block of part 1 is its example. Part 2 usually reuses that example with a
+    /// new answer, unless its first block [introduces](Block::introduces_example) another one.
+    #[must_use]
+    pub fn examples(&self) -> Vec<Example> {
+        let mut examples: Vec<Example> = Vec::new();
+
+        for (number, part) in self.parts.iter().take(2).enumerate() {
+            let new_input = part
+                .blocks
+                .first()
+                .filter(|block| number == 0 || block.introduces_example())
+                .map(|block| normalise(&block.content));
+
+            let index = match new_input {
+                Some(input) => match examples.iter().position(|e| e.input == input) {
+                    Some(index) => index,
+                    None => {
+                        examples.push(Example {
+                            input,
+                            answers: Answers::default(),
+                        });
+                        examples.len() - 1
+                    }
+                },
+                None if examples.is_empty() => continue,
+                None => 0,
+            };
+
+            let answers = &mut examples[index].answers;
+            match number {
+                0 => answers.part_1.clone_from(&part.example_answer),
+                _ => answers.part_2.clone_from(&part.example_answer),
+            }
+        }
+
+        examples
+    }
+
+    /// The answers for the real input, as far as they're on the page
+    #[must_use]
+    pub fn puzzle_answers(&self) -> Answers {
+        let answer = |i: usize| self.parts.get(i).and_then(|p| p.puzzle_answer.clone());
+        Answers {
+            part_1: answer(0),
+            part_2: answer(1),
+        }
+    }
+}
+
+/// The contents of the first `<name …>…</name>` in `html`, and everything after it
+fn element<'a>(html: &'a str, name: &str) -> Option<(&'a str, &'a str)> {
+    let mut search = 0;
+    loop {
+        let start = search + html[search..].find(&format!("<{name}"))?;
+        let after_name = &html[start + name.len() + 1..];
+        // Don't mistake `<pre>` for `<p>` and the like
+        if after_name.starts_with(['>', ' ', '\n', '\t', '/']) {
+            let open_end = start + html[start..].find('>')? + 1;
+            let close = format!("</{name}>");
+            let close_start = open_end + html[open_end..].find(&close)?;
+            return Some((
+                &html[open_end..close_start],
+                &html[close_start + close.len()..],
+            ));
+        }
+        search = start + 1;
+    }
+}
+
+/// The contents of `<code><em>…</em></code>` and `<em><code>…</code></em>`, in order
+fn emphasised_code(html: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    let mut rest = html;
+    while let Some((code, after)) = element(rest, "code") {
+        if let Some(inner) = code
+            .trim()
+            .strip_prefix("<em>")
+            .and_then(|c| c.strip_suffix("</em>"))
+        {
+            found.push(inner);
+        }
+        rest = after;
+    }
+
+    let mut rest = html;
+    while let Some((em, after)) = element(rest, "em") {
+        if let Some(inner) = em
+            .trim()
+            .strip_prefix("<code>")
+            .and_then(|c| c.strip_suffix("</code>"))
+        {
+            found.push(inner);
+        }
+        rest = after;
+    }
+
+    // Back in document order
+    found.sort_by_key(|inner| inner.as_ptr() as usize);
+    found
+}
+
+/// `html` without tags and with entities decoded
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find('>')
+            .map_or("", |end| &rest[start + end + 1..]);
+    }
+    text.push_str(rest);
+    decode_entities(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').map(|end| (&rest[1..end], end + 1));
+        let c = entity.and_then(|(name, _)| match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let code = name.strip_prefix('#')?;
+                let code = match code.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => code.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (c, entity) {
+            (Some(c), Some((_, len))) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><head><title>Day 14 - Advent of Code 2022</title></head><body>
+<main>
+<article class="day-desc"><h2>--- Day 14: Regolith Reservoir ---</h2>
+<p>For example:</p>
+<pre><code>498,4 -&gt; 498,6 -&gt; 496,6
+503,4 -&gt; 502,4 -&gt; 502,9 -&gt; 494,9
+</code></pre>
+<p>The sand falls like this:</p>
+<pre><code>......+...
+<em>o</em>.........
+</code></pre>
+<p>In this example, <code><em>24</em></code> units of sand come to rest.
+Using <em>your</em> scan, how many units of sand come to rest?</p>
+</article>
+<p>Your puzzle answer was <code>692</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In the example above, the situation finally looks like this:</p>
+<pre><code>......o...
+############
+</code></pre>
+<p>You get <em><code>93</code></em> units of sand.</p>
+</article>
+<p>Your puzzle answer was <code>31706</code>.</p>
+</main></body></html>
+"#;
+
+    #[test]
+    fn parses_parts() {
+        let page = PuzzlePage::parse(PAGE);
+        assert_eq!(page.title.as_deref(), Some("Day 14: Regolith Reservoir"));
+        assert_eq!(page.parts.len(), 2);
+        assert_eq!(page.parts[0].blocks.len(), 2);
+        assert_eq!(page.parts[0].blocks[1].content, "......+...\no.........\n");
+        assert!(page.parts[0].blocks[0].introduces_example());
+        assert!(!page.parts[0].blocks[1].introduces_example());
+        assert_eq!(page.parts[0].example_answer.as_deref(), Some("24"));
+        assert_eq!(page.parts[1].example_answer.as_deref(), Some("93"));
+
+        let answers = page.puzzle_answers();
+        assert_eq!(answers.part(1), Some("692"));
+        assert_eq!(answers.part(2), Some("31706"));
+    }
+
+    #[test]
+    fn shared_example() {
+        let examples = PuzzlePage::parse(PAGE).examples();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(
+            examples[0].input,
+            "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n"
+        );
+        assert_eq!(examples[0].answers.part(1), Some("24"));
+        assert_eq!(examples[0].answers.part(2), Some("93"));
+    }
+
+    #[test]
+    fn second_example() {
+        let page = r#"
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+treb7uchet
+</code></pre><p>Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<article class="day-desc"><h2>--- Part Two ---</h2>
+<p>For example:</p>
+<pre><code>two1nine
+7pqrstsixteen
+</code></pre><p>Adding these together produces <code><em>281</em></code>.</p>
+</article>"#;
+        let examples = PuzzlePage::parse(page).examples();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].answers.part(1), Some("142"));
+        assert_eq!(examples[0].answers.part(2), None);
+        assert_eq!(examples[1].input, "two1nine\n7pqrstsixteen\n");
+        assert_eq!(examples[1].answers.part(2), Some("281"));
+        assert_eq!(PuzzlePage::parse(page).puzzle_answers(), Answers::default());
+    }
+
+    #[test]
+    fn markup() {
+        assert_eq!(
+            text("a &lt;b&gt; &amp; &#39;c&#x27; &bogus; &"),
+            "a <b> & 'c' &bogus; &"
+        );
+        assert_eq!(element("<p>x</p><pre>y</pre>", "pre"), Some(("y", "")));
+        assert_eq!(
+            emphasised_code("<code>1</code><em><code>2</code></em><code><em>3</em></code>"),
+            ["2", "3"]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! The generated parts of `README.md`: a star calendar per year.
+//!
+//! A year's calendar sits between `<!-- aoc readme <year> -->` and
+//! `<!-- /aoc readme <year> -->`, after whatever was written about that year by hand.
+//! Regenerating only replaces what's between the markers, and adds sections for new years.
+
+use std::time::Duration;
+
+use aoc_tools::registry::Language;
+
+/// How far a part of a day got
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Star {
+    Unsolved,
+    /// The solution prints an answer, but it isn't known to be accepted
+    Answered,
+    Accepted,
+}
+
+impl Star {
+    fn cell(self) -> &'static str {
+        match self {
+            Star::Unsolved => "",
+            Star::Answered => "☆",
+            Star::Accepted => "⭐",
+        }
+    }
+}
+
+/// A day with a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day {
+    pub day: u8,
+    /// The solution's directory, relative to the root
+    pub path: String,
+    pub language: Language,
+    pub stars: [Star; 2],
+    pub runtime: Option<Duration>,
+}
+
+fn start_marker(year: u16) -> String {
+    format!("<!-- aoc readme {year} -->")
+}
+
+fn end_marker(year: u16) -> String {
+    format!("<!-- /aoc readme {year} -->")
+}
+
+/// `12 ms`, `<1 ms` or `1.5 s`
+fn runtime(runtime: Duration) -> String {
+    match runtime.as_millis() {
+        0 => String::from("<1 ms"),
+        millis @ 1..=999 => format!("{millis} ms"),
+        _ => format!("{:.1} s", runtime.as_secs_f64()),
+    }
+}
+
+/// The calendar of a year, with its markers
+pub fn calendar(year: u16, days: &[Day]) -> String {
+    let mut languages: Vec<(Language, usize)> = Vec::new();
+    for day in days {
+        match languages.iter_mut().find(|(l, _)| *l == day.language) {
+            Some((_, count)) => *count += 1,
+            None => languages.push((day.language, 1)),
+        }
+    }
+    let languages: Vec<String> = languages
+        .iter()
+        .map(|(language, count)| {
+            let s = if *count == 1 { "" } else { "s" };
+            format!("{count} day{s} in {language}")
+        })
+        .collect();
+    let count = |of: Star| {
+        days.iter()
+            .flat_map(|day| day.stars)
+            .filter(|&star| star == of)
+            .count()
+    };
+    let answered = match count(Star::Answered) {
+        0 => String::new(),
+        answered => format!(" and {answered} ☆ answered but not accepted yet"),
+    };
+
+    let mut calendar = start_marker(year) + "\n";
+    calendar += &format!(
+        "{}, {} ⭐ with an accepted answer{answered}.\n\n",
+        languages.join(", "),
+        count(Star::Accepted)
+    );
+    calendar += "| Day | Part 1 | Part 2 | Language | Runtime |\n";
+    calendar += "| --: | :----: | :----: | :------- | ------: |\n";
+    for day in days {
+        calendar += &format!(
+            "| [{}]({}) | {} | {} | {} | {} |\n",
+            day.day,
+            day.path,
+            day.stars[0].cell(),
+            day.stars[1].cell(),
+            day.language,
+            day.runtime.map_or(String::from("–"), runtime)
+        );
+    }
+    calendar + &end_marker(year) + "\n"
+}
+
+/// The year a `## 2022` heading is about
+fn heading_year(line: &str) -> Option<u16> {
+    line.strip_prefix("## ")?.trim().parse().ok()
+}
+
+/// `readme` with the calendar of `year` replaced, added to the end of the year's section, or
+/// in a new section before the older years
+pub fn splice(readme: &str, year: u16, calendar: &str) -> String {
+    let lines: Vec<&str> = readme.lines().collect();
+    let joined = |lines: &[&str]| -> String { lines.iter().map(|l| format!("{l}\n")).collect() };
+
+    let start = lines.iter().position(|&l| l == start_marker(year));
+    let end = lines.iter().position(|&l| l == end_marker(year));
+    if let (Some(start), Some(end)) = (start, end) {
+        if start < end {
+            return joined(&lines[..start]) + calendar + &joined(&lines[end + 1..]);
+        }
+    }
+
+    let section = lines.iter().position(|l| heading_year(l) == Some(year));
+    let (at, text) = match section {
+        Some(section) => {
+            // The end of the section, before the blank lines leading up to the next heading
+            let next = lines[section + 1..]
+                .iter()
+                .position(|l| l.starts_with("## "))
+                .map_or(lines.len(), |i| section + 1 + i);
+            let mut at = next;
+            while at > section + 1 && lines[at - 1].trim().is_empty() {
+                at -= 1;
+            }
+            (at, format!("\n{calendar}"))
+        }
+        None => {
+            // Before the first older year, or the first section that isn't a year at all
+            let at = lines
+                .iter()
+                .position(|l| {
+                    l.starts_with("## ") && heading_year(l).is_none_or(|other| other < year)
+                })
+                .unwrap_or(lines.len());
+            (at, format!("## {year}\n{calendar}\n"))
+        }
+    };
+
+    let mut spliced = joined(&lines[..at]);
+    spliced += &text;
+    if section.is_some() && at < lines.len() && !lines[at].trim().is_empty() {
+        spliced.push('\n');
+    }
+    spliced + &joined(&lines[at..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = "# Advent of Code
+Intro.
+
+## 2022
+Rust this year.
+
+## 2019
+Swift.
+
+## Tools
+Commands.
+";
+
+    const ACCEPTED: [Star; 2] = [Star::Accepted; 2];
+
+    fn day(day: u8, stars: [Star; 2], runtime: Option<u64>) -> Day {
+        Day {
+            day,
+            path: format!("2022/{day:02}"),
+            language: Language::Rust,
+            stars,
+            runtime: runtime.map(Duration::from_micros),
+        }
+    }
+
+    #[test]
+    fn calendars() {
+        assert!(calendar(2022, &[day(1, ACCEPTED, None)])
+            .contains("\n1 day in Rust, 2 ⭐ with an accepted answer.\n"));
+        let calendar = calendar(
+            2022,
+            &[
+                day(1, ACCEPTED, Some(400)),
+                day(14, [Star::Accepted, Star::Answered], Some(12_300)),
+                day(15, [Star::Unsolved; 2], None),
+            ],
+        );
+        assert_eq!(
+            calendar,
+            "<!-- aoc readme 2022 -->
+3 days in Rust, 3 ⭐ with an accepted answer and 1 ☆ answered but not accepted yet.
+
+| Day | Part 1 | Part 2 | Language | Runtime |
+| --: | :----: | :----: | :------- | ------: |
+| [1](2022/01) | ⭐ | ⭐ | Rust | <1 ms |
+| [14](2022/14) | ⭐ | ☆ | Rust | 12 ms |
+| [15](2022/15) |  |  | Rust | – |
+<!-- /aoc readme 2022 -->
+"
+        );
+        assert_eq!(runtime(Duration::from_millis(1530)), "1.5 s");
+    }
+
+    #[test]
+    fn splices_sections() {
+        let first = calendar(2022, &[day(1, ACCEPTED, None)]);
+        let readme = splice(README, 2022, &first);
+        assert!(readme.contains("## 2022\nRust this year.\n\n<!-- aoc readme 2022 -->\n"));
+        assert!(readme.contains("<!-- /aoc readme 2022 -->\n\n## 2019\n"));
+
+        // Regenerating replaces the calendar, and nothing else
+        let second = calendar(2022, &[day(2, [Star::Accepted, Star::Unsolved], None)]);
+        let regenerated = splice(&readme, 2022, &second);
+        assert_eq!(regenerated, readme.replace(&first, &second));
+        assert_eq!(splice(&regenerated, 2022, &second), regenerated);
+
+        // New years go before the older ones
+        let newer = calendar(2024, &[]);
+        let readme = splice(&readme, 2024, &newer);
+        assert!(readme.contains(&format!("Intro.\n\n## 2024\n{newer}\n## 2022\n")));
+        let older = calendar(2018, &[]);
+        assert!(splice(&readme, 2018, &older)
+            .contains(&format!("Swift.\n\n## 2018\n{older}\n## Tools\n")));
+    }
+}
@@ -0,0 +1,424 @@
+//! The solutions in this repository, and how long they take.
+//!
+//! Every day is a directory: `2022/14/` is a crate with an `aoc-2022-14` binary, in the
+//! `2022` workspace from 2022 on, and on its own in 2020. The 2019 days are Swift
+//! playgrounds, like `2019/aoc-2019-22.playground/`.
+//!
+//! Runtimes are kept in `benchmarks.tsv` at the root, as `year  day  microseconds` lines.
+//! The answers a solution should give come from the [`AnswerDb`] and the `input/1.answers`
+//! sidecar next to its input.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::answers::{AnswerDb, Puzzle};
+use aoc_common::input::Answers;
+use aoc_common::trace::Verbosity;
+
+/// The WebAssembly target the solutions build for
+#[cfg(feature = "wasm")]
+pub const WASM_TARGET: &str = "wasm32-wasip1";
+
+/// The root of this repository
+pub fn repository() -> PathBuf {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    root.canonicalize().unwrap_or(root)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Swift,
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Language::Rust => "Rust",
+            Language::Swift => "Swift",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub language: Language,
+    pub dir: PathBuf,
+}
+
+/// What a run of a solution printed, and how long it took
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    /// Standard output, and standard error when it failed or was asked to log
+    pub output: String,
+    pub runtime: Duration,
+    pub success: bool,
+}
+
+impl Run {
+    /// Whether `answer` is on a line of its own in the output
+    pub fn gave(&self, answer: &str) -> bool {
+        self.output.lines().any(|line| line.trim() == answer.trim())
+    }
+
+    /// The lines of the output with something on them
+    pub fn answers(&self) -> Vec<String> {
+        self.output
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    /// The answers for part 1 and 2: the first line, and the last when there's more than one,
+    /// with whatever it drew in between
+    pub fn parts(&self) -> [Option<String>; 2] {
+        let mut answers = self.answers().into_iter();
+        let part_1 = answers.next();
+        [part_1, answers.next_back()]
+    }
+}
+
+impl Solution {
+    /// The input it's benchmarked with, `input/1.txt` in its directory. The 2020 days find
+    /// their input themselves.
+    pub fn input(&self) -> PathBuf {
+        self.dir.join("input/1.txt")
+    }
+
+    /// The accepted answers for part 1 and 2, as far as they're known
+    pub fn answers(&self, db: &AnswerDb) -> [Option<String>; 2] {
+        let sidecar = Answers::load(&self.input()).ok().flatten();
+        [1, 2].map(|part| {
+            db.right_answer(Puzzle::new(self.year, self.day, part))
+                .or_else(|| sidecar.as_ref()?.part(part))
+                .map(String::from)
+        })
+    }
+
+    /// The answers the built solution prints on [its input](Solution::input), see
+    /// [`Run::parts`], or `None` if it doesn't have one, isn't built or fails
+    pub fn printed_answers(&self) -> Option<[Option<String>; 2]> {
+        if !self.runnable() {
+            return None;
+        }
+        let run = self.run(&[]).ok()?;
+        run.success.then(|| run.parts())
+    }
+
+    /// Whether it can show an animation with `--animate`
+    pub fn animates(&self) -> bool {
+        fs::read_to_string(self.dir.join("src/main.rs"))
+            .is_ok_and(|source| source.contains("animate_requested"))
+    }
+
+    /// Builds the release binary of only this solution
+    pub fn build(&self) -> Result<(), String> {
+        self.cargo_build(&[])
+    }
+
+    /// Builds the release WebAssembly module of only this solution, see
+    /// [`Solution::wasm_module`]
+    #[cfg(feature = "wasm")]
+    pub fn build_wasm(&self) -> Result<(), String> {
+        self.cargo_build(&["--target", WASM_TARGET])
+    }
+
+    fn cargo_build(&self, args: &[&str]) -> Result<(), String> {
+        if self.language != Language::Rust {
+            return Err(format!("{} solutions can't be built here", self.language));
+        }
+        let mut command = Command::new("cargo");
+        command.args(["build", "--release", "--quiet"]).args(args);
+        if self.year != 2020 {
+            command.args(["--package", &format!("aoc-{}-{:02}", self.year, self.day)]);
+        }
+        let output = command
+            .current_dir(&self.dir)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| format!("couldn't run cargo: {e}"))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).into_owned())
+        }
+    }
+
+    /// Runs the built binary on [its input](Solution::input), with `args` after the input
+    pub fn run(&self, args: &[&str]) -> Result<Run, String> {
+        let start = Instant::now();
+        let output = self
+            .command()
+            .args(args)
+            .output()
+            .map_err(|e| format!("couldn't run {}: {e}", self.binary().display()))?;
+        let runtime = start.elapsed();
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        if !output.status.success() || args.iter().any(|arg| Verbosity::is_flag(arg)) {
+            text += &String::from_utf8_lossy(&output.stderr);
+        }
+        Ok(Run {
+            output: text,
+            runtime,
+            success: output.status.success(),
+        })
+    }
+
+    /// Runs the built binary on `input`, passed on standard input as `-`, and kills it if it
+    /// takes longer than `timeout`, which gives `None`
+    pub fn run_on(&self, input: &[u8], timeout: Duration) -> Result<Option<Run>, String> {
+        let binary = self.binary();
+        let start = Instant::now();
+        let mut child = Command::new(&binary)
+            .arg("-")
+            .current_dir(&self.dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("couldn't run {}: {e}", binary.display()))?;
+
+        // Write and read on threads of their own, so none of them waits on a full pipe
+        let input = input.to_vec();
+        let mut stdin = child.stdin.take();
+        thread::spawn(move || stdin.as_mut().map(|stdin| stdin.write_all(&input)));
+        let stdout = read_all(child.stdout.take());
+        let stderr = read_all(child.stderr.take());
+
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if start.elapsed() > timeout => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Ok(None);
+                }
+                Ok(None) => thread::sleep(Duration::from_millis(1)),
+                Err(e) => return Err(format!("couldn't wait for {}: {e}", binary.display())),
+            }
+        };
+        let runtime = start.elapsed();
+        let mut output = stdout.join().unwrap_or_default();
+        if !status.success() {
+            output += &stderr.join().unwrap_or_default();
+        }
+        Ok(Some(Run {
+            output,
+            runtime,
+            success: status.success(),
+        }))
+    }
+
+    /// Whether it's built, and has an input to run on
+    fn runnable(&self) -> bool {
+        (self.year == 2020 || self.input().is_file()) && self.binary().is_file()
+    }
+
+    /// The binary, in its directory, with its input
+    fn command(&self) -> Command {
+        let mut command = Command::new(self.binary());
+        command
+            .args(self.input_args())
+            .current_dir(&self.dir)
+            .stdin(Stdio::null());
+        command
+    }
+
+    /// The arguments it's run with, in its directory: its input. The 2020 days find their
+    /// input themselves.
+    pub fn input_args(&self) -> Vec<String> {
+        if self.year == 2020 {
+            Vec::new()
+        } else {
+            vec![String::from("input/1.txt")]
+        }
+    }
+
+    /// Where `cargo build --release` puts the binary
+    pub fn binary(&self) -> PathBuf {
+        self.target()
+            .join("release")
+            .join(format!("aoc-{}-{:02}", self.year, self.day))
+    }
+
+    /// Where `cargo build --release --target wasm32-wasip1` puts the module
+    #[cfg(feature = "wasm")]
+    pub fn wasm_module(&self) -> PathBuf {
+        self.target()
+            .join(WASM_TARGET)
+            .join("release")
+            .join(format!("aoc-{}-{:02}.wasm", self.year, self.day))
+    }
+
+    fn target(&self) -> PathBuf {
+        if self.year == 2020 {
+            self.dir.join("target")
+        } else {
+            self.dir.join("../target")
+        }
+    }
+}
+
+/// Reads all of a pipe on another thread, as text
+fn read_all(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+/// The years with solutions, newest first
+pub fn years(root: &Path) -> Vec<u16> {
+    let mut years: Vec<u16> = fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .filter(|year| (2015..3000).contains(year))
+        .collect();
+    years.sort_unstable_by(|a, b| b.cmp(a));
+    years
+}
+
+/// The solutions of `year`, by day
+pub fn solutions(root: &Path, year: u16) -> Vec<Solution> {
+    let prefix = format!("aoc-{year}-");
+    let mut solutions: Vec<Solution> = fs::read_dir(root.join(year.to_string()))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let (day, language) = match name
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_suffix(".playground"))
+            {
+                Some(day) => (day, Language::Swift),
+                None if entry.path().join("Cargo.toml").is_file() => {
+                    (name.as_str(), Language::Rust)
+                }
+                None => return None,
+            };
+            let day = day.parse().ok().filter(|day| (1..=25).contains(day))?;
+            Some(Solution {
+                year,
+                day,
+                language,
+                dir: entry.path(),
+            })
+        })
+        .collect();
+    solutions.sort_by_key(|solution| solution.day);
+    solutions
+}
+
+/// The days of `year` with a solution
+pub fn solved_days(root: &Path, year: u16) -> BTreeSet<u8> {
+    solutions(root, year).iter().map(|s| s.day).collect()
+}
+
+/// Runtimes by year and day, see the [module docs](self)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Benchmarks(pub BTreeMap<(u16, u8), Duration>);
+
+impl Benchmarks {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join("benchmarks.tsv")
+    }
+
+    /// The benchmarks in `path`, none if there's no such file. Lines that don't parse are
+    /// skipped.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        let runtimes = text
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t').map(str::trim);
+                let year = fields.next()?.parse().ok()?;
+                let day = fields.next()?.parse().ok()?;
+                let micros = fields.next()?.parse().ok()?;
+                Some(((year, day), Duration::from_micros(micros)))
+            })
+            .collect();
+        Ok(Benchmarks(runtimes))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text: String = self
+            .0
+            .iter()
+            .map(|((year, day), runtime)| format!("{year}\t{day}\t{}\n", runtime.as_micros()))
+            .collect();
+        fs::write(path, text)
+    }
+}
+
+/// Builds the release binaries of `solutions`, all of the same year
+pub fn build(root: &Path, year: u16, solutions: &[Solution]) -> Result<(), String> {
+    let dirs: Vec<PathBuf> = if year == 2020 {
+        solutions.iter().map(|s| s.dir.clone()).collect()
+    } else {
+        vec![root.join(year.to_string())]
+    };
+    for dir in dirs {
+        let status = Command::new("cargo")
+            .args(["build", "--release", "--quiet", "--bins"])
+            .current_dir(&dir)
+            .status()
+            .map_err(|e| format!("couldn't run cargo: {e}"))?;
+        if !status.success() {
+            eprintln!("  {} doesn't build, skipping it", dir.display());
+        }
+    }
+    Ok(())
+}
+
+/// The fastest of a few runs of a built solution on [its input](Solution::input), or `None`
+/// if it doesn't have one, isn't built or fails
+pub fn time(solution: &Solution) -> Option<Duration> {
+    const RUNS: usize = 3;
+    const ENOUGH: Duration = Duration::from_secs(10);
+
+    if !solution.runnable() {
+        return None;
+    }
+
+    let mut fastest: Option<Duration> = None;
+    let started = Instant::now();
+    for _ in 0..RUNS {
+        let mut command = solution.command();
+        let start = Instant::now();
+        let status = command
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .ok()?;
+        if !status.success() {
+            return None;
+        }
+        let runtime = start.elapsed();
+        fastest = Some(fastest.map_or(runtime, |fastest| fastest.min(runtime)));
+        if started.elapsed() > ENOUGH {
+            break;
+        }
+    }
+    fastest
+}
@@ -0,0 +1,437 @@
+//! `aoc-serve`: the solutions over HTTP, for tools that have an input and want its answers.
+//!
+//! ```text
+//! aoc-serve [--listen=127.0.0.1:8025] [--max-input=<bytes>] [--timeout=<seconds>] [--jobs=<n>]
+//! ```
+//!
+//! It builds every Rust day from 2022 on at startup, then answers:
+//!
+//! - `GET /solutions`: the days it can solve, as `[[2022, 1], [2022, 2], …]`
+//! - `POST /solve/<year>/<day>` with an input as the body:
+//!   `{"year": 2022, "day": 15, "answers": ["…", "…"], "runtime_ms": 870.2}`, with the lines the
+//!   solution printed as answers, usually part 1 and part 2.
+//!
+//! Inputs over `--max-input` get a 413, solutions running longer than `--timeout` are killed
+//! with a 504, and requests beyond `--jobs` running at once get a 503 straight away. Errors are
+//! `{"error": "…"}`.
+
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use aoc_tools::registry::{self, repository, Language, Solution};
+
+const USAGE: &str = "\
+usage: aoc-serve [--listen=127.0.0.1:8025] [--max-input=<bytes>] [--timeout=<seconds>] [--jobs=<n>]
+
+Serves the solutions over HTTP: POST an input to /solve/<year>/<day> for its answers.";
+
+/// What a request may ask for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Limits {
+    /// The largest input, in bytes
+    max_input: usize,
+    /// How long a solution may run
+    timeout: Duration,
+    /// How many requests are handled at once
+    jobs: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_input: 1 << 20,
+            timeout: Duration::from_secs(30),
+            jobs: thread::available_parallelism().map_or(4, usize::from),
+        }
+    }
+}
+
+/// What a solution printed for an input
+#[derive(Debug, Clone, PartialEq)]
+struct Solved {
+    answers: Vec<String>,
+    runtime: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SolveError {
+    /// There's no solution for that day
+    Unknown,
+    /// The solution failed on the input, with what it printed
+    Failed(String),
+    TimedOut,
+    /// The solution couldn't be run at all
+    Unavailable(String),
+}
+
+/// Where the answers come from: the solutions' binaries, or a stand-in in tests
+trait Solver: Send + Sync {
+    fn days(&self) -> Vec<(u16, u8)>;
+
+    fn solve(
+        &self,
+        year: u16,
+        day: u8,
+        input: &[u8],
+        timeout: Duration,
+    ) -> Result<Solved, SolveError>;
+}
+
+/// The built solutions that read their input from standard input
+struct Binaries(BTreeMap<(u16, u8), Solution>);
+
+impl Binaries {
+    /// Builds the solutions of every year, leaving out those that don't build. The 2020 days
+    /// read their own input, so they can't be served.
+    fn build() -> Result<Self, String> {
+        let root = repository();
+        let mut solutions = BTreeMap::new();
+        for year in registry::years(&root)
+            .into_iter()
+            .filter(|&year| year > 2020)
+        {
+            let rust: Vec<Solution> = registry::solutions(&root, year)
+                .into_iter()
+                .filter(|s| s.language == Language::Rust)
+                .collect();
+            if rust.is_empty() {
+                continue;
+            }
+            eprintln!("building {year}");
+            registry::build(&root, year, &rust)?;
+            for solution in rust.into_iter().filter(|s| s.binary().is_file()) {
+                solutions.insert((solution.year, solution.day), solution);
+            }
+        }
+        Ok(Binaries(solutions))
+    }
+}
+
+impl Solver for Binaries {
+    fn days(&self) -> Vec<(u16, u8)> {
+        self.0.keys().copied().collect()
+    }
+
+    fn solve(
+        &self,
+        year: u16,
+        day: u8,
+        input: &[u8],
+        timeout: Duration,
+    ) -> Result<Solved, SolveError> {
+        let solution = self.0.get(&(year, day)).ok_or(SolveError::Unknown)?;
+        match solution.run_on(input, timeout) {
+            Ok(Some(run)) if run.success => Ok(Solved {
+                answers: run.answers(),
+                runtime: run.runtime,
+            }),
+            Ok(Some(run)) => Err(SolveError::Failed(run.output)),
+            Ok(None) => Err(SolveError::TimedOut),
+            Err(e) => Err(SolveError::Unavailable(e)),
+        }
+    }
+}
+
+type Reply = Response<Cursor<Vec<u8>>>;
+
+fn reply(status: u16, body: &Value) -> Reply {
+    let json = Header::from_bytes("Content-Type", "application/json").expect("a valid header");
+    Response::from_data(body.to_string())
+        .with_status_code(status)
+        .with_header(json)
+}
+
+fn error(status: u16, message: &str) -> Reply {
+    reply(status, &json!({ "error": message }))
+}
+
+/// Answers a request, once it's been given one of the jobs
+fn handle(request: &mut Request, solver: &dyn Solver, limits: Limits) -> Reply {
+    let path: Vec<&str> = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let (year, day) = match (request.method(), path.as_slice()) {
+        (Method::Get, ["solutions"]) => return reply(200, &json!(solver.days())),
+        (Method::Post, ["solve", year, day]) => match (year.parse(), day.parse()) {
+            (Ok(year), Ok(day)) => (year, day),
+            _ => return error(404, "not a year and day"),
+        },
+        (_, ["solutions"] | ["solve", _, _]) => return error(405, "method not allowed"),
+        _ => return error(404, "not found"),
+    };
+
+    let too_large = format!("inputs are at most {} bytes", limits.max_input);
+    if request
+        .body_length()
+        .is_some_and(|length| length > limits.max_input)
+    {
+        return error(413, &too_large);
+    }
+    let mut input = Vec::new();
+    let limit = limits.max_input as u64 + 1;
+    if let Err(e) = request.as_reader().take(limit).read_to_end(&mut input) {
+        return error(400, &format!("couldn't read the input: {e}"));
+    }
+    if input.len() > limits.max_input {
+        return error(413, &too_large);
+    }
+
+    match solver.solve(year, day, &input, limits.timeout) {
+        Ok(solved) => reply(
+            200,
+            &json!({
+                "year": year,
+                "day": day,
+                "answers": solved.answers,
+                "runtime_ms": solved.runtime.as_secs_f64() * 1000.0,
+            }),
+        ),
+        Err(SolveError::Unknown) => error(404, &format!("no solution for {year} day {day}")),
+        Err(SolveError::Failed(output)) => reply(
+            422,
+            &json!({ "error": "the solution failed on this input", "output": output }),
+        ),
+        Err(SolveError::TimedOut) => error(
+            504,
+            &format!("the solution took longer than {:?}", limits.timeout),
+        ),
+        Err(SolveError::Unavailable(e)) => error(500, &e),
+    }
+}
+
+/// Gives back a job when the request is done with it, even if handling it panicked
+struct Job(Arc<AtomicUsize>);
+
+impl Drop for Job {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Handles the requests of `server`, each on a thread of its own, until it's unblocked
+fn serve(server: &Server, solver: Arc<dyn Solver>, limits: Limits) {
+    let running = Arc::new(AtomicUsize::new(0));
+    for mut request in server.incoming_requests() {
+        let busy = running.fetch_add(1, Ordering::SeqCst) >= limits.jobs;
+        let job = Job(Arc::clone(&running));
+        if busy {
+            let busy = "too many requests at once, try again in a moment";
+            let _ = request.respond(
+                error(503, busy)
+                    .with_header(Header::from_bytes("Retry-After", "1").expect("a valid header")),
+            );
+            continue;
+        }
+        let solver = Arc::clone(&solver);
+        thread::spawn(move || {
+            let reply = handle(&mut request, solver.as_ref(), limits);
+            let _ = request.respond(reply);
+            drop(job);
+        });
+    }
+}
+
+fn main() -> ExitCode {
+    let mut listen = String::from("127.0.0.1:8025");
+    let mut limits = Limits::default();
+    for arg in std::env::args().skip(1) {
+        let parsed = match arg.split_once('=') {
+            Some(("--listen", address)) => {
+                listen = address.to_owned();
+                true
+            }
+            Some(("--max-input", bytes)) => bytes.parse().map(|n| limits.max_input = n).is_ok(),
+            Some(("--timeout", seconds)) => seconds
+                .parse()
+                .ok()
+                .and_then(|s| Duration::try_from_secs_f64(s).ok())
+                .map(|timeout| limits.timeout = timeout)
+                .is_some(),
+            Some(("--jobs", jobs)) => jobs.parse().map(|n| limits.jobs = n).is_ok(),
+            _ => false,
+        };
+        if !parsed {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let solver = match Binaries::build() {
+        Ok(solver) => solver,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let server = match Server::http(&listen) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("error: couldn't listen on {listen}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!(
+        "serving {} solutions on http://{}",
+        solver.0.len(),
+        server.server_addr()
+    );
+    serve(&server, Arc::new(solver), limits);
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write;
+    use std::net::{SocketAddr, TcpStream};
+    use std::sync::mpsc::{self, Receiver, Sender};
+    use std::sync::Mutex;
+
+    /// Solves 2022 day 15 by counting lines. `wait` waits to be let go, `sleep` runs out of
+    /// time and `fail` fails.
+    struct StandIn {
+        waiting: Mutex<Sender<()>>,
+        go: Mutex<Receiver<()>>,
+    }
+
+    impl Solver for StandIn {
+        fn days(&self) -> Vec<(u16, u8)> {
+            vec![(2022, 15)]
+        }
+
+        fn solve(
+            &self,
+            year: u16,
+            day: u8,
+            input: &[u8],
+            timeout: Duration,
+        ) -> Result<Solved, SolveError> {
+            if (year, day) != (2022, 15) {
+                return Err(SolveError::Unknown);
+            }
+            match input {
+                b"wait" => {
+                    self.waiting.lock().unwrap().send(()).unwrap();
+                    self.go.lock().unwrap().recv().unwrap();
+                }
+                b"sleep" => {
+                    thread::sleep(timeout);
+                    return Err(SolveError::TimedOut);
+                }
+                b"fail" => return Err(SolveError::Failed(String::from("panicked"))),
+                _ => {}
+            }
+            let lines = input.split(|&b| b == b'\n').count();
+            Ok(Solved {
+                answers: vec![lines.to_string(), String::from("2")],
+                runtime: Duration::from_millis(5),
+            })
+        }
+    }
+
+    /// A server on a free port, with its stand-in's waiting and go channels
+    fn start(limits: Limits) -> (SocketAddr, Receiver<()>, Sender<()>) {
+        let (waiting, waited) = mpsc::channel();
+        let (go, went) = mpsc::channel();
+        let solver = StandIn {
+            waiting: Mutex::new(waiting),
+            go: Mutex::new(went),
+        };
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let address = server.server_addr().to_ip().unwrap();
+        thread::spawn(move || serve(&server, Arc::new(solver), limits));
+        (address, waited, go)
+    }
+
+    /// The status and JSON body of a request
+    fn request(address: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    fn limits() -> Limits {
+        Limits {
+            max_input: 16,
+            timeout: Duration::from_millis(50),
+            jobs: 1,
+        }
+    }
+
+    #[test]
+    fn solves() {
+        let (address, _, _) = start(limits());
+        let (status, body) = request(address, "POST", "/solve/2022/15", "1\n2\n3");
+        assert_eq!(status, 200);
+        assert_eq!(body["year"], 2022);
+        assert_eq!(body["day"], 15);
+        assert_eq!(body["answers"], json!(["3", "2"]));
+        assert_eq!(body["runtime_ms"], 5.0);
+        assert_eq!(
+            request(address, "GET", "/solutions", ""),
+            (200, json!([[2022, 15]]))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let (address, _, _) = start(limits());
+        let status = |method, path, body| request(address, method, path, body).0;
+        assert_eq!(status("POST", "/solve/2022/16", ""), 404);
+        assert_eq!(status("POST", "/solve/2022/fifteen", ""), 404);
+        assert_eq!(status("POST", "/nowhere", ""), 404);
+        assert_eq!(status("GET", "/solve/2022/15", ""), 405);
+        assert_eq!(status("POST", "/solve/2022/15", "seventeen bytes!!"), 413);
+        assert_eq!(status("POST", "/solve/2022/15", "sleep"), 504);
+        let (failed, body) = request(address, "POST", "/solve/2022/15", "fail");
+        assert_eq!(failed, 422);
+        assert_eq!(body["output"], "panicked");
+    }
+
+    #[test]
+    fn limits_concurrency() {
+        let (address, waited, go) = start(limits());
+        let waiting = thread::spawn(move || request(address, "POST", "/solve/2022/15", "wait"));
+        waited.recv().unwrap();
+
+        let (status, body) = request(address, "POST", "/solve/2022/15", "1");
+        assert_eq!(status, 503);
+        assert_eq!(
+            body["error"],
+            "too many requests at once, try again in a moment"
+        );
+
+        go.send(()).unwrap();
+        assert_eq!(waiting.join().unwrap().0, 200);
+        // The job is given back after the response is sent
+        let free = (0..100).any(|_| {
+            thread::sleep(Duration::from_millis(10));
+            request(address, "POST", "/solve/2022/15", "1").0 == 200
+        });
+        assert!(free);
+    }
+}
@@ -0,0 +1,168 @@
+//! Running the solutions as WebAssembly, to check they give the same answers as natively.
+//!
+//! Every Rust day builds for `wasm32-wasip1`. The modules run in an embedded wasmtime with
+//! only their own directory to read from, mounted as `.`, so `input/1.txt` is where it is for
+//! the native binary.
+
+use std::path::Path;
+use std::time::Instant;
+
+use wasmtime::{Engine, Linker, Module, Store};
+use wasmtime_wasi::pipe::MemoryOutputPipe;
+use wasmtime_wasi::preview1::{self, WasiP1Ctx};
+use wasmtime_wasi::{DirPerms, FilePerms, I32Exit, WasiCtxBuilder};
+
+use aoc_tools::registry::{self, Language, Run, Solution};
+
+/// How much output a module may write
+const OUTPUT_CAPACITY: usize = 64 << 20;
+
+/// Runs the WASI module at `module`, binary or text, with `args`, with `dir` as its current directory
+pub fn run(engine: &Engine, module: &Path, dir: &Path, args: &[String]) -> Result<Run, String> {
+    let name = module
+        .file_stem()
+        .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+    let module = Module::from_file(engine, module)
+        .map_err(|e| format!("couldn't load {}: {e}", module.display()))?;
+
+    let stdout = MemoryOutputPipe::new(OUTPUT_CAPACITY);
+    let stderr = MemoryOutputPipe::new(OUTPUT_CAPACITY);
+    let wasi = WasiCtxBuilder::new()
+        .arg(&name)
+        .args(args)
+        .stdout(stdout.clone())
+        .stderr(stderr.clone())
+        .preopened_dir(dir, ".", DirPerms::READ, FilePerms::READ)
+        .map_err(|e| format!("couldn't open {}: {e}", dir.display()))?
+        .build_p1();
+
+    let mut linker: Linker<WasiP1Ctx> = Linker::new(engine);
+    preview1::add_to_linker_sync(&mut linker, |wasi| wasi).map_err(|e| e.to_string())?;
+    let mut store = Store::new(engine, wasi);
+    let start = Instant::now();
+    let result = linker
+        .instantiate(&mut store, &module)
+        .and_then(|instance| instance.get_typed_func::<(), ()>(&mut store, "_start"))
+        .and_then(|start| start.call(&mut store, ()));
+    let runtime = start.elapsed();
+
+    let success = match &result {
+        Ok(()) => true,
+        Err(e) => e.downcast_ref::<I32Exit>().is_some_and(|exit| exit.0 == 0),
+    };
+    let mut output = String::from_utf8_lossy(&stdout.contents()).into_owned();
+    if !success {
+        output += &String::from_utf8_lossy(&stderr.contents());
+        // Exiting with a status isn't worth mentioning, traps like panics are
+        if let Some(e) = result.as_ref().err().filter(|e| !e.is::<I32Exit>()) {
+            output += &format!("{}\n", e.root_cause());
+        }
+    }
+    Ok(Run {
+        output,
+        runtime,
+        success,
+    })
+}
+
+/// Runs every Rust solution of `years` with an input both natively and as WebAssembly, and
+/// reports those that don't give the same output
+pub fn compare(root: &Path, years: &[u16]) -> Result<(), String> {
+    let engine = Engine::default();
+    let mut differing = Vec::new();
+    for &year in years {
+        let solutions: Vec<Solution> = registry::solutions(root, year)
+            .into_iter()
+            .filter(|s| s.language == Language::Rust && (year == 2020 || s.input().is_file()))
+            .collect();
+        for solution in &solutions {
+            let Solution { year, day, .. } = *solution;
+            let native = solution.build().and_then(|()| solution.run(&[]));
+            let wasm = solution.build_wasm().and_then(|()| {
+                let args = solution.input_args();
+                run(&engine, &solution.wasm_module(), &solution.dir, &args)
+            });
+            match (native, wasm) {
+                (Ok(native), Ok(wasm)) if native.success && native.output == wasm.output => {
+                    println!(
+                        "{year} {day:2}  same    {:>8.1?} native, {:>8.1?} WebAssembly",
+                        native.runtime, wasm.runtime
+                    );
+                }
+                (Ok(native), Ok(wasm)) if native.success => {
+                    println!("{year} {day:2}  differs");
+                    println!("  native:\n{}", indent(&native.output));
+                    println!("  WebAssembly:\n{}", indent(&wasm.output));
+                    differing.push((year, day));
+                }
+                (Ok(_), Ok(_)) => println!("{year} {day:2}  fails natively, skipped"),
+                (Err(_), _) => println!("{year} {day:2}  doesn't build natively, skipped"),
+                (_, Err(e)) => {
+                    println!("{year} {day:2}  doesn't build or run as WebAssembly\n{e}");
+                    differing.push((year, day));
+                }
+            }
+        }
+    }
+    if differing.is_empty() {
+        Ok(())
+    } else {
+        let days: Vec<String> = differing
+            .iter()
+            .map(|(year, day)| format!("{year}/{day:02}"))
+            .collect();
+        Err(format!("not the same as WebAssembly: {}", days.join(", ")))
+    }
+}
+
+fn indent(text: &str) -> String {
+    text.lines().map(|line| format!("    {line}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    /// Prints "42", and exits with the status in its first argument's length
+    const MODULE: &str = r#"
+        (module
+            (import "wasi_snapshot_preview1" "fd_write"
+                (func $fd_write (param i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "args_sizes_get"
+                (func $args_sizes_get (param i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 16) "42\n")
+            (func (export "_start")
+                (i32.store (i32.const 0) (i32.const 16))
+                (i32.store (i32.const 4) (i32.const 3))
+                (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8)))
+                (drop (call $args_sizes_get (i32.const 32) (i32.const 36)))
+                ;; The program name and "ok" take 7 + 3 bytes
+                (if (i32.ne (i32.load (i32.const 36)) (i32.const 10))
+                    (then (call $proc_exit (i32.const 1))))))
+    "#;
+
+    #[test]
+    fn runs_modules() {
+        let dir = std::env::temp_dir().join(format!("aoc-wasm-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        // wasmtime takes the text format too
+        let module = dir.join("answer.wat");
+        fs::write(&module, MODULE).unwrap();
+
+        let engine = Engine::default();
+        let run = |arg: &str| super::run(&engine, &module, &dir, &[arg.to_owned()]).unwrap();
+        let ok = run("ok");
+        assert!(ok.success);
+        assert_eq!(ok.output, "42\n");
+        assert!(ok.gave("42"));
+        let failed = run("not ok");
+        assert!(!failed.success);
+        assert_eq!(failed.output, "42\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}